@@ -54,14 +54,13 @@ fn counter(parent: ui::CommonRef, aux: &mut Aux) -> View<AppAux, i32> {
         view.get_mut(label)
             .unwrap()
             .set_text(format!("Count: {}", count));
-        layout::update_layout(view);
     });
 
     view.set_state(|_| {});
 
-    let mut rb = layout::RelativeBox::new(layout::RelativeBoxConfig::center()).into_node(None);
-    rb.push(&view, ());
-    aux.central_widget.with(|x| x.set_layout(rb));
+    let mut rb = layout::RelativeBox::new().into_node(None);
+    rb.push(&view, layout::RelativeBoxConfig::center());
+    aux.set_root_layout(rb);
     view.set_layout_mode(ui::LayoutMode::Shrink);
 
     view
@@ -126,18 +126,18 @@ impl<T: 'static> TodoItemList<T> {
         view.button(aux)
             .text("Clear items")
             .layout(&mut vstack, Some((0.0, 5.0).into()))
-            .press(|view, aux, _| TodoItemList::clear_items(view, aux));
+            .press(|view, _, _| TodoItemList::clear_items(view));
 
         view.button(aux)
             .text("Next filter")
             .layout(&mut vstack, Some((0.0, 5.0).into()))
-            .press(|view, aux, _| {
+            .press(|view, _, _| {
                 view.set_state(|x| match x.filter {
                     ItemFilter::All => x.filter = ItemFilter::Completed,
                     ItemFilter::Completed => x.filter = ItemFilter::Incomplete,
                     ItemFilter::Incomplete => x.filter = ItemFilter::All,
                 });
-                Self::filter_items(view, aux);
+                Self::filter_items(view);
             });
 
         let mut tb = kit::ComboBox::new(view.common().clone(), aux);
@@ -175,9 +175,9 @@ impl<T: 'static> TodoItemList<T> {
 
         view.set_layout(vstack);
 
-        let mut rb = layout::RelativeBox::new(layout::RelativeBoxConfig::center()).into_node(None);
-        rb.push(&view, ());
-        aux.central_widget.with(|x| x.set_layout(rb));
+        let mut rb = layout::RelativeBox::new().into_node(None);
+        rb.push(&view, layout::RelativeBoxConfig::center());
+        aux.set_root_layout(rb);
 
         view.set_layout_mode(ui::LayoutMode::Shrink);
 
@@ -197,26 +197,24 @@ impl<T: 'static> TodoItemList<T> {
                 .push(item_c, Some((0.0, 5.0).into()));
         });
 
-        view.late_handle(item, |view, aux, _: &TodoItemCompletionEvent| {
-            Self::filter_items(view, aux)
+        view.late_handle(item, |view, _, _: &TodoItemCompletionEvent| {
+            Self::filter_items(view)
         });
 
         view.set_state(move |state| state.items.push(item));
-        Self::filter_items(view, aux);
+        Self::filter_items(view);
     }
 
-    pub fn clear_items(view: &mut View<T, TodoItemList<T>>, aux: &mut ui::Aux<T>) {
+    pub fn clear_items(view: &mut View<T, TodoItemList<T>>) {
         for item in view.state().items.clone() {
             view.get(item).unwrap().set_visible(ui::Visibility::None);
             view.get(item).unwrap().mark_for_detach();
             view.remove(item);
         }
         view.set_state(|x| x.items.clear());
-        layout::update_layout(view);
-        layout::update_direct_layout(&aux.central_widget);
     }
 
-    fn filter_items(view: &mut View<T, TodoItemList<T>>, aux: &mut ui::Aux<T>) {
+    fn filter_items(view: &mut View<T, TodoItemList<T>>) {
         let filter = view.state().filter;
         for item in view.state().items.clone() {
             if filter == ItemFilter::All {
@@ -237,8 +235,6 @@ impl<T: 'static> TodoItemList<T> {
             }
             ui::propagate_visibility(view.get_mut(item).unwrap());
         }
-        layout::update_layout(view);
-        layout::update_direct_layout(&aux.central_widget);
     }
 
     fn submit_item(
@@ -250,7 +246,6 @@ impl<T: 'static> TodoItemList<T> {
         if !text.is_empty() {
             TodoItemList::add_item(view, aux, text);
             view.get_mut(tb).unwrap().set_text("");
-            layout::update_direct_layout(&aux.central_widget);
         }
     }
 }
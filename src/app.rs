@@ -1,10 +1,14 @@
 use {
     crate::{prelude::*, theme, ui},
     glutin::event::{self as winit_event, Event, WindowEvent},
+    raw_window_handle::{HasRawWindowHandle, RawWindowHandle},
     reclutch::display::{self as gfx, GraphicsDisplay},
     thiserror::Error,
 };
 
+#[cfg(feature = "gamepad")]
+pub mod gamepad;
+
 #[derive(Error, Debug)]
 pub enum AppError {
     #[error("{0}")]
@@ -13,12 +17,27 @@ pub enum AppError {
     CreationError(#[from] glutin::CreationError),
     #[error("{0}")]
     SkiaError(#[from] reclutch::error::SkiaError),
+    #[error("{0}")]
+    BadIcon(#[from] glutin::window::BadIcon),
+    #[error("The OpenGL function {0} is unavailable")]
+    MissingGlFunction(&'static str),
+    #[cfg(feature = "gamepad")]
+    #[error("{0}")]
+    GamepadError(#[from] gilrs::Error),
 }
 
 type RootReadWrites<T, U> = (ui::Write<T>, ui::Write<AppAux<U>>);
 
+/// Vertical distance between the cursor and a tooltip.
+const TOOLTIP_OFFSET: f32 = 18.;
+/// Distance between the status indicator and the window edges.
+const STATUS_MARGIN: f32 = 10.;
+
 pub struct Root<T: 'static, W: ui::WidgetChildren<AppData<T>>> {
     child: W,
+    tooltip: crate::kit::Tooltip<AppData<T>>,
+    hover: Option<(u64, std::time::Instant)>,
+    status: crate::kit::StatusIndicator<AppData<T>>,
 
     common: ui::CommonRef,
     listeners: ui::ListenerList<RootReadWrites<Self, T>>,
@@ -36,6 +55,9 @@ impl<T: 'static, W: ui::WidgetChildren<AppData<T>>> ui::Element for Root<T, W> {
     fn update(&mut self, aux: &mut AppAux<T>) {
         ui::dispatch_components(self, aux, |x| &mut x.components).unwrap();
         ui::dispatch_list::<RootReadWrites<Self, T>, _>((self, aux), |(x, _)| &mut x.listeners);
+        self.update_tooltip(aux);
+        self.update_status(aux);
+        aux.cursor_icon = ui::cursor_at(&self.child, aux.data.cursor).unwrap_or_default();
     }
 
     #[inline]
@@ -69,8 +91,16 @@ impl<T: 'static, W: ui::WidgetChildren<AppData<T>>> Root<T, W> {
             },
         );
 
+        let tooltip = crate::kit::Tooltip::new(common.clone(), aux);
+        tooltip.set_visible(ui::Visibility::None);
+        let status = crate::kit::StatusIndicator::new(common.clone(), aux);
+        status.set_visible(ui::Visibility::None);
+
         Root {
             child: new(common.clone(), aux),
+            tooltip,
+            hover: None,
+            status,
 
             common,
             listeners: ui::ListenerList::new(vec![focus_listener]),
@@ -82,15 +112,333 @@ impl<T: 'static, W: ui::WidgetChildren<AppData<T>>> Root<T, W> {
             )),
         }
     }
+
+    /// Shows the tooltip of the widget under the cursor once the cursor has rested there long enough.
+    fn update_tooltip(&mut self, aux: &mut AppAux<T>) {
+        let cursor = aux.data.cursor;
+        let (id, text) = match ui::tooltip_at(&self.child, cursor) {
+            Some(x) => x,
+            None => {
+                self.hover = None;
+                self.tooltip.set_visible(ui::Visibility::None);
+                return;
+            }
+        };
+
+        let since = match self.hover {
+            Some((hover_id, since)) if hover_id == id => since,
+            _ => {
+                let now = std::time::Instant::now();
+                self.hover = Some((id, now));
+                self.tooltip.set_visible(ui::Visibility::None);
+                now
+            }
+        };
+
+        if since.elapsed() < aux.interaction.tooltip_delay
+            || self.tooltip.visible() != ui::Visibility::None
+        {
+            return;
+        }
+
+        if self.tooltip.text() != text {
+            self.tooltip.set_text(text);
+        }
+
+        // keep the tooltip within the window, flipping above the cursor if there's no room below.
+        let size = self.tooltip.size();
+        let bounds = self.size();
+        let x = cursor.x.min(bounds.width - size.width).max(0.);
+        let y = if cursor.y + TOOLTIP_OFFSET + size.height > bounds.height {
+            cursor.y - size.height
+        } else {
+            cursor.y + TOOLTIP_OFFSET
+        };
+        self.tooltip.set_position(gfx::Point::new(x, y.max(0.)));
+        self.tooltip.set_visible(ui::Visibility::All);
+    }
+
+    /// Keeps the status indicator in sync with [`AppData::status`](AppData::status), in the bottom-left corner.
+    fn update_status(&mut self, aux: &mut AppAux<T>) {
+        aux.data.status.expire();
+
+        let text = match aux.data.status.message() {
+            Some(text) => text,
+            None => {
+                self.status.set_visible(ui::Visibility::None);
+                return;
+            }
+        };
+
+        let progress = aux.data.status.progress();
+        if self.status.text() != text || self.status.progress() != progress {
+            self.status.set_status(text, progress);
+        }
+
+        let size = self.status.size();
+        self.status.set_position(gfx::Point::new(
+            STATUS_MARGIN,
+            self.size().height - size.height - STATUS_MARGIN,
+        ));
+        self.status.set_visible(ui::Visibility::All);
+    }
 }
 
 impl<T: 'static, W: ui::WidgetChildren<AppData<T>>> ui::WidgetChildren<AppData<T>> for Root<T, W> {
-    crate::children![for <AppData<T>>; child];
+    crate::children![for <AppData<T>>; child, status, tooltip];
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Clipboard backed by the OS clipboard.
+struct OsClipboard(copypasta::ClipboardContext);
+
+impl ui::Clipboard for OsClipboard {
+    fn get_text(&mut self) -> Option<String> {
+        use copypasta::ClipboardProvider;
+        self.0.get_contents().ok()
+    }
+
+    fn set_text(&mut self, text: String) {
+        use copypasta::ClipboardProvider;
+        let _ = self.0.set_contents(text);
+    }
+}
+
+/// Returns the OS clipboard, falling back to a [`LocalClipboard`](ui::LocalClipboard) if it is unavailable.
+fn os_clipboard() -> Box<dyn ui::Clipboard> {
+    use copypasta::ClipboardProvider;
+    match copypasta::ClipboardContext::new() {
+        Ok(ctxt) => Box::new(OsClipboard(ctxt)),
+        Err(_) => Box::new(ui::LocalClipboard::default()),
+    }
+}
+
+/// Returns the interaction settings of the OS, keeping the defaults for any which can't be read.
+///
+/// Only Windows is queried (the double-click time, drag distance and keyboard repeat); other platforms get the defaults,
+/// as winit doesn't expose these settings.
+fn os_interaction_config() -> ui::InteractionConfig {
+    #[allow(unused_mut)]
+    let mut config = ui::InteractionConfig::default();
+
+    #[cfg(windows)]
+    {
+        use winapi::um::winuser;
+
+        // SAFETY: these calls only read system settings, into locals of the sizes the settings are documented to have.
+        unsafe {
+            config.double_click_interval =
+                std::time::Duration::from_millis(winuser::GetDoubleClickTime() as _);
+
+            let drag = winuser::GetSystemMetrics(winuser::SM_CXDRAG);
+            if drag > 0 {
+                config.drag_threshold = drag as _;
+            }
+
+            let mut delay: u32 = 0;
+            if winuser::SystemParametersInfoW(
+                winuser::SPI_GETKEYBOARDDELAY,
+                0,
+                &mut delay as *mut u32 as *mut _,
+                0,
+            ) != 0
+            {
+                // ranges from 0 (about 250 ms) to 3 (about 1 s).
+                config.key_repeat_delay =
+                    std::time::Duration::from_millis(250 * (delay as u64 + 1));
+            }
+
+            let mut speed: u32 = 0;
+            if winuser::SystemParametersInfoW(
+                winuser::SPI_GETKEYBOARDSPEED,
+                0,
+                &mut speed as *mut u32 as *mut _,
+                0,
+            ) != 0
+            {
+                // ranges from 0 (about 2.5 repeats per second) to 31 (about 30).
+                let rate = 2.5 + speed.min(31) as f32 * (30. - 2.5) / 31.;
+                config.key_repeat_interval = std::time::Duration::from_secs_f32(1. / rate);
+            }
+        }
+    }
+
+    config
+}
+
+/// Ephemeral status message with optional progress (e.g. "Saving… 45%"), shown in the corner of the window.
+///
+/// This is available through [`AppData::status`](AppData::status) so that background tasks have a standard way to surface progress.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Status {
+    message: Option<(String, Option<f32>)>,
+    expiry: Option<std::time::Instant>,
+}
+
+impl Status {
+    /// Shows a message which is hidden again after `duration`, or when replaced or cleared if `None`.
+    pub fn show(
+        &mut self,
+        text: impl Into<String>,
+        duration: impl Into<Option<std::time::Duration>>,
+    ) {
+        self.message = Some((text.into(), None));
+        self.expiry = duration
+            .into()
+            .map(|duration| std::time::Instant::now() + duration);
+    }
+
+    /// Shows a message with progress (from `0.0` to `1.0`), until replaced or cleared.
+    pub fn show_progress(&mut self, text: impl Into<String>, progress: f32) {
+        self.message = Some((text.into(), Some(progress)));
+        self.expiry = None;
+    }
+
+    /// Hides the current message.
+    pub fn clear(&mut self) {
+        self.message = None;
+        self.expiry = None;
+    }
+
+    /// Returns the current message, if there is one.
+    #[inline]
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_ref().map(|(text, _)| text.as_str())
+    }
+
+    /// Returns the progress of the current message, if it has any.
+    #[inline]
+    pub fn progress(&self) -> Option<f32> {
+        self.message.as_ref().and_then(|&(_, progress)| progress)
+    }
+
+    fn expire(&mut self) {
+        if self
+            .expiry
+            .map(|expiry| std::time::Instant::now() >= expiry)
+            .unwrap_or(false)
+        {
+            self.clear();
+        }
+    }
+}
+
+/// Request from the UI to the window, made through [`WindowControls`](WindowControls).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WindowRequest {
+    /// Move the window with the cursor until the left mouse button is released.
+    Drag,
+    Minimize,
+    SetMaximized(bool),
+    Close,
+}
+
+/// Hooks for the buttons and drag regions of a custom title bar, for windows without [decorations](AppOptions::decorations).
+///
+/// Requests are applied to the window by [`run`](run) after each update.
+/// Hosts of a [`Runner`](Runner) apply them themselves, having [taken](WindowControls::take_requests) them.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct WindowControls {
+    maximized: bool,
+    requests: Vec<WindowRequest>,
+    handle: Option<WindowHandle>,
+}
+
+impl WindowControls {
+    /// Starts moving the window with the cursor, until the left mouse button is released (see [`drag_region`](drag_region)).
+    pub fn start_drag(&mut self) {
+        self.requests.push(WindowRequest::Drag);
+    }
+
+    pub fn minimize(&mut self) {
+        self.requests.push(WindowRequest::Minimize);
+    }
+
+    pub fn set_maximized(&mut self, maximized: bool) {
+        self.maximized = maximized;
+        self.requests.push(WindowRequest::SetMaximized(maximized));
+    }
+
+    /// Maximizes the window, or restores it if it's maximized; as done by the maximize button of a title bar.
+    pub fn toggle_maximized(&mut self) {
+        self.set_maximized(!self.maximized);
+    }
+
+    /// Returns `true` if the window was last [maximized](WindowControls::set_maximized) rather than restored.
+    #[inline]
+    pub fn maximized(&self) -> bool {
+        self.maximized
+    }
+
+    /// Closes the window, ending [`run`](run), without emitting [`CloseRequestedEvent`](ui::CloseRequestedEvent).
+    pub fn close(&mut self) {
+        self.requests.push(WindowRequest::Close);
+    }
+
+    /// Returns the requests made since the last call, in order.
+    pub fn take_requests(&mut self) -> Vec<WindowRequest> {
+        std::mem::take(&mut self.requests)
+    }
+
+    /// Returns the native handle of the window, for integrating native libraries (e.g. video playback or external renderers) with it.
+    ///
+    /// This is set by [`run`](run) once the window is created; hosts of a [`Runner`](Runner) set it themselves.
+    #[inline]
+    pub fn handle(&self) -> Option<WindowHandle> {
+        self.handle
+    }
+
+    #[inline]
+    pub fn set_handle(&mut self, handle: impl Into<Option<WindowHandle>>) {
+        self.handle = handle.into();
+    }
+}
+
+/// Native handle of a window, as given to libraries which take a [`HasRawWindowHandle`](HasRawWindowHandle).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WindowHandle(pub RawWindowHandle);
+
+impl WindowHandle {
+    /// Returns the handle of `window`.
+    #[inline]
+    pub fn of(window: &impl HasRawWindowHandle) -> Self {
+        WindowHandle(window.raw_window_handle())
+    }
+}
+
+unsafe impl HasRawWindowHandle for WindowHandle {
+    #[inline]
+    fn raw_window_handle(&self) -> RawWindowHandle {
+        self.0
+    }
+}
+
+/// Returns a listener which [starts dragging the window](WindowControls::start_drag) when the left mouse button is pressed within the widget,
+/// making the widget a drag region of a custom title bar.
+pub fn drag_region<T: 'static, W: ui::WidgetChildren<AppData<T>>>(
+    aux: &mut AppAux<T>,
+) -> ui::Listener<crate::kit::ReadWrite<W>> {
+    aux.listen::<crate::kit::ReadWrite<W>>().and_on(
+        aux.id,
+        |(obj, aux), event: &ui::MousePressEvent| {
+            let bounds = obj.bounds();
+            if event
+                .0
+                .with(|&(btn, pos)| btn == ui::MouseButton::Left && bounds.contains(pos))
+                .is_some()
+            {
+                aux.data.window.start_drag();
+            }
+        },
+    )
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct AppData<T> {
     pub data: T,
+    /// Status message shown in the corner of the window.
+    pub status: Status,
+    /// Controls of the window, for custom title bars.
+    pub window: WindowControls,
     cursor: gfx::Point,
 }
 
@@ -98,6 +446,31 @@ pub struct AppData<T> {
 pub struct AppOptions {
     pub window_title: String,
     pub window_size: gfx::Size,
+    /// Whether the window has the title bar and borders of the OS.
+    ///
+    /// Without them, the root widget is expected to draw its own title bar, using [`drag_region`](drag_region) and [`WindowControls`](WindowControls).
+    pub decorations: bool,
+    pub window_icon: Option<RgbaImage>,
+    /// Smallest size the window can be resized to, if limited.
+    pub min_window_size: Option<gfx::Size>,
+    /// Largest size the window can be resized to, if limited.
+    pub max_window_size: Option<gfx::Size>,
+    /// Initial position of the top-left of the window on the desktop, or left to the OS if `None`.
+    pub window_position: Option<gfx::Point>,
+    pub resizable: bool,
+    /// Whether the window starts maximized.
+    pub maximized: bool,
+    /// Whether the window starts as borderless fullscreen on the primary monitor.
+    pub fullscreen: bool,
+    /// Whether presenting a frame waits for the vertical blank of the display.
+    pub vsync: bool,
+    /// Most frames to update and draw per second, or as many as possible if `None`.
+    pub max_fps: Option<f32>,
+    /// Scale of the UI, overriding the scale factor of the monitor the window is on if set.
+    pub scale_factor: Option<f64>,
+    /// Whether to emit gamepad input, including the [navigation](gamepad::Gamepads::set_navigation) key events.
+    #[cfg(feature = "gamepad")]
+    pub gamepad: bool,
 }
 
 impl Default for AppOptions {
@@ -105,6 +478,19 @@ impl Default for AppOptions {
         AppOptions {
             window_title: "Otway UI".into(),
             window_size: gfx::Size::new(960.0, 540.0),
+            decorations: true,
+            window_icon: None,
+            min_window_size: None,
+            max_window_size: None,
+            window_position: None,
+            resizable: true,
+            maximized: false,
+            fullscreen: false,
+            vsync: true,
+            max_fps: Some(60.),
+            scale_factor: None,
+            #[cfg(feature = "gamepad")]
+            gamepad: true,
         }
     }
 }
@@ -114,214 +500,660 @@ pub struct WindowResizeEvent {
     pub logical: gfx::Size,
 }
 
+/// Otway UI driven by an event loop owned by someone else, for embedding into applications (e.g. games) which already have a window and event loop.
+///
+/// The host forwards window events to [`handle_event`](Runner::handle_event), then calls [`update`](Runner::update) and [`draw`](Runner::draw) once per frame
+/// (e.g. upon `MainEventsCleared` and `RedrawRequested` respectively), swapping buffers itself afterwards.
+/// Hosts which sleep while idle should also [wake their event loop](ui::Proxy::set_waker) when events are sent through the [proxy](ui::Aux::proxy).
+/// [`run`](run) is itself built on a runner.
+pub struct Runner<T: 'static, W: ui::WidgetChildren<AppData<T>>> {
+    root: Root<T, W>,
+    aux: AppAux<T>,
+    display: gfx::skia::SkiaGraphicsDisplay,
+    commands: (gfx::CommandGroup, gfx::CommandGroup),
+    scale_factor: f64,
+    window_scale_factor: f64,
+    scale_override: Option<f64>,
+    physical_size: gfx::Size,
+    close_request: Option<ui::CloseRequestedEvent>,
+    // button, position and time of the last mouse press, for counting clicks.
+    last_press: Option<(ui::MouseButton, gfx::Point, std::time::Instant)>,
+}
+
+impl<T: 'static, W: ui::WidgetChildren<AppData<T>>> Runner<T, W> {
+    /// Creates the UI, drawing into `display`, which targets a window of `physical_size` at `scale_factor`.
+    pub fn new(
+        new: impl FnOnce(ui::CommonRef, &mut AppAux<T>) -> W,
+        data: T,
+        theme: impl FnOnce(&mut dyn gfx::GraphicsDisplay) -> Box<dyn theme::Theme<AppData<T>>>,
+        mut display: gfx::skia::SkiaGraphicsDisplay,
+        physical_size: gfx::Size,
+        scale_factor: f64,
+    ) -> Self {
+        let mut aux = ui::Aux::new(
+            AppData {
+                data,
+                status: Default::default(),
+                window: Default::default(),
+                cursor: Default::default(),
+            },
+            theme(&mut display),
+        );
+        aux.clipboard = os_clipboard();
+        aux.interaction = os_interaction_config();
+        aux.set_scale_factor(scale_factor as _);
+        let central_widget = aux.central_widget.clone();
+        let mut root = Root::new(new, central_widget, &mut aux);
+        root.set_layout_mode(ui::LayoutMode::Fill);
+
+        let mut runner = Runner {
+            root,
+            aux,
+            display,
+            commands: (gfx::CommandGroup::new(), gfx::CommandGroup::new()),
+            scale_factor,
+            window_scale_factor: scale_factor,
+            scale_override: None,
+            physical_size,
+            close_request: None,
+            last_press: None,
+        };
+        runner.resize(physical_size, scale_factor);
+        runner
+    }
+
+    /// Translates a window event into UI events, which are processed upon the next [`update`](Runner::update).
+    ///
+    /// Requests to close the window are emitted as [`CloseRequestedEvent`](ui::CloseRequestedEvent);
+    /// the host should close the window once [`exit_requested`](Runner::exit_requested) (after the update).
+    ///
+    /// Resizes and scale factor changes are applied to the layout immediately.
+    pub fn handle_event(&mut self, event: &WindowEvent) {
+        let aux = &mut self.aux;
+        match event {
+            WindowEvent::CloseRequested => {
+                let request = ui::CloseRequestedEvent::default();
+                aux.emit(&aux.id, request.clone());
+                self.close_request = Some(request);
+            }
+            WindowEvent::ScaleFactorChanged {
+                scale_factor,
+                new_inner_size,
+            } => {
+                let size = gfx::Size::new(new_inner_size.width as _, new_inner_size.height as _);
+                self.resize(size, *scale_factor);
+            }
+            WindowEvent::Resized(size) => {
+                self.resize(
+                    gfx::Size::new(size.width as _, size.height as _),
+                    self.window_scale_factor,
+                );
+                let logical = self.root.size();
+                self.aux.emit(
+                    &self.aux.id,
+                    WindowResizeEvent {
+                        physical: self.physical_size,
+                        logical,
+                    },
+                );
+            }
+            WindowEvent::ModifiersChanged(key_modifiers) => {
+                aux.set_modifiers(ui::KeyModifiers {
+                    shift: key_modifiers.shift(),
+                    ctrl: key_modifiers.ctrl(),
+                    alt: key_modifiers.alt(),
+                    logo: key_modifiers.logo(),
+                });
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                let position = position.to_logical::<f64>(self.scale_factor);
+                let point = gfx::Point::new(position.x as _, position.y as _);
+                aux.data.cursor = point;
+                aux.queue
+                    .emit(aux.id, ui::MouseMoveEvent(ui::ConsumableEvent::new(point)));
+            }
+            WindowEvent::MouseInput { state, button, .. } => {
+                let mouse_button = match *button {
+                    winit_event::MouseButton::Left => ui::MouseButton::Left,
+                    winit_event::MouseButton::Middle => ui::MouseButton::Middle,
+                    winit_event::MouseButton::Right => ui::MouseButton::Right,
+                    winit_event::MouseButton::Other(x) => ui::MouseButton::Other(x),
+                };
+
+                match state {
+                    winit_event::ElementState::Pressed => {
+                        let now = std::time::Instant::now();
+                        let point = aux.data.cursor;
+                        let click_count = match self.last_press {
+                            Some((button, pos, time))
+                                if button == mouse_button
+                                    && now - time <= aux.interaction.double_click_interval
+                                    && (point - pos).length() < aux.interaction.drag_threshold =>
+                            {
+                                aux.click_count() + 1
+                            }
+                            _ => 1,
+                        };
+                        self.last_press = Some((mouse_button, point, now));
+                        aux.set_click_count(click_count);
+
+                        aux.queue.emit(
+                            aux.id,
+                            ui::MousePressEvent(ui::ConsumableEvent::new((mouse_button, point))),
+                        );
+                        if click_count == 2 {
+                            aux.queue.emit(
+                                aux.id,
+                                ui::MouseDoubleClickEvent(ui::ConsumableEvent::new((
+                                    mouse_button,
+                                    point,
+                                ))),
+                            );
+                        }
+                    }
+                    winit_event::ElementState::Released => aux.queue.emit(
+                        aux.id,
+                        ui::MouseReleaseEvent(ui::ConsumableEvent::new((
+                            mouse_button,
+                            aux.data.cursor,
+                        ))),
+                    ),
+                };
+            }
+            WindowEvent::MouseWheel { delta, .. } => {
+                let delta = match *delta {
+                    winit_event::MouseScrollDelta::LineDelta(x, y) => {
+                        ui::ScrollDelta::Lines(gfx::Vector::new(x, y))
+                    }
+                    winit_event::MouseScrollDelta::PixelDelta(pixels) => {
+                        ui::ScrollDelta::Pixels(gfx::Vector::new(pixels.x as _, pixels.y as _))
+                    }
+                };
+                aux.queue.emit(
+                    aux.id,
+                    ui::MouseScrollEvent(ui::ConsumableEvent::new((aux.data.cursor, delta))),
+                );
+            }
+            WindowEvent::KeyboardInput { input, .. } => {
+                let key = input
+                    .virtual_keycode
+                    .map(ui::KeyInput::from)
+                    .unwrap_or(ui::KeyInput::Unknown(input.scancode));
+                match input.state {
+                    winit_event::ElementState::Pressed => {
+                        aux.queue.emit(
+                            aux.id,
+                            ui::RawKeyPressEvent(ui::ConsumableEvent::new(input.scancode)),
+                        );
+                        aux.queue
+                            .emit(aux.id, ui::KeyPressEvent(ui::ConsumableEvent::new(key)));
+                    }
+                    winit_event::ElementState::Released => {
+                        aux.queue.emit(
+                            aux.id,
+                            ui::RawKeyReleaseEvent(ui::ConsumableEvent::new(input.scancode)),
+                        );
+                        aux.queue
+                            .emit(aux.id, ui::KeyReleaseEvent(ui::ConsumableEvent::new(key)));
+                    }
+                }
+            }
+            // FIXME: glutin 0.24 doesn't report input method composition (nor enabling/disabling), so `ImeCompositionEvent`
+            // and `ImeCommitEvent` are never emitted here; committed text arrives as individual characters.
+            // Forwarding them needs a winit with `WindowEvent::Ime` (see "Input Method Composition" in ROADMAP.md).
+            WindowEvent::ReceivedCharacter(c) if !c.is_control() => aux
+                .queue
+                .emit(aux.id, ui::TextEvent(ui::ConsumableEvent::new(*c))),
+            _ => {}
+        }
+    }
+
+    /// Advances the frame clock, then processes the pending events and layout changes.
+    ///
+    /// This should be invoked once per frame, prior to [`draw`](Runner::draw).
+    pub fn update(&mut self) {
+        self.aux.tick();
+        ui::propagate_update(&mut self.root, &mut self.aux);
+        self.aux.update_root_layout();
+        ui::layout::update_dirty_layouts(&self.root);
+
+        if let Some(request) = self.close_request.take() {
+            if !request.cancelled() {
+                self.aux.request_exit();
+            }
+        }
+    }
+
+    /// Returns `true` if the application should exit, either because it [requested to](ui::Aux::request_exit),
+    /// or because closing the window wasn't [cancelled](ui::CloseRequestedEvent::cancel).
+    #[inline]
+    pub fn exit_requested(&self) -> bool {
+        self.aux.exit_requested()
+    }
+
+    /// Draws the UI and presents it to the display. Swapping the buffers of the window is left to the caller.
+    pub fn draw(&mut self) {
+        let size = self.display.size();
+        if self.physical_size.width != size.0 as f32 || self.physical_size.height != size.1 as f32 {
+            self.display
+                .resize((
+                    self.physical_size.width as _,
+                    self.physical_size.height as _,
+                ))
+                .expect("Display error when resizing");
+        }
+
+        self.commands.0.push(
+            &mut self.display,
+            &[
+                gfx::DisplayCommand::Save,
+                gfx::DisplayCommand::Clear(self.aux.theme.color(theme::colors::BACKGROUND)),
+                gfx::DisplayCommand::Scale(gfx::Vector::new(
+                    self.scale_factor as _,
+                    self.scale_factor as _,
+                )),
+            ],
+            gfx::ZOrder(std::i32::MIN),
+            false,
+            None,
+        );
+
+        self.root.repaint();
+
+        ui::propagate_draw(&mut self.root, &mut self.display, &mut self.aux);
+
+        self.commands.1.push(
+            &mut self.display,
+            &[gfx::DisplayCommand::Restore],
+            gfx::ZOrder(std::i32::MAX),
+            false,
+            None,
+        );
+
+        self.display.present(None).unwrap();
+    }
+
+    /// Resizes the UI to fill a window of `physical_size` at `scale_factor`, the scale factor of the monitor the window is on.
+    ///
+    /// The UI itself is scaled by the [scale override](Runner::set_scale_override) instead, if set.
+    pub fn resize(&mut self, physical_size: gfx::Size, scale_factor: f64) {
+        self.physical_size = physical_size;
+        self.window_scale_factor = scale_factor;
+        let scale_factor = self.scale_override.unwrap_or(scale_factor);
+        if self.scale_factor != scale_factor {
+            self.scale_factor = scale_factor;
+            self.commands.0.repaint();
+            self.commands.1.repaint();
+        }
+        self.aux.set_scale_factor(scale_factor as _);
+        self.root.set_size(gfx::Size::new(
+            (physical_size.width as f64 / scale_factor) as _,
+            (physical_size.height as f64 / scale_factor) as _,
+        ));
+        ui::layout::update_layout(&self.root);
+    }
+
+    /// Returns the cursor icon requested by the widgets, for the host to set on its window.
+    pub fn cursor_icon(&self) -> glutin::window::CursorIcon {
+        match self.aux.cursor_icon {
+            ui::CursorIcon::Default => glutin::window::CursorIcon::Default,
+            ui::CursorIcon::Pointer => glutin::window::CursorIcon::Hand,
+            ui::CursorIcon::Text => glutin::window::CursorIcon::Text,
+            ui::CursorIcon::ResizeHorizontal => glutin::window::CursorIcon::EwResize,
+            ui::CursorIcon::ResizeVertical => glutin::window::CursorIcon::NsResize,
+            ui::CursorIcon::Move => glutin::window::CursorIcon::Move,
+            ui::CursorIcon::NotAllowed => glutin::window::CursorIcon::NotAllowed,
+        }
+    }
+
+    /// Returns where the candidate window of an input method should be placed (in physical coordinates), below the caret of the focused text widget, if known.
+    ///
+    /// Hosts pass this to `Window::set_ime_position`; how closely the input method follows it depends on the platform.
+    pub fn ime_position(&self) -> Option<gfx::Point> {
+        self.aux.ime_area.map(|area| {
+            gfx::Point::new(
+                (area.min_x() as f64 * self.scale_factor) as _,
+                (area.max_y() as f64 * self.scale_factor) as _,
+            )
+        })
+    }
+
+    /// Returns the keyboard modifiers currently held, as last reported through [`handle_event`](Runner::handle_event).
+    #[inline]
+    pub fn key_modifiers(&self) -> ui::KeyModifiers {
+        self.aux.modifiers()
+    }
+
+    /// Returns the scale of the UI; the [override](Runner::set_scale_override) if set, otherwise that of the window.
+    #[inline]
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    /// Forces the UI to be drawn at `scale_factor`, regardless of the monitor the window is on, or follows the window again if `None`.
+    pub fn set_scale_override(&mut self, scale_factor: Option<f64>) {
+        self.scale_override = scale_factor;
+        self.resize(self.physical_size, self.window_scale_factor);
+    }
+
+    #[inline]
+    pub fn scale_override(&self) -> Option<f64> {
+        self.scale_override
+    }
+
+    /// Returns the widget given to [`new`](Runner::new).
+    #[inline]
+    pub fn widget(&self) -> &W {
+        &self.root.child
+    }
+
+    #[inline]
+    pub fn widget_mut(&mut self) -> &mut W {
+        &mut self.root.child
+    }
+
+    #[inline]
+    pub fn aux(&self) -> &AppAux<T> {
+        &self.aux
+    }
+
+    #[inline]
+    pub fn aux_mut(&mut self) -> &mut AppAux<T> {
+        &mut self.aux
+    }
+}
+
 pub fn run<T: 'static, W: ui::WidgetChildren<AppData<T>>>(
     new: impl FnOnce(ui::CommonRef, &mut AppAux<T>) -> W,
     aux: T,
     theme: impl FnOnce(&mut dyn gfx::GraphicsDisplay) -> Box<dyn theme::Theme<AppData<T>>>,
-    mut options: AppOptions,
+    options: AppOptions,
 ) -> Result<(), AppError> {
     let el = glutin::event_loop::EventLoop::new();
 
-    let wb = glutin::window::WindowBuilder::new()
+    let physical = |size: gfx::Size| glutin::dpi::PhysicalSize::new(size.width, size.height);
+
+    let mut wb = glutin::window::WindowBuilder::new()
         .with_title(options.window_title.clone())
-        .with_inner_size(glutin::dpi::PhysicalSize::new(
-            options.window_size.width,
-            options.window_size.height,
-        ));
+        .with_inner_size(physical(options.window_size))
+        .with_decorations(options.decorations)
+        .with_resizable(options.resizable)
+        .with_maximized(options.maximized);
+    if let Some(icon) = options.window_icon.clone() {
+        wb = wb.with_window_icon(Some(glutin::window::Icon::from_rgba(
+            icon.pixels,
+            icon.width,
+            icon.height,
+        )?));
+    }
+    if let Some(size) = options.min_window_size {
+        wb = wb.with_min_inner_size(physical(size));
+    }
+    if let Some(size) = options.max_window_size {
+        wb = wb.with_max_inner_size(physical(size));
+    }
+    if options.fullscreen {
+        wb = wb.with_fullscreen(Some(glutin::window::Fullscreen::Borderless(
+            el.primary_monitor(),
+        )));
+    }
     let ctxt = glutin::ContextBuilder::new()
-        .with_vsync(true)
+        .with_vsync(options.vsync)
         .build_windowed(wb, &el)?;
     let ctxt = unsafe { ctxt.make_current().map_err(|(_, e)| e)? };
-    let mut scale_factor = ctxt.window().scale_factor();
-    let mut display = gfx::skia::SkiaGraphicsDisplay::new_gl_framebuffer(
+    if let Some(position) = options.window_position {
+        ctxt.window()
+            .set_outer_position(glutin::dpi::PhysicalPosition::new(position.x, position.y));
+    }
+    let size = ctxt.window().inner_size();
+    let display = gfx::skia::SkiaGraphicsDisplay::new_gl_framebuffer(
         |s| ctxt.get_proc_address(s),
         &gfx::skia::SkiaOpenGlFramebuffer {
             framebuffer_id: 0,
-            size: (
-                options.window_size.width as _,
-                options.window_size.height as _,
-            ),
+            size: (size.width as _, size.height as _),
         },
     )?;
-    let central_widget = ui::CommonRef::new(None);
-    let mut aux = ui::Aux {
-        data: AppData {
-            data: aux,
-            cursor: Default::default(),
-        },
-        theme: theme(&mut display),
-        id: uniq::id::next(),
-        queue: Default::default(),
-        central_widget: central_widget.clone(),
-        focus_widget: Default::default(),
-    };
-    let mut root = Root::new(new, central_widget, &mut aux);
-    root.set_layout_mode(ui::LayoutMode::Fill);
-    let mut key_mods = ui::KeyModifiers {
-        shift: false,
-        ctrl: false,
-        alt: false,
-        logo: false,
+    let mut runner = Runner::new(
+        new,
+        aux,
+        theme,
+        display,
+        gfx::Size::new(size.width as _, size.height as _),
+        ctxt.window().scale_factor(),
+    );
+    runner.set_scale_override(options.scale_factor);
+    runner.aux_mut().data.window.maximized = options.maximized;
+    runner
+        .aux_mut()
+        .data
+        .window
+        .set_handle(WindowHandle::of(ctxt.window()));
+    let mut cursor_icon = runner.cursor_icon();
+    let mut ime_position = None;
+    let mut cursor = glutin::dpi::PhysicalPosition::new(0., 0.);
+    // the point of the window held by the cursor while dragging it.
+    let mut drag: Option<glutin::dpi::PhysicalPosition<f64>> = None;
+
+    #[cfg(feature = "gamepad")]
+    let mut gamepads = if options.gamepad {
+        Some(gamepad::Gamepads::new()?)
+    } else {
+        None
     };
-    let (mut cmds_a, mut cmds_b) = (gfx::CommandGroup::new(), gfx::CommandGroup::new());
 
-    root.set_size({
-        let logical = ctxt.window().inner_size().to_logical::<f64>(scale_factor);
-        gfx::Size::new(logical.width as _, logical.height as _)
+    let el_proxy = std::sync::Mutex::new(el.create_proxy());
+    runner.aux().proxy().set_waker(move || {
+        let _ = el_proxy.lock().unwrap().send_event(());
     });
-    ui::layout::update_layout(&root);
 
-    let bg_color = aux.theme.color(theme::colors::BACKGROUND);
+    let frame_interval = options
+        .max_fps
+        .filter(|&fps| fps > 0.)
+        .map(|fps| std::time::Duration::from_secs_f32(1. / fps));
+    let mut next_frame = std::time::Instant::now();
 
     el.run(move |event, _window, control_flow| {
-        *control_flow = glutin::event_loop::ControlFlow::WaitUntil(
-            std::time::Instant::now() + std::time::Duration::from_millis(16),
-        );
+        // wake for whichever comes first; the next frame or the next timer.
+        let wake = |next_frame: std::time::Instant, runner: &Runner<T, W>| {
+            runner
+                .aux()
+                .next_timer()
+                .map_or(next_frame, |timer| next_frame.min(timer))
+        };
+        *control_flow = match frame_interval {
+            Some(_) => glutin::event_loop::ControlFlow::WaitUntil(wake(next_frame, &runner)),
+            None => glutin::event_loop::ControlFlow::Poll,
+        };
 
         match event {
-            Event::MainEventsCleared => ctxt.window().request_redraw(),
-            Event::RedrawRequested(_) => {
-                let size = display.size();
-                if options.window_size.width != size.0 as f32
-                    || options.window_size.height != size.1 as f32
-                {
-                    display
-                        .resize((
-                            options.window_size.width as _,
-                            options.window_size.height as _,
-                        ))
-                        .expect("Display error when resizing");
+            Event::MainEventsCleared => {
+                // events are left queued until the next frame (or timer) is due.
+                let now = std::time::Instant::now();
+                if now < wake(next_frame, &runner) {
+                    return;
+                }
+                if let Some(interval) = frame_interval {
+                    // skip frames which were missed rather than rushing to catch up on them.
+                    if now >= next_frame {
+                        next_frame = (next_frame + interval).max(now);
+                    }
                 }
 
-                cmds_a.push(
-                    &mut display,
-                    &[
-                        gfx::DisplayCommand::Save,
-                        gfx::DisplayCommand::Clear(bg_color),
-                        gfx::DisplayCommand::Scale(gfx::Vector::new(
-                            scale_factor as _,
-                            scale_factor as _,
-                        )),
-                    ],
-                    gfx::ZOrder(std::i32::MIN),
-                    false,
-                    None,
-                );
+                #[cfg(feature = "gamepad")]
+                {
+                    if let Some(gamepads) = &mut gamepads {
+                        gamepads.poll(runner.aux_mut());
+                    }
+                }
 
-                root.repaint();
+                runner.update();
 
-                ui::propagate_draw(&mut root, &mut display, &mut aux);
+                if frame_interval.is_some() {
+                    *control_flow =
+                        glutin::event_loop::ControlFlow::WaitUntil(wake(next_frame, &runner));
+                }
 
-                cmds_b.push(
-                    &mut display,
-                    &[gfx::DisplayCommand::Restore],
-                    gfx::ZOrder(std::i32::MAX),
-                    false,
-                    None,
-                );
+                for request in runner.aux_mut().data.window.take_requests() {
+                    match request {
+                        WindowRequest::Drag => drag = Some(cursor),
+                        WindowRequest::Minimize => ctxt.window().set_minimized(true),
+                        WindowRequest::SetMaximized(maximized) => {
+                            ctxt.window().set_maximized(maximized)
+                        }
+                        WindowRequest::Close => {
+                            *control_flow = glutin::event_loop::ControlFlow::Exit;
+                        }
+                    }
+                }
 
-                display.present(None).unwrap();
-                ctxt.swap_buffers().unwrap();
-            }
-            Event::WindowEvent { event, .. } => match event {
-                WindowEvent::CloseRequested => {
+                if runner.exit_requested() {
                     *control_flow = glutin::event_loop::ControlFlow::Exit;
+                    return;
                 }
-                WindowEvent::ScaleFactorChanged {
-                    scale_factor: new_scale_factor,
-                    ..
-                } => {
-                    scale_factor = new_scale_factor;
-                    let size = ctxt.window().inner_size();
-                    options.window_size.width = size.width as _;
-                    options.window_size.height = size.height as _;
-
-                    cmds_a.repaint();
-                    cmds_b.repaint();
-                    let size: glutin::dpi::LogicalSize<f64> = size.to_logical(scale_factor);
-                    root.set_size(gfx::Size::new(size.width as _, size.height as _));
-                    ui::layout::update_layout(&root);
-                }
-                WindowEvent::Resized(size) => {
-                    options.window_size.width = size.width as _;
-                    options.window_size.height = size.height as _;
-
-                    let size: glutin::dpi::LogicalSize<f64> = size.to_logical(scale_factor);
-                    root.set_size(gfx::Size::new(size.width as _, size.height as _));
-                    ui::layout::update_layout(&root);
-                    aux.emit(
-                        &aux.id,
-                        WindowResizeEvent {
-                            physical: options.window_size,
-                            logical: gfx::Size::new(size.width as _, size.height as _),
-                        },
-                    );
-                }
-                WindowEvent::ModifiersChanged(key_modifiers) => {
-                    key_mods.shift = key_modifiers.shift();
-                    key_mods.ctrl = key_modifiers.ctrl();
-                    key_mods.alt = key_modifiers.alt();
-                    key_mods.logo = key_modifiers.logo();
-                }
-                WindowEvent::CursorMoved { position, .. } => {
-                    let position = position.to_logical::<f64>(scale_factor);
-                    let point = gfx::Point::new(position.x as _, position.y as _);
-                    aux.data.cursor = point;
-                    aux.queue
-                        .emit(aux.id, ui::MouseMoveEvent(ui::ConsumableEvent::new(point)));
+
+                if runner.ime_position() != ime_position {
+                    ime_position = runner.ime_position();
+                    if let Some(position) = ime_position {
+                        ctxt.window()
+                            .set_ime_position(glutin::dpi::PhysicalPosition::new(
+                                position.x, position.y,
+                            ));
+                    }
                 }
-                WindowEvent::MouseInput { state, button, .. } => {
-                    let mouse_button = match button {
-                        winit_event::MouseButton::Left => ui::MouseButton::Left,
-                        winit_event::MouseButton::Middle => ui::MouseButton::Middle,
-                        winit_event::MouseButton::Right => ui::MouseButton::Right,
-                        winit_event::MouseButton::Other(x) => ui::MouseButton::Other(x),
-                    };
-
-                    match state {
-                        winit_event::ElementState::Pressed => aux.queue.emit(
-                            aux.id,
-                            ui::MousePressEvent(ui::ConsumableEvent::new((
-                                mouse_button,
-                                aux.data.cursor,
-                            ))),
-                        ),
-                        winit_event::ElementState::Released => aux.queue.emit(
-                            aux.id,
-                            ui::MouseReleaseEvent(ui::ConsumableEvent::new((
-                                mouse_button,
-                                aux.data.cursor,
-                            ))),
-                        ),
-                    };
+
+                if runner.cursor_icon() != cursor_icon {
+                    cursor_icon = runner.cursor_icon();
+                    ctxt.window().set_cursor_icon(cursor_icon);
                 }
-                WindowEvent::KeyboardInput { input, .. } => match input.state {
-                    winit_event::ElementState::Pressed => aux.queue.emit(
-                        aux.id,
-                        ui::KeyPressEvent(ui::ConsumableEvent::new(
-                            input.virtual_keycode.unwrap().into(),
-                        )),
-                    ),
-                    winit_event::ElementState::Released if input.virtual_keycode.is_some() => {
-                        aux.queue.emit(
-                            aux.id,
-                            ui::KeyReleaseEvent(ui::ConsumableEvent::new(
-                                input.virtual_keycode.unwrap().into(),
-                            )),
-                        )
+
+                ctxt.window().request_redraw();
+            }
+            Event::RedrawRequested(_) => {
+                runner.draw();
+                ctxt.swap_buffers().unwrap();
+            }
+            Event::WindowEvent { event, .. } => {
+                match event {
+                    WindowEvent::CursorMoved { position, .. } => {
+                        cursor = position;
+                        if let (Some(grab), Ok(origin)) = (drag, ctxt.window().outer_position()) {
+                            ctxt.window()
+                                .set_outer_position(glutin::dpi::PhysicalPosition::new(
+                                    origin.x + (position.x - grab.x) as i32,
+                                    origin.y + (position.y - grab.y) as i32,
+                                ));
+                        }
                     }
+                    WindowEvent::Resized(size) => ctxt.resize(size),
+                    WindowEvent::ScaleFactorChanged {
+                        ref new_inner_size, ..
+                    } => ctxt.resize(**new_inner_size),
+                    WindowEvent::MouseInput {
+                        state: winit_event::ElementState::Released,
+                        button: winit_event::MouseButton::Left,
+                        ..
+                    } => drag = None,
                     _ => {}
-                },
-                WindowEvent::ReceivedCharacter(c) if !c.is_control() => aux
-                    .queue
-                    .emit(aux.id, ui::TextEvent(ui::ConsumableEvent::new(c))),
-                _ => {}
-            },
-            _ => return,
+                }
+                runner.handle_event(&event);
+            }
+            _ => {}
         }
-
-        ui::propagate_update(&mut root, &mut aux);
     });
 }
 
+/// Image in 8-bit RGBA, stored row by row from the top-left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RgbaImage {
+    pub width: u32,
+    pub height: u32,
+    /// Four bytes per pixel, in the order red, green, blue, alpha.
+    pub pixels: Vec<u8>,
+}
+
+impl RgbaImage {
+    /// Returns the `[r, g, b, a]` of the pixel at `(x, y)`, or `None` if it's out of bounds.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let i = ((y * self.width + x) * 4) as usize;
+        let mut out = [0; 4];
+        out.copy_from_slice(&self.pixels[i..i + 4]);
+        Some(out)
+    }
+}
+
+/// Builds the UI, lays it out to `size` and renders a single frame into an image, without opening a window (e.g. for documentation screenshots and golden-image tests).
+///
+/// Rendering still goes through OpenGL, by way of a headless context, so a GPU (or a software OpenGL implementation such as Mesa's llvmpipe) is needed.
+/// As with [`run`](run), this creates an event loop, which some platforms only allow once per process.
+pub fn render_to_image<T: 'static, W: ui::WidgetChildren<AppData<T>>>(
+    new: impl FnOnce(ui::CommonRef, &mut AppAux<T>) -> W,
+    data: T,
+    theme: impl FnOnce(&mut dyn gfx::GraphicsDisplay) -> Box<dyn theme::Theme<AppData<T>>>,
+    size: gfx::Size,
+) -> Result<RgbaImage, AppError> {
+    const GL_RGBA: u32 = 0x1908;
+    const GL_UNSIGNED_BYTE: u32 = 0x1401;
+    type ReadPixels =
+        unsafe extern "system" fn(i32, i32, i32, i32, u32, u32, *mut std::ffi::c_void);
+
+    let (width, height) = (size.width.max(1.) as u32, size.height.max(1.) as u32);
+
+    let el = glutin::event_loop::EventLoop::new();
+    let ctxt = glutin::ContextBuilder::new()
+        .build_headless(&el, glutin::dpi::PhysicalSize::new(width, height))?;
+    let ctxt = unsafe { ctxt.make_current().map_err(|(_, e)| e)? };
+
+    let read_pixels = ctxt.get_proc_address("glReadPixels");
+    if read_pixels.is_null() {
+        return Err(AppError::MissingGlFunction("glReadPixels"));
+    }
+    let read_pixels: ReadPixels = unsafe { std::mem::transmute(read_pixels) };
+
+    let display = gfx::skia::SkiaGraphicsDisplay::new_gl_framebuffer(
+        |s| ctxt.get_proc_address(s),
+        &gfx::skia::SkiaOpenGlFramebuffer {
+            framebuffer_id: 0,
+            size: (width as _, height as _),
+        },
+    )?;
+    let mut runner = Runner::new(
+        new,
+        data,
+        theme,
+        display,
+        gfx::Size::new(width as _, height as _),
+        1.,
+    );
+    runner.update();
+    runner.draw();
+
+    let row = (width * 4) as usize;
+    let mut pixels = vec![0; row * height as usize];
+    // SAFETY: the context which `read_pixels` was loaded from is still current on this thread, and `pixels` holds
+    // exactly `width * height` pixels of 4 bytes, as requested through `GL_RGBA` and `GL_UNSIGNED_BYTE`.
+    unsafe {
+        read_pixels(
+            0,
+            0,
+            width as _,
+            height as _,
+            GL_RGBA,
+            GL_UNSIGNED_BYTE,
+            pixels.as_mut_ptr() as *mut _,
+        );
+    }
+
+    // OpenGL reads from the bottom row up.
+    let pixels = pixels.chunks(row).rev().flatten().copied().collect();
+
+    Ok(RgbaImage {
+        width,
+        height,
+        pixels,
+    })
+}
+
 pub type AppAux<T> = ui::Aux<AppData<T>>;
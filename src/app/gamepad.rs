@@ -0,0 +1,137 @@
+use crate::ui;
+
+pub use gilrs::{Axis, Button, GamepadId};
+
+/// A gamepad button was pressed or released.
+pub struct GamepadButtonEvent {
+    pub gamepad: GamepadId,
+    pub button: Button,
+    pub pressed: bool,
+}
+
+/// A stick or trigger of a gamepad moved. Sticks range from `-1.0` to `1.0`, whereas triggers range from `0.0` to `1.0`.
+pub struct GamepadAxisEvent {
+    pub gamepad: GamepadId,
+    pub axis: Axis,
+    pub value: f32,
+}
+
+/// A gamepad was connected or disconnected.
+pub struct GamepadConnectionEvent {
+    pub gamepad: GamepadId,
+    pub connected: bool,
+}
+
+/// Gamepads connected to the system, whose input is emitted into the event queue by [`poll`](Gamepads::poll).
+///
+/// With [navigation](Gamepads::set_navigation) enabled (the default), the D-pad and the face buttons are also emitted as key events,
+/// so that widgets can be operated without a keyboard or mouse (e.g. for couch or kiosk UIs);
+/// the D-pad acts as the arrow keys, the south button (A on Xbox controllers) as Enter, and the east button (B) as Escape.
+///
+/// [`run`](super::run) polls gamepads each frame if [enabled](super::AppOptions::gamepad); hosts of a [`Runner`](super::Runner) poll them before each update.
+pub struct Gamepads {
+    gilrs: gilrs::Gilrs,
+    navigation: bool,
+}
+
+impl Gamepads {
+    /// Starts listening to gamepads.
+    ///
+    /// On platforms without gamepad support this succeeds, but no gamepads will ever be connected.
+    pub fn new() -> Result<Self, gilrs::Error> {
+        let gilrs = match gilrs::Gilrs::new() {
+            Ok(gilrs) | Err(gilrs::Error::NotImplemented(gilrs)) => gilrs,
+            Err(err) => return Err(err),
+        };
+
+        Ok(Gamepads {
+            gilrs,
+            navigation: true,
+        })
+    }
+
+    /// Changes whether the D-pad and face buttons are also emitted as key events.
+    #[inline]
+    pub fn set_navigation(&mut self, navigation: bool) {
+        self.navigation = navigation;
+    }
+
+    #[inline]
+    pub fn navigation(&self) -> bool {
+        self.navigation
+    }
+
+    /// Returns the IDs and names of the gamepads currently connected.
+    pub fn connected(&self) -> Vec<(GamepadId, String)> {
+        self.gilrs
+            .gamepads()
+            .map(|(id, gamepad)| (id, gamepad.name().to_string()))
+            .collect()
+    }
+
+    /// Emits the input received since the last poll.
+    pub fn poll<T: 'static>(&mut self, aux: &mut ui::Aux<T>) {
+        while let Some(gilrs::Event { id, event, .. }) = self.gilrs.next_event() {
+            match event {
+                gilrs::EventType::ButtonPressed(button, _) => {
+                    aux.emit(
+                        &aux.id,
+                        GamepadButtonEvent {
+                            gamepad: id,
+                            button,
+                            pressed: true,
+                        },
+                    );
+                    if let Some(key) = self.navigation_key(button) {
+                        aux.emit(&aux.id, ui::KeyPressEvent(ui::ConsumableEvent::new(key)));
+                    }
+                }
+                gilrs::EventType::ButtonReleased(button, _) => {
+                    aux.emit(
+                        &aux.id,
+                        GamepadButtonEvent {
+                            gamepad: id,
+                            button,
+                            pressed: false,
+                        },
+                    );
+                    if let Some(key) = self.navigation_key(button) {
+                        aux.emit(&aux.id, ui::KeyReleaseEvent(ui::ConsumableEvent::new(key)));
+                    }
+                }
+                gilrs::EventType::AxisChanged(axis, value, _) => aux.emit(
+                    &aux.id,
+                    GamepadAxisEvent {
+                        gamepad: id,
+                        axis,
+                        value,
+                    },
+                ),
+                gilrs::EventType::Connected | gilrs::EventType::Disconnected => aux.emit(
+                    &aux.id,
+                    GamepadConnectionEvent {
+                        gamepad: id,
+                        connected: matches!(event, gilrs::EventType::Connected),
+                    },
+                ),
+                _ => {}
+            }
+        }
+    }
+
+    fn navigation_key(&self, button: Button) -> Option<ui::KeyInput> {
+        if !self.navigation {
+            return None;
+        }
+
+        match button {
+            Button::DPadUp => Some(ui::KeyInput::Up),
+            Button::DPadDown => Some(ui::KeyInput::Down),
+            Button::DPadLeft => Some(ui::KeyInput::Left),
+            Button::DPadRight => Some(ui::KeyInput::Right),
+            Button::South => Some(ui::KeyInput::Return),
+            Button::East => Some(ui::KeyInput::Escape),
+            _ => None,
+        }
+    }
+}
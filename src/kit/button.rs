@@ -3,10 +3,64 @@ use {
     reclutch::display as gfx,
 };
 
+/// Which side of the text an icon is placed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IconPosition {
+    Before,
+    After,
+}
+
+impl Default for IconPosition {
+    #[inline]
+    fn default() -> Self {
+        IconPosition::Before
+    }
+}
+
+/// A [`Button`](Button) in toggle mode was pressed. Contains the new toggled state.
+pub struct ToggledEvent(pub bool);
+
+/// Timing of the repeated presses emitted while a [`Button`](Button) is held down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RepeatConfig {
+    /// How long the button has to be held before it starts repeating.
+    pub delay: std::time::Duration,
+    /// Time between repeated presses.
+    pub interval: std::time::Duration,
+}
+
+impl Default for RepeatConfig {
+    fn default() -> Self {
+        RepeatConfig::from(ui::InteractionConfig::default())
+    }
+}
+
+impl From<ui::InteractionConfig> for RepeatConfig {
+    /// Repeats at the same rate as held keys.
+    fn from(config: ui::InteractionConfig) -> Self {
+        RepeatConfig {
+            delay: config.key_repeat_delay,
+            interval: config.key_repeat_interval,
+        }
+    }
+}
+
 /// Simple labelled button control which emits interaction events.
+///
+/// An icon can be shown beside the text, or on its own if the text is empty.
+///
+/// In [toggle mode](Button::set_toggle_mode), the button latches; each press flips whether it is toggled on.
 pub struct Button<T: 'static> {
     label: kit::Label<T>,
     alignment: ui::layout::Alignment,
+    icon: Option<kit::Icon>,
+    icon_position: IconPosition,
+    icon_rect: gfx::Rect,
+    repeat: Option<RepeatConfig>,
+    held: Option<(gfx::Point, std::time::Instant)>,
+    interaction: kit::Interaction,
+    toggle_mode: bool,
+    toggled: bool,
 
     painter: theme::Painter<Self>,
     common: ui::CommonRef,
@@ -20,24 +74,81 @@ impl<T: 'static> Button<T> {
 
         let focus_listener = kit::focus_handler(
             aux,
-            kit::focus_forwarder(),
+            |obj: &mut Self, aux, event| {
+                if obj.interaction.apply_focus(event) {
+                    obj.update_label();
+                }
+                obj.repaint();
+                kit::focus_forwarder()(obj, aux, event);
+            },
             kit::FocusConfig {
                 mouse_trigger: Default::default(),
                 interaction_handler: common.with(|x| x.id()),
             },
         );
 
+        // pressing enter or space while focused acts as a click.
+        let keyboard_listener = kit::keyboard_handler(aux, |obj: &mut Self, aux, event| {
+            match event {
+                kit::KeyboardEvent::KeyPress(key) if kit::is_activation_key(key) => {
+                    obj.interact(aux, kit::InteractionEvent::Press(obj.bounds().center()));
+                }
+                kit::KeyboardEvent::KeyRelease(key) if kit::is_activation_key(key) => {
+                    obj.held = None;
+                    obj.interact(aux, kit::InteractionEvent::Release(obj.bounds().center()));
+                }
+                _ => {}
+            }
+            kit::keyboard_forwarder()(obj, aux, event);
+        });
+
+        // releases outside the button aren't reported as interaction events, but still end repeating.
+        let release_listener = aux.listen::<kit::ReadWrite<Self>>().and_on(
+            aux.id,
+            |(obj, _), event: &ui::MouseReleaseEvent| {
+                if event.0.get().0 == ui::MouseButton::Left {
+                    obj.held = None;
+                    if obj.interaction.pressed {
+                        obj.interaction.pressed = false;
+                        obj.update_label();
+                        obj.repaint();
+                    }
+                }
+            },
+        );
+
+        let theme_listener = aux.listen::<kit::ReadWrite<Self>>().and_on(
+            aux.id,
+            |(obj, aux), _: &ui::ThemeChangedEvent| {
+                theme::refresh_painter(obj, |x| &mut x.painter, aux);
+                obj.update_label();
+            },
+        );
+
         Button {
             label: kit::Label::new(common.clone(), aux),
             alignment: aux.theme.standards().button_text_alignment,
+            icon: None,
+            icon_position: IconPosition::Before,
+            icon_rect: Default::default(),
+            repeat: None,
+            held: None,
+            interaction: Default::default(),
+            toggle_mode: false,
+            toggled: false,
 
-            painter: theme::get_painter(aux.theme.as_ref(), theme::painters::BUTTON),
+            painter: theme::get_painter(aux, theme::painters::BUTTON),
             common,
-            listeners: ui::ListenerList::new(vec![focus_listener]),
+            listeners: ui::ListenerList::new(vec![
+                focus_listener,
+                keyboard_listener,
+                release_listener,
+                theme_listener,
+            ]),
 
             components: ui::ComponentList::new().and_push(kit::InteractionState::new(
                 aux,
-                kit::interaction_forwarder(None),
+                |obj: &mut Self, aux, event| obj.interact(aux, event),
                 None,
                 None,
             )),
@@ -64,20 +175,175 @@ impl<T: 'static> Button<T> {
         self.alignment
     }
 
+    /// Changes the icon shown beside the text, or removes it if `None`.
+    pub fn set_icon(&mut self, icon: impl Into<Option<kit::Icon>>) {
+        self.icon = icon.into();
+        self.update_label();
+    }
+
+    #[inline]
+    pub fn icon(&self) -> Option<&kit::Icon> {
+        self.icon.as_ref()
+    }
+
+    pub fn set_icon_position(&mut self, position: IconPosition) {
+        self.icon_position = position;
+        self.update_label();
+    }
+
+    #[inline]
+    pub fn icon_position(&self) -> IconPosition {
+        self.icon_position
+    }
+
+    /// Changes whether holding the button down repeatedly emits [`PressEvent`](kit::PressEvent)s, and how quickly.
+    ///
+    /// Repeating pauses while the cursor is outside the button. Pass `None` to only emit a single press.
+    pub fn set_repeat(&mut self, repeat: impl Into<Option<RepeatConfig>>) {
+        self.repeat = repeat.into();
+        if self.repeat.is_none() {
+            self.held = None;
+        }
+    }
+
+    #[inline]
+    pub fn repeat(&self) -> Option<RepeatConfig> {
+        self.repeat
+    }
+
+    /// Changes whether the button latches, toggling on and off with each press and emitting [`ToggledEvent`](ToggledEvent).
+    ///
+    /// Toggle buttons don't [repeat](Button::set_repeat).
+    pub fn set_toggle_mode(&mut self, toggle_mode: bool) {
+        self.toggle_mode = toggle_mode;
+        self.held = None;
+        if !toggle_mode {
+            self.set_toggled(false);
+        }
+    }
+
+    #[inline]
+    pub fn toggle_mode(&self) -> bool {
+        self.toggle_mode
+    }
+
+    /// Changes the toggled state without emitting [`ToggledEvent`](ToggledEvent).
+    ///
+    /// This has no effect outside of toggle mode.
+    pub fn set_toggled(&mut self, toggled: bool) {
+        self.toggled = toggled && self.toggle_mode;
+        self.repaint();
+    }
+
+    /// Returns `true` if the button is in toggle mode and currently toggled on.
+    #[inline]
+    pub fn toggled(&self) -> bool {
+        self.toggled
+    }
+
+    /// Handles an interaction, whether from the mouse or the keyboard.
+    fn interact(&mut self, aux: &mut ui::Aux<T>, event: kit::InteractionEvent) {
+        if self.interaction.apply(event) {
+            // the painter may offset the content by the interaction (e.g. depressing it while pressed).
+            self.update_label();
+            self.repaint();
+        }
+
+        match event {
+            kit::InteractionEvent::Press(_) if self.toggle_mode => {
+                self.set_toggled(!self.toggled);
+                self.emit(aux, ToggledEvent(self.toggled));
+            }
+            kit::InteractionEvent::Press(pos) => {
+                self.held = self
+                    .repeat
+                    .map(|repeat| (pos, std::time::Instant::now() + repeat.delay));
+            }
+            _ => {}
+        }
+        kit::interaction_forwarder(None)(self, aux, event);
+    }
+
+    /// Emits a repeated press if the button has been held long enough.
+    fn update_repeat(&mut self, aux: &mut ui::Aux<T>) {
+        if let (Some(repeat), Some((pos, next))) = (self.repeat, self.held) {
+            let now = std::time::Instant::now();
+            if now >= next {
+                self.held = Some((pos, now + repeat.interval));
+                if self.interaction.hovered {
+                    self.emit(aux, kit::PressEvent(pos));
+                }
+            }
+        }
+    }
+
+    /// Returns the rectangle (in absolute coordinates) the icon is drawn within, if there is an icon.
+    pub fn icon_bounds(&self) -> Option<gfx::Rect> {
+        self.icon.as_ref()?;
+        Some(self.icon_rect.translate(self.bounds().origin.to_vector()))
+    }
+
     fn update_label(&mut self) {
         let label_bounds = self.label.bounds();
-        let padding = theme::multi_metrics(
-            self,
-            &[theme::metrics::PADDING_X, theme::metrics::PADDING_Y],
-            |x| &mut x.painter,
+        let has_text = match self.label.text() {
+            gfx::DisplayText::Simple(text) => !text.is_empty(),
+            _ => true,
+        };
+        let label_size = if has_text {
+            label_bounds.size
+        } else {
+            gfx::Size::zero()
+        };
+
+        // the icon and the text are aligned together as a single block of content.
+        let icon_size = if self.icon.is_some() {
+            self.painter.icon_size(self)
+        } else {
+            0.
+        };
+        let spacing = if self.icon.is_some() && has_text {
+            self.painter.icon_spacing(self)
+        } else {
+            0.
+        };
+        let content = gfx::Size::new(
+            icon_size + spacing + label_size.width,
+            label_size.height.max(icon_size),
         );
-        let padding = gfx::Size::new(padding[0].unwrap(), padding[1].unwrap());
-        self.set_size(label_bounds.size + padding);
+
+        let padding = self.painter.padding(self);
+        let padding = gfx::Size::new(padding.horizontal(), padding.vertical());
+        let size = content + padding;
+        self.set_preferred_size(size);
+        self.set_size(size);
+
         let bounds = self.rect();
-        let y = ui::layout::align_y(label_bounds, bounds, ui::layout::Alignment::Middle, 0.) - 1.;
-        let x = ui::layout::align_x(label_bounds, bounds, self.alignment, padding.width / 2.0);
+        let offset = self.painter.content_offset(self);
+        let content_rect = gfx::Rect::new(bounds.origin, content);
+        let x = ui::layout::align_x(content_rect, bounds, self.alignment, padding.width / 2.0)
+            + offset.x;
+        let y = ui::layout::align_y(label_bounds, bounds, ui::layout::Alignment::Middle, 0.) - 1.
+            + offset.y;
+
+        let (icon_x, label_x) = match self.icon_position {
+            IconPosition::Before => (x, x + icon_size + spacing),
+            IconPosition::After => (x + label_size.width + spacing, x),
+        };
+        self.icon_rect.size = gfx::Size::new(icon_size, icon_size);
+        self.icon_rect.origin = gfx::Point::new(
+            icon_x,
+            ui::layout::align_y(self.icon_rect, bounds, ui::layout::Alignment::Middle, 0.)
+                + offset.y,
+        );
 
-        self.label.set_position(gfx::Point::new(x, y));
+        self.label.set_position(gfx::Point::new(label_x, y));
+    }
+}
+
+impl<T: 'static> kit::Interactive for Button<T> {
+    #[inline]
+    fn interaction(&self) -> kit::Interaction {
+        self.interaction
     }
 }
 
@@ -93,6 +359,7 @@ impl<T: 'static> ui::Element for Button<T> {
     fn update(&mut self, aux: &mut ui::Aux<T>) {
         ui::dispatch_components(self, aux, |x| &mut x.components).unwrap();
         ui::dispatch_list::<kit::ReadWrite<Self>, _>((self, aux), |(x, _)| &mut x.listeners);
+        self.update_repeat(aux);
 
         ui::propagate_repaint(self);
     }
@@ -0,0 +1,684 @@
+use {
+    crate::{kit, prelude::*, theme, ui},
+    reclutch::display as gfx,
+};
+
+/// A day was selected in a [`Calendar`](Calendar).
+pub struct DaySelectedEvent(pub Date);
+/// The month displayed by a [`Calendar`](Calendar) changed. Contains the first day of the new month.
+pub struct MonthChangedEvent(pub Date);
+
+/// Day of the week.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    /// Returns the weekday from the number of days since Monday (wrapping).
+    pub fn from_monday(days: u32) -> Self {
+        match days % 7 {
+            0 => Weekday::Monday,
+            1 => Weekday::Tuesday,
+            2 => Weekday::Wednesday,
+            3 => Weekday::Thursday,
+            4 => Weekday::Friday,
+            5 => Weekday::Saturday,
+            _ => Weekday::Sunday,
+        }
+    }
+
+    /// Returns the number of days since Monday.
+    #[inline]
+    pub fn days_from_monday(self) -> u32 {
+        self as u32
+    }
+
+    /// Returns the next day of the week.
+    #[inline]
+    pub fn succ(self) -> Self {
+        Weekday::from_monday(self.days_from_monday() + 1)
+    }
+
+    /// Returns a two-letter English abbreviation.
+    pub fn short_name(self) -> &'static str {
+        match self {
+            Weekday::Monday => "Mo",
+            Weekday::Tuesday => "Tu",
+            Weekday::Wednesday => "We",
+            Weekday::Thursday => "Th",
+            Weekday::Friday => "Fr",
+            Weekday::Saturday => "Sa",
+            Weekday::Sunday => "Su",
+        }
+    }
+
+    /// Returns the conventional first day of the week for a BCP 47 locale tag (e.g. `"en-US"`, `"de_DE"`).
+    ///
+    /// Only the region subtag is considered. Unknown or missing regions default to Monday (ISO 8601).
+    pub fn first_for_locale(locale: &str) -> Self {
+        let region = locale
+            .split(|c| c == '-' || c == '_')
+            .skip(1)
+            .find(|x| x.len() == 2 && x.chars().all(|c| c.is_ascii_alphabetic()))
+            .map(|x| x.to_ascii_uppercase());
+
+        match region.as_ref().map(|x| &x[..]) {
+            Some("US") | Some("CA") | Some("MX") | Some("BR") | Some("JP") | Some("KR")
+            | Some("CN") | Some("TW") | Some("HK") | Some("IL") | Some("IN") | Some("PH")
+            | Some("ZA") | Some("SA") => Weekday::Sunday,
+            Some("AE") | Some("AF") | Some("BH") | Some("DZ") | Some("EG") | Some("IQ")
+            | Some("IR") | Some("JO") | Some("KW") | Some("LY") | Some("OM") | Some("QA")
+            | Some("SY") => Weekday::Saturday,
+            _ => Weekday::Monday,
+        }
+    }
+}
+
+/// A proleptic Gregorian calendar date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+impl Date {
+    /// Creates a new date, returning `None` if the month or day is out of range.
+    ///
+    /// `month` and `day` both start from 1.
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        if month >= 1 && month <= 12 && day >= 1 && day <= days_in_month(year, month) {
+            Some(Date { year, month, day })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    #[inline]
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    #[inline]
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    /// Returns the day of the week this date falls on.
+    pub fn weekday(&self) -> Weekday {
+        const OFFSETS: [i32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+        let y = if self.month < 3 {
+            self.year - 1
+        } else {
+            self.year
+        };
+        // Sakamoto's method; 0 is Sunday.
+        let from_sunday = (y + y.div_euclid(4) - y.div_euclid(100)
+            + y.div_euclid(400)
+            + OFFSETS[self.month as usize - 1]
+            + self.day as i32)
+            .rem_euclid(7) as u32;
+        Weekday::from_monday(from_sunday + 6)
+    }
+
+    /// Returns the first day of the month this date is in.
+    #[inline]
+    pub fn first_of_month(&self) -> Self {
+        Date { day: 1, ..*self }
+    }
+
+    /// Offsets the date by a number of months, clamping the day to the length of the resulting month.
+    pub fn add_months(&self, months: i32) -> Self {
+        let index = self.year * 12 + self.month as i32 - 1 + months;
+        let year = index.div_euclid(12);
+        let month = index.rem_euclid(12) as u32 + 1;
+        Date {
+            year,
+            month,
+            day: self.day.min(days_in_month(year, month)),
+        }
+    }
+
+    /// Offsets the date by a number of days.
+    pub fn add_days(&self, days: i32) -> Self {
+        let mut date = *self;
+        let mut days = days;
+        while days > 0 {
+            let remaining = (days_in_month(date.year, date.month) - date.day) as i32;
+            if days <= remaining {
+                date.day += days as u32;
+                break;
+            }
+            days -= remaining + 1;
+            date = date.add_months(1).first_of_month();
+        }
+        while days < 0 {
+            if -days < date.day as i32 {
+                date.day = (date.day as i32 + days) as u32;
+                break;
+            }
+            days += date.day as i32;
+            let prev = date.add_months(-1);
+            date = Date {
+                day: days_in_month(prev.year, prev.month),
+                ..prev
+            };
+        }
+        date
+    }
+}
+
+#[inline]
+pub fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// Returns the number of days in a month (`month` starting from 1).
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// A single day cell within a [`Calendar`](Calendar).
+pub struct CalendarDay<T: 'static> {
+    label: kit::Label<T>,
+    date: Date,
+    in_month: bool,
+    selected: bool,
+    enabled: bool,
+
+    painter: theme::Painter<Self>,
+    common: ui::CommonRef,
+    components: ui::ComponentList<Self>,
+}
+
+impl<T: 'static> CalendarDay<T> {
+    pub fn new(parent: ui::CommonRef, aux: &mut ui::Aux<T>, date: Date) -> Self {
+        let common = ui::CommonRef::new(parent);
+
+        let mut day = CalendarDay {
+            label: kit::Label::new(common.clone(), aux),
+            date,
+            in_month: true,
+            selected: false,
+            enabled: true,
+
+            painter: theme::get_painter(aux, theme::painters::CALENDAR_DAY),
+            common,
+            components: ui::ComponentList::new().and_push(kit::InteractionState::new(
+                aux,
+                kit::interaction_forwarder(None),
+                None,
+                None,
+            )),
+        };
+
+        day.label.set_text(date.day().to_string());
+        theme::apply_size_hint(&mut day, |x| &mut x.painter);
+        day.center_label();
+
+        day
+    }
+
+    #[inline]
+    pub fn date(&self) -> Date {
+        self.date
+    }
+
+    /// Returns `true` if this day belongs to the month displayed by the calendar,
+    /// as opposed to the leading/trailing days of the adjacent months.
+    #[inline]
+    pub fn in_month(&self) -> bool {
+        self.in_month
+    }
+
+    #[inline]
+    pub fn selected(&self) -> bool {
+        self.selected
+    }
+
+    /// Returns `false` if the day is outside the selectable range of the calendar.
+    #[inline]
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_state(&mut self, in_month: bool, selected: bool, enabled: bool, aux: &mut ui::Aux<T>) {
+        self.in_month = in_month;
+        self.selected = selected;
+        self.enabled = enabled;
+        self.label.set_color(aux.color(if !enabled {
+            theme::colors::DISABLED_FOREGROUND
+        } else if in_month {
+            theme::colors::FOREGROUND
+        } else {
+            theme::colors::WEAK_FOREGROUND
+        }));
+        self.repaint();
+    }
+
+    fn center_label(&mut self) {
+        let bounds = gfx::Rect::new(Default::default(), self.size());
+        let label_bounds = self.label.rect();
+        self.label.set_position(gfx::Point::new(
+            ui::layout::align_x(label_bounds, bounds, ui::layout::Alignment::Middle, 0.),
+            ui::layout::align_y(label_bounds, bounds, ui::layout::Alignment::Middle, 0.) - 1.,
+        ));
+    }
+}
+
+impl<T: 'static> ui::Element for CalendarDay<T> {
+    type Aux = T;
+
+    #[inline]
+    fn common(&self) -> &ui::CommonRef {
+        &self.common
+    }
+
+    #[inline]
+    fn update(&mut self, aux: &mut ui::Aux<Self::Aux>) {
+        ui::dispatch_components(self, aux, |x| &mut x.components).unwrap();
+    }
+
+    fn draw(&mut self, display: &mut dyn gfx::GraphicsDisplay, aux: &mut ui::Aux<Self::Aux>) {
+        ui::draw(
+            self,
+            |o, a| theme::paint(o, |o| &mut o.painter, a),
+            display,
+            aux,
+            None,
+        )
+    }
+}
+
+impl<T: 'static> ui::WidgetChildren<T> for CalendarDay<T> {
+    crate::children![for <T>; label];
+}
+
+enum CalendarAction {
+    Select(usize),
+    ShowMonth(i32),
+    Refresh,
+}
+
+/// Month grid which allows the user to pick a day.
+///
+/// The grid always has 6 rows of 7 days, starting on [`first_weekday`](Calendar::first_weekday).
+/// Days before the [minimum](Calendar::set_min_date) or after the [maximum](Calendar::set_max_date) cannot be selected.
+pub struct Calendar<T: 'static> {
+    month: Date,
+    selected: Option<Date>,
+    min_date: Option<Date>,
+    max_date: Option<Date>,
+    first_weekday: Weekday,
+
+    prev_button: kit::Button<T>,
+    next_button: kit::Button<T>,
+    month_label: kit::Label<T>,
+    weekday_labels: Vec<kit::Label<T>>,
+    days: Vec<CalendarDay<T>>,
+    action: Option<CalendarAction>,
+    day_listener: ui::Listener<kit::ReadWrite<Self>>,
+
+    painter: theme::Painter<Self>,
+    common: ui::CommonRef,
+    listeners: ui::ListenerList<kit::ReadWrite<Self>>,
+    components: ui::ComponentList<Self>,
+}
+
+impl<T: 'static> Calendar<T> {
+    /// Creates a new calendar, initially displaying the month of `date`.
+    pub fn new(parent: ui::CommonRef, aux: &mut ui::Aux<T>, date: Date) -> Self {
+        let common = ui::CommonRef::new(parent);
+
+        let focus_listener = kit::focus_handler(
+            aux,
+            kit::focus_forwarder(),
+            kit::FocusConfig {
+                interaction_handler: common.with(|x| x.id()),
+                mouse_trigger: Default::default(),
+            },
+        );
+
+        let mut prev_button = kit::Button::new(common.clone(), aux);
+        prev_button.set_text("<");
+        let mut next_button = kit::Button::new(common.clone(), aux);
+        next_button.set_text(">");
+
+        let nav_listener = aux
+            .listen::<kit::ReadWrite<Self>>()
+            .and_on(prev_button.id(), |(obj, _), _: &kit::PressEvent| {
+                obj.action = Some(CalendarAction::ShowMonth(-1));
+            })
+            .and_on(next_button.id(), |(obj, _), _: &kit::PressEvent| {
+                obj.action = Some(CalendarAction::ShowMonth(1));
+            })
+            .and_on(aux.id, |(obj, aux), _: &ui::ThemeChangedEvent| {
+                theme::refresh_painter(obj, |x| &mut x.painter, aux);
+                // the day cells are rebuilt with painters from the new theme.
+                obj.action = Some(CalendarAction::Refresh);
+            });
+
+        let mut calendar = Calendar {
+            month: date.first_of_month(),
+            selected: None,
+            min_date: None,
+            max_date: None,
+            first_weekday: Weekday::Monday,
+
+            prev_button,
+            next_button,
+            month_label: kit::Label::new(common.clone(), aux),
+            weekday_labels: (0..7)
+                .map(|_| kit::Label::new(common.clone(), aux))
+                .collect(),
+            days: Vec::new(),
+            action: None,
+            day_listener: aux.listen(),
+
+            painter: theme::get_painter(aux, theme::painters::CALENDAR),
+            common,
+            listeners: ui::ListenerList::new(vec![focus_listener, nav_listener]),
+            components: ui::ComponentList::new().and_push(kit::InteractionState::new(
+                aux,
+                kit::interaction_forwarder(None),
+                None,
+                None,
+            )),
+        };
+
+        calendar.update_days(aux);
+
+        calendar
+    }
+
+    /// Changes the displayed month to the month of `date`.
+    pub fn set_month(&mut self, date: Date, aux: &mut ui::Aux<T>) {
+        let month = self.clamp(date).first_of_month();
+        if month != self.month {
+            self.month = month;
+            self.update_days(aux);
+            self.emit(aux, MonthChangedEvent(self.month));
+        }
+    }
+
+    /// Returns the first day of the displayed month.
+    #[inline]
+    pub fn month(&self) -> Date {
+        self.month
+    }
+
+    /// Changes the selected day without emitting [`DaySelectedEvent`](DaySelectedEvent).
+    ///
+    /// The date is clamped to the minimum/maximum and the displayed month follows the selection.
+    pub fn set_selected(&mut self, selected: impl Into<Option<Date>>, aux: &mut ui::Aux<T>) {
+        self.selected = selected.into().map(|x| self.clamp(x));
+        if let Some(selected) = self.selected {
+            self.month = selected.first_of_month();
+        }
+        self.update_days(aux);
+    }
+
+    #[inline]
+    pub fn selected(&self) -> Option<Date> {
+        self.selected
+    }
+
+    pub fn set_min_date(&mut self, min_date: impl Into<Option<Date>>, aux: &mut ui::Aux<T>) {
+        self.min_date = min_date.into();
+        self.reclamp(aux);
+    }
+
+    #[inline]
+    pub fn min_date(&self) -> Option<Date> {
+        self.min_date
+    }
+
+    pub fn set_max_date(&mut self, max_date: impl Into<Option<Date>>, aux: &mut ui::Aux<T>) {
+        self.max_date = max_date.into();
+        self.reclamp(aux);
+    }
+
+    #[inline]
+    pub fn max_date(&self) -> Option<Date> {
+        self.max_date
+    }
+
+    /// Changes the day of the week which the grid starts on.
+    ///
+    /// For a locale-appropriate value, see [`Weekday::first_for_locale`](Weekday::first_for_locale).
+    pub fn set_first_weekday(&mut self, first_weekday: Weekday, aux: &mut ui::Aux<T>) {
+        self.first_weekday = first_weekday;
+        self.update_days(aux);
+    }
+
+    #[inline]
+    pub fn first_weekday(&self) -> Weekday {
+        self.first_weekday
+    }
+
+    /// Returns `true` if `date` is within the minimum/maximum range.
+    pub fn is_selectable(&self, date: Date) -> bool {
+        self.min_date.map(|x| date >= x).unwrap_or(true)
+            && self.max_date.map(|x| date <= x).unwrap_or(true)
+    }
+
+    fn clamp(&self, mut date: Date) -> Date {
+        if let Some(min_date) = self.min_date {
+            date = date.max(min_date);
+        }
+        if let Some(max_date) = self.max_date {
+            date = date.min(max_date);
+        }
+        date
+    }
+
+    fn reclamp(&mut self, aux: &mut ui::Aux<T>) {
+        self.selected = self.selected.map(|x| self.clamp(x));
+        // only move the displayed month if it lies entirely outside of the range.
+        let last_day = self.month.add_months(1).add_days(-1);
+        if !self.is_selectable(self.month) && !self.is_selectable(last_day) {
+            self.month = self.clamp(self.month).first_of_month();
+        }
+        self.update_days(aux);
+    }
+
+    fn update_days(&mut self, aux: &mut ui::Aux<T>) {
+        self.month_label.set_text(format!(
+            "{} {}",
+            MONTH_NAMES[self.month.month() as usize - 1],
+            self.month.year()
+        ));
+
+        let mut weekday = self.first_weekday;
+        for label in &mut self.weekday_labels {
+            label.set_text(weekday.short_name());
+            label.set_color(aux.color(theme::colors::WEAK_FOREGROUND));
+            weekday = weekday.succ();
+        }
+
+        let leading = (self.month.weekday().days_from_monday() + 7
+            - self.first_weekday.days_from_monday())
+            % 7;
+        let start = self.month.add_days(-(leading as i32));
+
+        let mut listener = aux.listen::<kit::ReadWrite<Self>>();
+        self.days = Vec::with_capacity(42);
+        for i in 0..42 {
+            let date = start.add_days(i);
+            let mut day = CalendarDay::new(self.common.clone(), aux, date);
+            day.set_state(
+                date.month() == self.month.month() && date.year() == self.month.year(),
+                self.selected == Some(date),
+                self.is_selectable(date),
+                aux,
+            );
+
+            let index = i as usize;
+            listener.on(day.id(), move |(obj, _), _: &kit::PressEvent| {
+                obj.action = Some(CalendarAction::Select(index));
+            });
+
+            self.days.push(day);
+        }
+        self.day_listener = listener;
+
+        self.arrange();
+    }
+
+    fn arrange(&mut self) {
+        let spacing = self.painter.spacing(self);
+        let cell = self.days.first().map(|x| x.size()).unwrap_or_default();
+        let width = cell.width * 7.;
+
+        let prev_size = self.prev_button.size();
+        let next_size = self.next_button.size();
+        let label_bounds = self.month_label.rect();
+        let header_height = prev_size.height.max(label_bounds.size.height);
+
+        self.prev_button.set_position(Default::default());
+        self.next_button
+            .set_position(gfx::Point::new(width - next_size.width, 0.));
+        self.month_label.set_position(gfx::Point::new(
+            (width - label_bounds.size.width) / 2.,
+            (header_height - label_bounds.size.height) / 2.,
+        ));
+
+        let mut y = header_height + spacing;
+        let mut row_height = 0.0f32;
+        for (i, label) in self.weekday_labels.iter_mut().enumerate() {
+            let size = label.rect().size;
+            row_height = row_height.max(size.height);
+            label.set_position(gfx::Point::new(
+                cell.width * i as f32 + (cell.width - size.width) / 2.,
+                y,
+            ));
+        }
+        y += row_height + spacing;
+
+        for (i, day) in self.days.iter_mut().enumerate() {
+            day.set_position(gfx::Point::new(
+                cell.width * (i % 7) as f32,
+                y + cell.height * (i / 7) as f32,
+            ));
+        }
+
+        self.set_size(gfx::Size::new(width, y + cell.height * 6.));
+        self.repaint();
+    }
+
+    fn handle_action(&mut self, action: CalendarAction, aux: &mut ui::Aux<T>) {
+        match action {
+            CalendarAction::Select(index) => {
+                let date = match self.days.get(index) {
+                    Some(day) if day.enabled() => day.date(),
+                    _ => return,
+                };
+                self.set_selected(date, aux);
+                self.emit(aux, DaySelectedEvent(date));
+            }
+            CalendarAction::ShowMonth(offset) => {
+                let month = self.month.add_months(offset);
+                self.set_month(month, aux);
+            }
+            CalendarAction::Refresh => self.update_days(aux),
+        }
+    }
+}
+
+impl<T: 'static> ui::Element for Calendar<T> {
+    type Aux = T;
+
+    #[inline]
+    fn common(&self) -> &ui::CommonRef {
+        &self.common
+    }
+
+    fn update(&mut self, aux: &mut ui::Aux<Self::Aux>) {
+        ui::dispatch_components(self, aux, |x| &mut x.components).unwrap();
+        ui::dispatch_list::<kit::ReadWrite<Self>, _>((self, aux), |(x, _)| &mut x.listeners);
+        ui::dispatch((self, aux), |(x, _): (&mut Self, _)| &mut x.day_listener);
+
+        // the day cells are rebuilt by actions, which can't happen while the day listener is being dispatched.
+        if let Some(action) = self.action.take() {
+            self.handle_action(action, aux);
+        }
+
+        ui::propagate_repaint(self);
+    }
+
+    fn draw(&mut self, display: &mut dyn gfx::GraphicsDisplay, aux: &mut ui::Aux<Self::Aux>) {
+        ui::draw(
+            self,
+            |o, a| theme::paint(o, |o| &mut o.painter, a),
+            display,
+            aux,
+            None,
+        )
+    }
+}
+
+impl<T: 'static> ui::WidgetChildren<T> for Calendar<T> {
+    fn children(&self) -> Vec<&dyn ui::WidgetChildren<T>> {
+        let mut children: Vec<&dyn ui::WidgetChildren<T>> =
+            vec![&self.prev_button, &self.next_button, &self.month_label];
+        children.extend(
+            self.weekday_labels
+                .iter()
+                .map(|x| x as &dyn ui::WidgetChildren<T>),
+        );
+        children.extend(self.days.iter().map(|x| x as &dyn ui::WidgetChildren<T>));
+        children
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn ui::WidgetChildren<T>> {
+        let mut children: Vec<&mut dyn ui::WidgetChildren<T>> = vec![
+            &mut self.prev_button,
+            &mut self.next_button,
+            &mut self.month_label,
+        ];
+        children.extend(
+            self.weekday_labels
+                .iter_mut()
+                .map(|x| x as &mut dyn ui::WidgetChildren<T>),
+        );
+        children.extend(
+            self.days
+                .iter_mut()
+                .map(|x| x as &mut dyn ui::WidgetChildren<T>),
+        );
+        children
+    }
+}
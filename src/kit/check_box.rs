@@ -7,6 +7,7 @@ pub struct CheckMarkToggledEvent(pub bool);
 
 pub struct CheckMarkBox<T: 'static> {
     checked: bool,
+    interaction: kit::Interaction,
 
     painter: theme::Painter<Self>,
     common: ui::CommonRef,
@@ -20,35 +21,59 @@ impl<T: 'static> CheckMarkBox<T> {
 
         let focus_listener = kit::focus_handler(
             aux,
-            kit::focus_forwarder(),
+            |obj: &mut Self, aux, event| {
+                obj.interaction.apply_focus(event);
+                obj.repaint();
+                kit::focus_forwarder()(obj, aux, event);
+            },
             kit::FocusConfig {
                 interaction_handler: common.with(|x| x.id()),
                 mouse_trigger: Default::default(),
             },
         );
 
+        // pressing enter or space while focused acts as a click.
+        let keyboard_listener = kit::keyboard_handler(aux, |obj: &mut Self, aux, event| {
+            match event {
+                kit::KeyboardEvent::KeyPress(key) if kit::is_activation_key(key) => {
+                    obj.interact(aux, kit::InteractionEvent::Press(obj.bounds().center()));
+                }
+                kit::KeyboardEvent::KeyRelease(key) if kit::is_activation_key(key) => {
+                    obj.interact(aux, kit::InteractionEvent::Release(obj.bounds().center()));
+                }
+                _ => {}
+            }
+            kit::keyboard_forwarder()(obj, aux, event);
+        });
+
+        let theme_listener = aux.listen::<kit::ReadWrite<Self>>().and_on(
+            aux.id,
+            |(obj, aux), _: &ui::ThemeChangedEvent| {
+                theme::refresh_painter(obj, |x| &mut x.painter, aux);
+                theme::apply_size_hint(obj, |x| &mut x.painter);
+            },
+        );
+
         let mut cm = CheckMarkBox {
             checked: false,
+            interaction: Default::default(),
 
-            painter: theme::get_painter(aux.theme.as_ref(), theme::painters::CHECK_MARK_BOX),
+            painter: theme::get_painter(aux, theme::painters::CHECK_MARK_BOX),
             common,
-            listeners: ui::ListenerList::new(vec![focus_listener]),
+            listeners: ui::ListenerList::new(vec![
+                focus_listener,
+                keyboard_listener,
+                theme_listener,
+            ]),
             components: ui::ComponentList::new().and_push(kit::InteractionState::new(
                 aux,
-                |obj: &mut Self, aux, ev| {
-                    if let kit::InteractionEvent::Press(_) = ev {
-                        obj.toggle();
-                        obj.emit(aux, CheckMarkToggledEvent(obj.checked));
-                    }
-                    kit::interaction_forwarder(None)(obj, aux, ev);
-                },
+                |obj: &mut Self, aux, event| obj.interact(aux, event),
                 None,
                 None,
             )),
         };
 
-        let size = theme::size_hint(&mut cm, |x| &mut x.painter);
-        ElementMixin::set_size(&cm, size);
+        theme::apply_size_hint(&mut cm, |x| &mut x.painter);
 
         cm
     }
@@ -67,6 +92,26 @@ impl<T: 'static> CheckMarkBox<T> {
         self.checked = !self.checked;
         self.repaint();
     }
+
+    /// Handles an interaction, whether from the mouse or the keyboard.
+    fn interact(&mut self, aux: &mut ui::Aux<T>, event: kit::InteractionEvent) {
+        if self.interaction.apply(event) {
+            self.repaint();
+        }
+
+        if let kit::InteractionEvent::Press(_) = event {
+            self.toggle();
+            self.emit(aux, CheckMarkToggledEvent(self.checked));
+        }
+        kit::interaction_forwarder(None)(self, aux, event);
+    }
+}
+
+impl<T: 'static> kit::Interactive for CheckMarkBox<T> {
+    #[inline]
+    fn interaction(&self) -> kit::Interaction {
+        self.interaction
+    }
 }
 
 impl<T: 'static> ui::Element for CheckMarkBox<T> {
@@ -98,6 +143,9 @@ impl<T: 'static> ui::Element for CheckMarkBox<T> {
 
 impl<T: 'static> ui::WidgetChildren<T> for CheckMarkBox<T> {}
 
+/// A [`CheckMarkBox`](CheckMarkBox) with a label beside it.
+///
+/// Clicking anywhere on the check box (including the label) toggles it, and [`CheckMarkToggledEvent`](CheckMarkToggledEvent) is emitted on behalf of the check box.
 pub struct CheckBox<T: 'static> {
     check_mark: CheckMarkBox<T>,
     label: kit::Label<T>,
@@ -111,43 +159,73 @@ impl<T: 'static> CheckBox<T> {
     pub fn new(parent: ui::CommonRef, aux: &mut ui::Aux<T>) -> Self {
         let common = ui::CommonRef::new(parent);
 
-        let mut check_mark = CheckMarkBox::new(common.clone(), aux);
+        let check_mark = CheckMarkBox::new(common.clone(), aux);
         let label = kit::Label::new(common.clone(), aux);
 
         let mut hstack = ui::layout::HStack::new().into_node(None);
         hstack.push(&check_mark, None);
         hstack.push(
             &label,
-            Some(
-                (
-                    theme::metrics(&mut check_mark, theme::metrics::CHECK_MARK_SPACING, |x| {
-                        &mut x.painter
-                    })
-                    .unwrap(),
-                    0.0,
-                )
-                    .into(),
-            ),
+            Some((check_mark.painter.spacing(&check_mark), 0.0).into()),
         );
         common.with(move |x| {
             x.set_layout(hstack);
             x.set_layout_mode(ui::LayoutMode::Shrink);
         });
 
+        let check_mark_listener = aux.listen::<kit::ReadWrite<Self>>().and_on(
+            check_mark.id(),
+            |(obj, aux), event: &CheckMarkToggledEvent| {
+                obj.emit(aux, CheckMarkToggledEvent(event.0))
+            },
+        );
+
         CheckBox {
             check_mark,
             label,
 
             common,
-            listeners: ui::ListenerList::new(vec![]),
+            listeners: ui::ListenerList::new(vec![check_mark_listener]),
             components: ui::ComponentList::new().and_push(kit::InteractionState::new(
                 aux,
-                kit::interaction_forwarder(None),
+                |obj: &mut Self, aux, event| {
+                    // presses on the check mark itself are handled (and re-emitted) by the check mark.
+                    if let kit::InteractionEvent::Press(_) = event {
+                        obj.toggle();
+                        obj.emit(aux, CheckMarkToggledEvent(obj.checked()));
+                    }
+                    kit::interaction_forwarder(None)(obj, aux, event);
+                },
                 None,
                 None,
             )),
         }
     }
+
+    pub fn set_text(&mut self, text: impl Into<gfx::DisplayText>) {
+        self.label.set_text(text);
+        ui::layout::update_layout(self);
+    }
+
+    #[inline]
+    pub fn text(&self) -> &gfx::DisplayText {
+        self.label.text()
+    }
+
+    #[inline]
+    pub fn set_checked(&mut self, checked: bool) {
+        self.check_mark.set_checked(checked);
+    }
+
+    #[inline]
+    pub fn checked(&self) -> bool {
+        self.check_mark.checked()
+    }
+
+    #[inline]
+    pub fn toggle(&mut self) {
+        self.check_mark.toggle();
+    }
 }
 
 impl<T: 'static> ui::Element for CheckBox<T> {
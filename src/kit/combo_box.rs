@@ -1,11 +1,42 @@
 use {
     crate::{kit, prelude::*, theme, ui},
+    as_any::Downcast,
     reclutch::display as gfx,
 };
 
+/// An item which can be listed by a [`ComboBox`](ComboBox); the item itself is the payload, retrievable through [`ComboBox::item`](ComboBox::item).
+pub trait ComboItem: as_any::AsAny {
+    /// Returns the text to display for the item.
+    fn text(&self) -> String;
+}
+
+impl as_any::Downcast for dyn ComboItem {}
+
+impl ComboItem for String {
+    #[inline]
+    fn text(&self) -> String {
+        self.clone()
+    }
+}
+
+impl ComboItem for &'static str {
+    #[inline]
+    fn text(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// A combo was selected from the list of a [`ComboBox`](ComboBox), either by clicking it or using the keyboard. Contains the index of the combo.
+pub struct ComboSelectedEvent(pub usize);
+
+/// Text was entered into an [editable](ComboBox::set_editable) [`ComboBox`](ComboBox) and committed with Enter, rather than selected from the list.
+pub struct ComboTextEvent(pub String);
+
 pub struct ComboListItem<T: 'static> {
     label: kit::Label<T>,
     selected: bool,
+    highlighted: bool,
+    pressed: bool,
 
     painter: theme::Painter<Self>,
     common: ui::CommonRef,
@@ -17,16 +48,31 @@ impl<T: 'static> ComboListItem<T> {
     pub fn new(parent: ui::CommonRef, aux: &mut ui::Aux<T>) -> Self {
         let common = ui::CommonRef::new(parent);
 
+        let theme_listener = aux.listen::<kit::ReadWrite<Self>>().and_on(
+            aux.id,
+            |(obj, aux), _: &ui::ThemeChangedEvent| {
+                theme::refresh_painter(obj, |x| &mut x.painter, aux);
+                obj.resize();
+            },
+        );
+
         ComboListItem {
             label: kit::Label::new(common.clone(), aux),
             selected: false,
+            highlighted: false,
+            pressed: false,
 
-            painter: theme::get_painter(aux.theme.as_ref(), theme::painters::COMBO_LIST_ITEM),
+            painter: theme::get_painter(aux, theme::painters::COMBO_LIST_ITEM),
             common,
-            listeners: ui::ListenerList::new(vec![]),
+            listeners: ui::ListenerList::new(vec![theme_listener]),
             components: ui::ComponentList::new().and_push(kit::InteractionState::new(
                 aux,
-                kit::interaction_forwarder(None),
+                |obj: &mut Self, aux, event| {
+                    if let kit::InteractionEvent::Press(_) = event {
+                        obj.pressed = true;
+                    }
+                    kit::interaction_forwarder(None)(obj, aux, event);
+                },
                 None,
                 None,
             )),
@@ -55,6 +101,23 @@ impl<T: 'static> ComboListItem<T> {
         self.selected
     }
 
+    /// Changes whether the item is highlighted (e.g. by keyboard navigation), as opposed to selected.
+    pub fn set_highlighted(&mut self, highlighted: bool) {
+        self.highlighted = highlighted;
+        self.repaint();
+    }
+
+    #[inline]
+    pub fn highlighted(&self) -> bool {
+        self.highlighted
+    }
+
+    /// Returns `true` if the item was pressed since the last call, resetting it.
+    #[inline]
+    pub fn take_pressed(&mut self) -> bool {
+        std::mem::replace(&mut self.pressed, false)
+    }
+
     fn resize(&mut self) {
         self.set_size(self.label.bounds().size);
         self.repaint();
@@ -90,9 +153,15 @@ impl<T: 'static> ui::WidgetChildren<T> for ComboListItem<T> {
     crate::children![for <T>; label];
 }
 
+/// Dropdown list of a [`ComboBox`](ComboBox).
+///
+/// Only the combos matching the [filter](ComboList::set_filter) are shown; indices are always those of the full list of combos.
 pub struct ComboList<T: 'static> {
     combos: Vec<String>,
+    filter: String,
+    shown: Vec<usize>,
     items: Vec<ComboListItem<T>>,
+    highlighted: Option<usize>,
 
     painter: theme::Painter<Self>,
     common: ui::CommonRef,
@@ -113,13 +182,24 @@ impl<T: 'static> ComboList<T> {
             },
         );
 
+        let theme_listener = aux.listen::<kit::ReadWrite<Self>>().and_on(
+            aux.id,
+            |(obj, aux), _: &ui::ThemeChangedEvent| {
+                theme::refresh_painter(obj, |x| &mut x.painter, aux);
+                obj.update_items(aux);
+            },
+        );
+
         ComboList {
             combos: Vec::new(),
+            filter: String::new(),
+            shown: Vec::new(),
             items: Vec::new(),
+            highlighted: None,
 
-            painter: theme::get_painter(aux.theme.as_ref(), theme::painters::COMBO_LIST),
+            painter: theme::get_painter(aux, theme::painters::COMBO_LIST),
             common,
-            listeners: ui::ListenerList::new(vec![focus_listener]),
+            listeners: ui::ListenerList::new(vec![focus_listener, theme_listener]),
             components: ui::ComponentList::new().and_push(kit::InteractionState::new(
                 aux,
                 kit::interaction_forwarder(None),
@@ -139,15 +219,71 @@ impl<T: 'static> ComboList<T> {
         &self.combos
     }
 
+    /// Only shows the combos containing `filter` (ignoring case), highlighting the first which starts with it.
+    pub fn set_filter(&mut self, filter: impl ToString, aux: &mut ui::Aux<T>) {
+        self.filter = filter.to_string();
+        self.update_items(aux);
+
+        let filter = self.filter.to_lowercase();
+        let highlighted = self
+            .shown
+            .iter()
+            .copied()
+            .find(|&x| self.combos[x].to_lowercase().starts_with(&filter))
+            .or_else(|| self.shown.first().copied());
+        self.set_highlighted(highlighted);
+    }
+
+    #[inline]
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    /// Returns the indices of the combos which are shown (i.e. match the filter).
+    #[inline]
+    pub fn shown(&self) -> &[usize] {
+        &self.shown
+    }
+
+    /// Highlights the combo at `index`, or none if `None` or it isn't shown.
+    pub fn set_highlighted(&mut self, index: impl Into<Option<usize>>) {
+        let index = index.into();
+        self.highlighted = index.filter(|x| self.shown.contains(x));
+        for (&i, item) in self.shown.iter().zip(self.items.iter_mut()) {
+            item.set_highlighted(Some(i) == self.highlighted);
+        }
+    }
+
+    #[inline]
+    pub fn highlighted(&self) -> Option<usize> {
+        self.highlighted
+    }
+
+    /// Returns the index of the item pressed since the last call, if any.
+    pub fn take_pressed(&mut self) -> Option<usize> {
+        let mut pressed = None;
+        for (&i, item) in self.shown.iter().zip(self.items.iter_mut()) {
+            if item.take_pressed() {
+                pressed = Some(i);
+            }
+        }
+        pressed
+    }
+
     fn update_items(&mut self, aux: &mut ui::Aux<T>) {
         let mut stack = ui::layout::VStack::new().into_node(None);
 
-        self.items = Vec::with_capacity(self.combos.len());
+        let filter = self.filter.to_lowercase();
+        self.shown = (0..self.combos.len())
+            .filter(|&x| self.combos[x].to_lowercase().contains(&filter))
+            .collect();
+
+        self.items = Vec::with_capacity(self.shown.len());
         let w = self.size().width;
         let mut h = 0.;
-        for combo in &self.combos {
+        for &i in &self.shown {
             let mut item = ComboListItem::new(self.common.clone(), aux);
-            item.set_text(combo);
+            item.set_text(&self.combos[i]);
 
             let item_size = item.size();
             h += item_size.height;
@@ -156,6 +292,7 @@ impl<T: 'static> ComboList<T> {
             stack.push(&item, None);
             self.items.push(item);
         }
+        self.set_highlighted(self.highlighted);
         self.set_size(gfx::Size::new(w, h));
 
         self.set_layout(stack);
@@ -206,10 +343,15 @@ impl<T: 'static> ui::WidgetChildren<T> for ComboList<T> {
 }
 
 pub struct ComboBox<T: 'static> {
+    items: Vec<Box<dyn ComboItem>>,
     combos: Vec<String>,
     label: kit::Label<T>,
+    editor: kit::TextBox<T>,
+    editable: bool,
     list: Option<ComboList<T>>,
     selected: Option<usize>,
+    last_typed: Option<std::time::Instant>,
+    interaction: kit::Interaction,
 
     painter: theme::Painter<Self>,
     common: ui::CommonRef,
@@ -223,26 +365,93 @@ impl<T: 'static> ComboBox<T> {
 
         let focus_listener = kit::focus_handler(
             aux,
-            kit::focus_forwarder(),
+            |obj: &mut Self, aux, event| {
+                if obj.interaction.apply_focus(event) {
+                    obj.repaint();
+                }
+                kit::focus_forwarder()(obj, aux, event);
+            },
             kit::FocusConfig {
                 interaction_handler: common.with(|x| x.id()),
                 mouse_trigger: Default::default(),
             },
         );
 
+        let keyboard_listener = kit::keyboard_handler(aux, |obj: &mut Self, aux, event| {
+            match event {
+                kit::KeyboardEvent::KeyPress(key) => obj.navigate(key, aux),
+                kit::KeyboardEvent::Text(c) => obj.type_ahead(c, aux),
+                _ => {}
+            }
+            kit::keyboard_forwarder()(obj, aux, event);
+        });
+
+        let editor = kit::TextBox::new(common.clone(), aux);
+        editor.set_visible(ui::Visibility::None);
+
+        let outside_listener = aux.listen::<kit::ReadWrite<Self>>().and_on(
+            aux.id,
+            |(obj, _), event: &ui::MousePressEvent| {
+                // looked at regardless of consumption; any press outside the box and its list closes the list.
+                let &(_, pos) = event.0.get();
+                let inside_list = obj
+                    .list
+                    .as_ref()
+                    .map_or(false, |x| x.bounds().contains(pos));
+                if obj.is_combo_list_open() && !obj.bounds().contains(pos) && !inside_list {
+                    obj.hide_combo_list();
+                }
+            },
+        );
+
+        let editor_listener = aux
+            .listen::<kit::ReadWrite<Self>>()
+            .and_on(editor.id(), |(obj, aux), event: &kit::KeyPressEvent| {
+                obj.navigate(event.0, aux)
+            })
+            .and_on(editor.id(), |(obj, aux), event: &kit::TextChangedEvent| {
+                obj.suggest(&event.new, aux)
+            });
+
+        let theme_listener = aux.listen::<kit::ReadWrite<Self>>().and_on(
+            aux.id,
+            |(obj, aux), _: &ui::ThemeChangedEvent| {
+                theme::refresh_painter(obj, |x| &mut x.painter, aux);
+                obj.resize();
+            },
+        );
+
         ComboBox {
+            items: Vec::new(),
             combos: Vec::new(),
             label: kit::Label::new(common.clone(), aux),
+            editor,
+            editable: false,
             list: None,
             selected: None,
+            last_typed: None,
+            interaction: Default::default(),
 
-            painter: theme::get_painter(aux.theme.as_ref(), theme::painters::COMBO_BOX),
+            painter: theme::get_painter(aux, theme::painters::COMBO_BOX),
             common,
-            listeners: ui::ListenerList::new(vec![focus_listener]),
+            listeners: ui::ListenerList::new(vec![
+                focus_listener,
+                keyboard_listener,
+                outside_listener,
+                editor_listener,
+                theme_listener,
+            ]),
             components: ui::ComponentList::new().and_push(kit::InteractionState::new(
                 aux,
                 |obj: &mut Self, aux, ev| {
+                    if obj.interaction.apply(ev) {
+                        obj.repaint();
+                    }
+
                     match ev {
+                        kit::InteractionEvent::Press(_) if obj.is_combo_list_open() => {
+                            obj.hide_combo_list()
+                        }
                         kit::InteractionEvent::Press(_) => obj.show_combo_list(aux),
                         _ => {}
                     }
@@ -255,24 +464,42 @@ impl<T: 'static> ComboBox<T> {
         }
     }
 
-    pub fn set_combos(&mut self, combos: &[String], aux: &mut ui::Aux<T>) {
-        self.combos = combos.to_vec();
+    /// Changes the listed items, selecting the first.
+    pub fn set_combos<I: ComboItem + Clone>(&mut self, combos: &[I], aux: &mut ui::Aux<T>) {
+        self.items = combos
+            .iter()
+            .map(|x| Box::new(x.clone()) as Box<dyn ComboItem>)
+            .collect();
+        self.combos = self.items.iter().map(|x| x.text()).collect();
         self.selected = if self.combos.is_empty() {
             None
         } else {
             Some(0)
         };
-        self.show_combo_list(aux);
+        if self.is_combo_list_open() {
+            self.show_combo_list(aux);
+        }
         self.repaint();
         self.update_label();
         self.resize();
     }
 
+    /// Returns the display text of each item.
     #[inline]
     pub fn combos(&self) -> &[String] {
         &self.combos
     }
 
+    /// Returns the item at `index`, if it is of type `I`.
+    pub fn item<I: ComboItem>(&self, index: usize) -> Option<&I> {
+        self.items.get(index)?.downcast_ref::<I>()
+    }
+
+    /// Returns the selected item, if it is of type `I`.
+    pub fn selected_item<I: ComboItem>(&self) -> Option<&I> {
+        self.item(self.selected?)
+    }
+
     pub fn set_selected(&mut self, selected: usize) {
         self.selected = Some(selected);
         self.repaint();
@@ -285,20 +512,54 @@ impl<T: 'static> ComboBox<T> {
         self.selected
     }
 
+    /// Changes whether the display area is a text box accepting free text entry, in which case the list offers suggestions.
+    pub fn set_editable(&mut self, editable: bool) {
+        self.editable = editable;
+        let (label, editor) = if editable {
+            (ui::Visibility::None, ui::Visibility::All)
+        } else {
+            (ui::Visibility::All, ui::Visibility::None)
+        };
+        self.label.set_visible(label);
+        self.editor.set_visible(editor);
+        self.update_label();
+        self.resize();
+    }
+
+    #[inline]
+    pub fn editable(&self) -> bool {
+        self.editable
+    }
+
+    /// Returns the text entered into the display area when [editable](ComboBox::set_editable).
+    #[inline]
+    pub fn text(&self) -> &str {
+        self.editor.text()
+    }
+
     pub fn selected_combo(&self) -> Option<&str> {
         self.selected
             .and_then(|x| self.combos.get(x).map(|x| &x[..]))
     }
 
+    /// Opens the list of combos directly below the box.
     pub fn show_combo_list(&mut self, aux: &mut ui::Aux<T>) {
+        // the list is positioned relative to the box.
+        let size = self.size();
         let mut list = ComboList::new(self.common.clone(), aux);
+        list.set_rect(gfx::Rect::new(
+            gfx::Point::new(0., size.height),
+            gfx::Size::new(size.width, 0.),
+        ));
         list.set_combos(&self.combos, aux);
+        list.set_highlighted(self.selected);
         self.list = Some(list);
     }
 
     #[inline]
     pub fn hide_combo_list(&mut self) {
         self.list = None;
+        self.repaint();
     }
 
     #[inline]
@@ -306,22 +567,112 @@ impl<T: 'static> ComboBox<T> {
         !self.list.is_none()
     }
 
+    /// Handles keyboard navigation.
+    ///
+    /// While the list is open, up/down move the highlight (through the shown combos), enter selects the highlighted combo
+    /// and escape closes the list. Otherwise, up/down cycle through the combos.
+    fn navigate(&mut self, key: ui::KeyInput, aux: &mut ui::Aux<T>) {
+        if self.combos.is_empty() {
+            return;
+        }
+
+        let step = |index: Option<usize>, up: bool, len: usize| match index {
+            Some(i) if up => (i + len - 1) % len,
+            Some(i) => (i + 1) % len,
+            None => 0,
+        };
+
+        if let Some(list) = &mut self.list {
+            match key {
+                ui::KeyInput::Up | ui::KeyInput::Down if !list.shown().is_empty() => {
+                    let shown = list.shown();
+                    let position = list
+                        .highlighted()
+                        .and_then(|x| shown.iter().position(|&y| y == x));
+                    let highlighted = shown[step(position, key == ui::KeyInput::Up, shown.len())];
+                    list.set_highlighted(highlighted);
+                }
+                ui::KeyInput::Back if !self.editable && !list.filter().is_empty() => {
+                    let mut filter = list.filter().to_string();
+                    filter.pop();
+                    list.set_filter(filter, aux);
+                    self.last_typed = Some(std::time::Instant::now());
+                }
+                ui::KeyInput::Return | ui::KeyInput::NumpadEnter => {
+                    if let Some(index) = list.highlighted() {
+                        self.set_selected(index);
+                        self.emit(aux, ComboSelectedEvent(index));
+                    } else if self.editable {
+                        self.emit(aux, ComboTextEvent(self.editor.text().to_string()));
+                    }
+                    self.hide_combo_list();
+                }
+                ui::KeyInput::Escape => self.hide_combo_list(),
+                _ => {}
+            }
+        } else {
+            match key {
+                ui::KeyInput::Up | ui::KeyInput::Down => {
+                    let index = step(self.selected, key == ui::KeyInput::Up, self.combos.len());
+                    self.set_selected(index);
+                    self.emit(aux, ComboSelectedEvent(index));
+                }
+                ui::KeyInput::Return | ui::KeyInput::NumpadEnter if self.editable => {
+                    self.emit(aux, ComboTextEvent(self.editor.text().to_string()));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Narrows the open list down to the combos containing the characters typed in quick succession.
+    fn type_ahead(&mut self, c: char, aux: &mut ui::Aux<T>) {
+        if self.editable || c.is_control() {
+            return;
+        }
+
+        if let Some(list) = &mut self.list {
+            let mut filter = list.filter().to_string();
+            filter.push(c);
+            list.set_filter(filter, aux);
+            self.last_typed = Some(std::time::Instant::now());
+        }
+    }
+
+    /// Opens the list as the user types, highlighting the first combo which starts with `text`.
+    fn suggest(&mut self, text: &str, aux: &mut ui::Aux<T>) {
+        if !self.is_combo_list_open() {
+            self.show_combo_list(aux);
+        }
+
+        let text = text.to_lowercase();
+        let suggestion = if text.is_empty() {
+            None
+        } else {
+            self.combos
+                .iter()
+                .position(|x| x.to_lowercase().starts_with(&text))
+        };
+
+        if let Some(list) = &mut self.list {
+            list.set_highlighted(suggestion);
+        }
+    }
+
     fn update_label(&mut self) {
-        self.label.set_text(
-            self.selected_combo()
-                .map(|x| x.to_string())
-                .unwrap_or_default(),
-        );
+        let text = self
+            .selected_combo()
+            .map(|x| x.to_string())
+            .unwrap_or_default();
+        if self.editable {
+            self.editor.set_text(&text);
+        }
+        self.label.set_text(text);
     }
 
     fn resize(&mut self) {
-        let metrics = theme::multi_metrics(
-            self,
-            &[theme::metrics::PADDING_X, theme::metrics::PADDING_Y],
-            |x| &mut x.painter,
-        );
-
-        let padding = gfx::Size::new(metrics[0].unwrap(), metrics[1].unwrap());
+        let padding = self.painter.padding(self);
+        let padding = gfx::Size::new(padding.horizontal(), padding.vertical());
         let label_bounds = self.label.bounds();
         self.set_size(label_bounds.size + padding);
 
@@ -331,6 +682,22 @@ impl<T: 'static> ComboBox<T> {
         let y = ui::layout::align_y(label_bounds, bounds, ui::layout::Alignment::Middle, 0.) - 1.;
 
         self.label.set_position(gfx::Point::new(x, y));
+
+        // the editor covers the display area, leaving the arrow uncovered so that it still opens the list.
+        self.editor.set_rect(gfx::Rect::new(
+            gfx::Point::new(x, y),
+            gfx::Size::new(
+                (bounds.size.width - padding.width).max(0.),
+                label_bounds.size.height,
+            ),
+        ));
+    }
+}
+
+impl<T: 'static> kit::Interactive for ComboBox<T> {
+    #[inline]
+    fn interaction(&self) -> kit::Interaction {
+        self.interaction
     }
 }
 
@@ -342,11 +709,28 @@ impl<T: 'static> ui::Element for ComboBox<T> {
         &self.common
     }
 
-    #[inline]
     fn update(&mut self, aux: &mut ui::Aux<Self::Aux>) {
         ui::dispatch_components(self, aux, |x| &mut x.components).unwrap();
         ui::dispatch_list::<kit::ReadWrite<Self>, _>((self, aux), |(x, _)| &mut x.listeners);
 
+        if let Some(last_typed) = self.last_typed {
+            if last_typed.elapsed() >= aux.interaction.type_ahead_timeout {
+                self.last_typed = None;
+                if let Some(list) = &mut self.list {
+                    let highlighted = list.highlighted();
+                    list.set_filter("", aux);
+                    list.set_highlighted(highlighted);
+                }
+            }
+        }
+
+        // the list (being a child) has already handled its presses by now.
+        if let Some(index) = self.list.as_mut().and_then(|x| x.take_pressed()) {
+            self.set_selected(index);
+            self.emit(aux, ComboSelectedEvent(index));
+            self.hide_combo_list();
+        }
+
         ui::propagate_repaint(self);
     }
 
@@ -364,17 +748,17 @@ impl<T: 'static> ui::Element for ComboBox<T> {
 impl<T: 'static> ui::WidgetChildren<T> for ComboBox<T> {
     fn children(&self) -> Vec<&dyn ui::WidgetChildren<T>> {
         if let Some(list) = &self.list {
-            vec![&self.label, list]
+            vec![&self.label, &self.editor, list]
         } else {
-            vec![&self.label]
+            vec![&self.label, &self.editor]
         }
     }
 
     fn children_mut(&mut self) -> Vec<&mut dyn ui::WidgetChildren<T>> {
         if let Some(list) = &mut self.list {
-            vec![&mut self.label, list]
+            vec![&mut self.label, &mut self.editor, list]
         } else {
-            vec![&mut self.label]
+            vec![&mut self.label, &mut self.editor]
         }
     }
 }
@@ -0,0 +1,172 @@
+use {
+    crate::{kit, prelude::*, ui},
+    reclutch::display as gfx,
+};
+
+/// The text of an [`EditableLabel`](EditableLabel) was edited and committed. Contains the new text.
+pub struct TextCommittedEvent(pub String);
+
+/// Label which can be edited in place.
+///
+/// Double-clicking the label (as counted by [`Aux::click_count`](ui::Aux::click_count)), or pressing F2 while it has focus, swaps it for a text box.
+/// Pressing Enter or moving focus away commits the edit, emitting [`TextCommittedEvent`](TextCommittedEvent), whereas pressing Escape reverts it.
+pub struct EditableLabel<T: 'static> {
+    label: kit::Label<T>,
+    text_box: kit::TextBox<T>,
+    editing: bool,
+
+    common: ui::CommonRef,
+    listeners: ui::ListenerList<kit::ReadWrite<Self>>,
+    components: ui::ComponentList<Self>,
+}
+
+impl<T: 'static> EditableLabel<T> {
+    pub fn new(parent: ui::CommonRef, aux: &mut ui::Aux<T>) -> Self {
+        let common = ui::CommonRef::new(parent);
+
+        let label = kit::Label::new(common.clone(), aux);
+        let text_box = kit::TextBox::new(common.clone(), aux);
+        text_box.set_visible(ui::Visibility::None);
+
+        let focus_listener = kit::focus_handler(
+            aux,
+            kit::focus_forwarder(),
+            kit::FocusConfig {
+                mouse_trigger: Default::default(),
+                interaction_handler: common.with(|x| x.id()),
+            },
+        );
+
+        let keyboard_listener = kit::keyboard_handler(aux, |obj: &mut Self, aux, event| {
+            if let kit::KeyboardEvent::KeyPress(ui::KeyInput::F2) = event {
+                obj.begin_edit(aux);
+            }
+            kit::keyboard_forwarder()(obj, aux, event);
+        });
+
+        let text_box_listener = aux
+            .listen::<kit::ReadWrite<Self>>()
+            .and_on(
+                text_box.id(),
+                |(obj, aux), event: &kit::KeyPressEvent| match event.0 {
+                    ui::KeyInput::Return | ui::KeyInput::NumpadEnter => obj.commit(aux),
+                    ui::KeyInput::Escape => obj.cancel(aux),
+                    _ => {}
+                },
+            )
+            .and_on(text_box.id(), |(obj, aux), _: &kit::FocusLostEvent| {
+                obj.commit(aux)
+            });
+
+        EditableLabel {
+            label,
+            text_box,
+            editing: false,
+
+            common,
+            listeners: ui::ListenerList::new(vec![
+                focus_listener,
+                keyboard_listener,
+                text_box_listener,
+            ]),
+            components: ui::ComponentList::new().and_push(kit::InteractionState::new(
+                aux,
+                |obj: &mut Self, aux, event| {
+                    if let kit::InteractionEvent::Press(_) = event {
+                        if aux.click_count() == 2 {
+                            obj.begin_edit(aux);
+                        }
+                    }
+                    kit::interaction_forwarder(None)(obj, aux, event);
+                },
+                None,
+                None,
+            )),
+        }
+    }
+
+    pub fn set_text(&mut self, text: impl ToString) {
+        self.label.set_text(text.to_string());
+        self.set_size(self.label.rect().size);
+        self.repaint();
+    }
+
+    pub fn text(&self) -> String {
+        match self.label.text() {
+            gfx::DisplayText::Simple(s) => s.clone(),
+            _ => String::new(),
+        }
+    }
+
+    /// Returns `true` if the label is currently being edited.
+    #[inline]
+    pub fn editing(&self) -> bool {
+        self.editing
+    }
+
+    /// Swaps the label for a text box containing its text, with all the text selected.
+    pub fn begin_edit(&mut self, aux: &mut ui::Aux<T>) {
+        if self.editing {
+            return;
+        }
+
+        self.editing = true;
+        self.text_box.set_text(self.text());
+        self.text_box.clear_history();
+        self.text_box.select_all();
+        self.text_box.set_size(self.size());
+        self.text_box.set_visible(ui::Visibility::All);
+        self.label.set_visible(ui::Visibility::None);
+        aux.grab_focus(self.text_box.common().clone());
+        self.repaint();
+    }
+
+    /// Ends editing, keeping the edited text and emitting [`TextCommittedEvent`](TextCommittedEvent).
+    pub fn commit(&mut self, aux: &mut ui::Aux<T>) {
+        if !self.editing {
+            return;
+        }
+
+        let text = self.text_box.text().to_string();
+        self.end_edit(aux);
+        self.set_text(text.clone());
+        self.emit(aux, TextCommittedEvent(text));
+    }
+
+    /// Ends editing, discarding the edited text.
+    pub fn cancel(&mut self, aux: &mut ui::Aux<T>) {
+        if self.editing {
+            self.end_edit(aux);
+        }
+    }
+
+    fn end_edit(&mut self, aux: &mut ui::Aux<T>) {
+        self.editing = false;
+        self.text_box.set_visible(ui::Visibility::None);
+        self.label.set_visible(ui::Visibility::All);
+        if aux.has_focus(self.text_box.common()) {
+            aux.grab_focus(self.common.clone());
+        }
+        self.repaint();
+    }
+}
+
+impl<T: 'static> ui::Element for EditableLabel<T> {
+    type Aux = T;
+
+    #[inline]
+    fn common(&self) -> &ui::CommonRef {
+        &self.common
+    }
+
+    fn update(&mut self, aux: &mut ui::Aux<T>) {
+        ui::dispatch_components(self, aux, |x| &mut x.components).unwrap();
+        ui::dispatch_list::<kit::ReadWrite<Self>, _>((self, aux), |(x, _)| &mut x.listeners);
+
+        ui::propagate_repaint(self);
+    }
+}
+
+impl<T: 'static> ui::WidgetChildren<T> for EditableLabel<T> {
+    crate::children![for <T>; label, text_box];
+}
@@ -0,0 +1,382 @@
+use {
+    crate::{kit, prelude::*, theme, ui},
+    reclutch::display as gfx,
+    std::path::{Path, PathBuf},
+};
+
+/// A file was chosen from a [`FileBrowser`](FileBrowser).
+pub struct FileChosenEvent(pub PathBuf);
+/// The directory listed by a [`FileBrowser`](FileBrowser) changed.
+pub struct DirectoryChangedEvent(pub PathBuf);
+
+/// The kind of entry represented by a [`FileBrowserItem`](FileBrowserItem).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FileKind {
+    /// The parent directory (`..`).
+    Parent,
+    Directory,
+    File,
+}
+
+/// A single row within a [`FileBrowser`](FileBrowser); an icon followed by the entry name.
+pub struct FileBrowserItem<T: 'static> {
+    label: kit::Label<T>,
+    path: PathBuf,
+    kind: FileKind,
+
+    painter: theme::Painter<Self>,
+    common: ui::CommonRef,
+    components: ui::ComponentList<Self>,
+}
+
+impl<T: 'static> FileBrowserItem<T> {
+    pub fn new(parent: ui::CommonRef, aux: &mut ui::Aux<T>, path: PathBuf, kind: FileKind) -> Self {
+        let common = ui::CommonRef::new(parent);
+
+        let mut item = FileBrowserItem {
+            label: kit::Label::new(common.clone(), aux),
+            path,
+            kind,
+
+            painter: theme::get_painter(aux, theme::painters::FILE_BROWSER_ITEM),
+            common,
+            components: ui::ComponentList::new().and_push(kit::InteractionState::new(
+                aux,
+                kit::interaction_forwarder(None),
+                None,
+                None,
+            )),
+        };
+
+        let name = match kind {
+            FileKind::Parent => String::from(".."),
+            _ => item
+                .path
+                .file_name()
+                .map(|x| x.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+        };
+        item.label.set_text(name);
+        item.resize();
+
+        item
+    }
+
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    #[inline]
+    pub fn kind(&self) -> FileKind {
+        self.kind
+    }
+
+    fn resize(&mut self) {
+        let icon_size = self.painter.icon_size(self);
+        let spacing = self.painter.icon_spacing(self);
+
+        let label_size = self.label.rect().size;
+        self.set_size(gfx::Size::new(
+            icon_size + spacing + label_size.width,
+            label_size.height.max(icon_size),
+        ));
+
+        let y = ui::layout::align_y(
+            self.label.rect(),
+            gfx::Rect::new(Default::default(), self.size()),
+            ui::layout::Alignment::Middle,
+            0.,
+        );
+        self.label
+            .set_position(gfx::Point::new(icon_size + spacing, y));
+        self.repaint();
+    }
+}
+
+impl<T: 'static> ui::Element for FileBrowserItem<T> {
+    type Aux = T;
+
+    #[inline]
+    fn common(&self) -> &ui::CommonRef {
+        &self.common
+    }
+
+    #[inline]
+    fn update(&mut self, aux: &mut ui::Aux<Self::Aux>) {
+        ui::dispatch_components(self, aux, |x| &mut x.components).unwrap();
+    }
+
+    fn draw(&mut self, display: &mut dyn gfx::GraphicsDisplay, aux: &mut ui::Aux<Self::Aux>) {
+        ui::draw(
+            self,
+            |o, a| theme::paint(o, |o| &mut o.painter, a),
+            display,
+            aux,
+            None,
+        )
+    }
+}
+
+impl<T: 'static> ui::WidgetChildren<T> for FileBrowserItem<T> {
+    crate::children![for <T>; label];
+}
+
+/// Lists the contents of a directory, allowing the user to navigate into subfolders and choose a file.
+///
+/// Pressing a directory navigates into it (emitting [`DirectoryChangedEvent`](DirectoryChangedEvent)),
+/// whereas pressing a file emits [`FileChosenEvent`](FileChosenEvent).
+/// Directories are always listed, but files can be restricted to a set of extensions via [`set_filters`](FileBrowser::set_filters).
+pub struct FileBrowser<T: 'static> {
+    directory: PathBuf,
+    filters: Vec<String>,
+    show_hidden: bool,
+    items: Vec<FileBrowserItem<T>>,
+    item_listener: ui::Listener<kit::ReadWrite<Self>>,
+    pressed: Option<usize>,
+
+    painter: theme::Painter<Self>,
+    common: ui::CommonRef,
+    listeners: ui::ListenerList<kit::ReadWrite<Self>>,
+    components: ui::ComponentList<Self>,
+}
+
+impl<T: 'static> FileBrowser<T> {
+    pub fn new(parent: ui::CommonRef, aux: &mut ui::Aux<T>) -> Self {
+        let common = ui::CommonRef::new(parent);
+
+        let focus_listener = kit::focus_handler(
+            aux,
+            kit::focus_forwarder(),
+            kit::FocusConfig {
+                interaction_handler: common.with(|x| x.id()),
+                mouse_trigger: Default::default(),
+            },
+        );
+
+        let theme_listener = aux.listen::<kit::ReadWrite<Self>>().and_on(
+            aux.id,
+            |(obj, aux), _: &ui::ThemeChangedEvent| {
+                theme::refresh_painter(obj, |x| &mut x.painter, aux);
+                // rebuild the items with painters from the new theme, without reading the directory again.
+                let entries = obj
+                    .items
+                    .iter()
+                    .map(|x| (x.path().to_path_buf(), x.kind()))
+                    .collect();
+                obj.update_items(entries, aux);
+            },
+        );
+
+        FileBrowser {
+            directory: PathBuf::new(),
+            filters: Vec::new(),
+            show_hidden: false,
+            items: Vec::new(),
+            item_listener: aux.listen(),
+            pressed: None,
+
+            painter: theme::get_painter(aux, theme::painters::FILE_BROWSER),
+            common,
+            listeners: ui::ListenerList::new(vec![focus_listener, theme_listener]),
+            components: ui::ComponentList::new().and_push(kit::InteractionState::new(
+                aux,
+                kit::interaction_forwarder(None),
+                None,
+                None,
+            )),
+        }
+    }
+
+    /// Changes the listed directory and refreshes the entries.
+    ///
+    /// On failure, the previously listed directory is kept.
+    pub fn set_directory(
+        &mut self,
+        directory: impl Into<PathBuf>,
+        aux: &mut ui::Aux<T>,
+    ) -> std::io::Result<()> {
+        let directory = directory.into();
+        let entries = self.read_entries(&directory)?;
+        self.directory = directory;
+        self.update_items(entries, aux);
+        Ok(())
+    }
+
+    /// Returns the currently listed directory.
+    #[inline]
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Re-reads the current directory.
+    pub fn refresh(&mut self, aux: &mut ui::Aux<T>) -> std::io::Result<()> {
+        let directory = self.directory.clone();
+        self.set_directory(directory, aux)
+    }
+
+    /// Restricts the listed files to the given extensions (without the leading dot, e.g. `"png"`).
+    ///
+    /// An empty list will show all files.
+    pub fn set_filters(&mut self, filters: &[&str], aux: &mut ui::Aux<T>) -> std::io::Result<()> {
+        self.filters = filters.iter().map(|x| x.to_lowercase()).collect();
+        self.refresh(aux)
+    }
+
+    #[inline]
+    pub fn filters(&self) -> &[String] {
+        &self.filters
+    }
+
+    /// Changes whether entries starting with a `.` are listed.
+    pub fn set_show_hidden(
+        &mut self,
+        show_hidden: bool,
+        aux: &mut ui::Aux<T>,
+    ) -> std::io::Result<()> {
+        self.show_hidden = show_hidden;
+        self.refresh(aux)
+    }
+
+    #[inline]
+    pub fn show_hidden(&self) -> bool {
+        self.show_hidden
+    }
+
+    /// Navigates to the parent of the current directory, if there is one.
+    pub fn navigate_up(&mut self, aux: &mut ui::Aux<T>) -> std::io::Result<()> {
+        if let Some(parent) = self.directory.parent().map(|x| x.to_path_buf()) {
+            self.set_directory(parent, aux)?;
+            self.emit(aux, DirectoryChangedEvent(self.directory.clone()));
+        }
+        Ok(())
+    }
+
+    fn matches_filters(&self, path: &Path) -> bool {
+        self.filters.is_empty()
+            || path
+                .extension()
+                .map(|x| self.filters.contains(&x.to_string_lossy().to_lowercase()))
+                .unwrap_or(false)
+    }
+
+    fn read_entries(&self, directory: &Path) -> std::io::Result<Vec<(PathBuf, FileKind)>> {
+        let mut entries = Vec::new();
+        for entry in std::fs::read_dir(directory)? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if !self.show_hidden && entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+
+            if entry.file_type()?.is_dir() {
+                entries.push((path, FileKind::Directory));
+            } else if self.matches_filters(&path) {
+                entries.push((path, FileKind::File));
+            }
+        }
+
+        // directories first, then alphabetically.
+        entries.sort_by(|a, b| (a.1, &a.0).cmp(&(b.1, &b.0)));
+
+        if let Some(parent) = directory.parent() {
+            entries.insert(0, (parent.to_path_buf(), FileKind::Parent));
+        }
+
+        Ok(entries)
+    }
+
+    fn update_items(&mut self, entries: Vec<(PathBuf, FileKind)>, aux: &mut ui::Aux<T>) {
+        let mut stack = ui::layout::VStack::new().into_node(None);
+        let mut listener = aux.listen::<kit::ReadWrite<Self>>();
+
+        self.items = Vec::with_capacity(entries.len());
+        let w = self.size().width;
+        let mut h = 0.;
+        for (i, (path, kind)) in entries.into_iter().enumerate() {
+            let mut item = FileBrowserItem::new(self.common.clone(), aux, path, kind);
+
+            let item_size = item.size();
+            h += item_size.height;
+            item.set_size(gfx::Size::new(w, item_size.height));
+
+            listener.on(item.id(), move |(obj, _), _: &kit::PressEvent| {
+                obj.pressed = Some(i);
+            });
+
+            stack.push(&item, None);
+            self.items.push(item);
+        }
+        self.set_size(gfx::Size::new(w, h));
+
+        self.item_listener = listener;
+        self.set_layout(stack);
+        ui::layout::update_layout(self);
+        self.repaint();
+    }
+
+    fn activate(&mut self, index: usize, aux: &mut ui::Aux<T>) {
+        let (path, kind) = match self.items.get(index) {
+            Some(item) => (item.path().to_path_buf(), item.kind()),
+            None => return,
+        };
+
+        match kind {
+            FileKind::Parent | FileKind::Directory => {
+                if self.set_directory(path, aux).is_ok() {
+                    self.emit(aux, DirectoryChangedEvent(self.directory.clone()));
+                }
+            }
+            FileKind::File => self.emit(aux, FileChosenEvent(path)),
+        }
+    }
+}
+
+impl<T: 'static> ui::Element for FileBrowser<T> {
+    type Aux = T;
+
+    #[inline]
+    fn common(&self) -> &ui::CommonRef {
+        &self.common
+    }
+
+    fn update(&mut self, aux: &mut ui::Aux<Self::Aux>) {
+        ui::dispatch_components(self, aux, |x| &mut x.components).unwrap();
+        ui::dispatch_list::<kit::ReadWrite<Self>, _>((self, aux), |(x, _)| &mut x.listeners);
+        ui::dispatch((self, aux), |(x, _): (&mut Self, _)| &mut x.item_listener);
+
+        // the items are rebuilt on navigation, which can't happen while the item listener is being dispatched.
+        if let Some(index) = self.pressed.take() {
+            self.activate(index, aux);
+        }
+
+        ui::propagate_repaint(self);
+    }
+
+    fn draw(&mut self, display: &mut dyn gfx::GraphicsDisplay, aux: &mut ui::Aux<Self::Aux>) {
+        ui::draw(
+            self,
+            |o, a| theme::paint(o, |o| &mut o.painter, a),
+            display,
+            aux,
+            None,
+        )
+    }
+}
+
+impl<T: 'static> ui::WidgetChildren<T> for FileBrowser<T> {
+    fn children(&self) -> Vec<&dyn ui::WidgetChildren<T>> {
+        self.items
+            .iter()
+            .map(|x| x as &dyn ui::WidgetChildren<T>)
+            .collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn ui::WidgetChildren<T>> {
+        self.items
+            .iter_mut()
+            .map(|x| x as &mut dyn ui::WidgetChildren<T>)
+            .collect()
+    }
+}
@@ -0,0 +1,158 @@
+use {
+    crate::{kit, prelude::*, theme, ui},
+    reclutch::display as gfx,
+};
+
+/// A colored section of a [`Gauge`](Gauge), covering the values from `start` to `end`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GaugeZone {
+    pub start: f32,
+    pub end: f32,
+    pub color: gfx::Color,
+}
+
+/// How long the needle takes to ease towards a newly set value.
+const NEEDLE_DURATION: std::time::Duration = std::time::Duration::from_millis(300);
+
+/// Displays a bounded value on a circular arc, with a needle pointing at the current value.
+///
+/// When animated, the needle eases towards a newly set value over a short transition rather than jumping to it.
+pub struct Gauge<T: 'static> {
+    value: f32,
+    needle: theme::Animator<f32>,
+    range: (f32, f32),
+    zones: Vec<GaugeZone>,
+    animated: bool,
+
+    painter: theme::Painter<Self>,
+    common: ui::CommonRef,
+    listeners: ui::ListenerList<kit::ReadWrite<Self>>,
+}
+
+impl<T: 'static> Gauge<T> {
+    pub fn new(parent: ui::CommonRef, aux: &mut ui::Aux<T>) -> Self {
+        let theme_listener = aux.listen::<kit::ReadWrite<Self>>().and_on(
+            aux.id,
+            |(obj, aux), _: &ui::ThemeChangedEvent| {
+                theme::refresh_painter(obj, |x| &mut x.painter, aux);
+                theme::apply_size_hint(obj, |x| &mut x.painter);
+            },
+        );
+
+        let mut gauge = Gauge {
+            value: 0.,
+            needle: theme::Animator::new(0., NEEDLE_DURATION),
+            range: (0., 1.),
+            zones: Vec::new(),
+            animated: true,
+
+            painter: theme::get_painter(aux, theme::painters::GAUGE),
+            common: ui::CommonRef::new(parent),
+            listeners: ui::ListenerList::new(vec![theme_listener]),
+        };
+
+        theme::apply_size_hint(&mut gauge, |x| &mut x.painter);
+
+        gauge
+    }
+
+    /// Changes the value, clamped to the range.
+    pub fn set_value(&mut self, value: f32) {
+        self.value = value.max(self.range.0).min(self.range.1);
+        if self.animated {
+            self.needle.set_target(self.value);
+        } else {
+            self.needle.set_value(self.value);
+        }
+        self.repaint();
+    }
+
+    #[inline]
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// Returns the value the needle is currently pointing at.
+    ///
+    /// This only differs from [`value`](Gauge::value) while the needle is animating.
+    #[inline]
+    pub fn needle(&self) -> f32 {
+        self.needle.value()
+    }
+
+    /// Changes the lower and upper bounds of the value.
+    pub fn set_range(&mut self, min: f32, max: f32) {
+        self.range = (min, max);
+        self.needle.set_value(self.needle.value().max(min).min(max));
+        self.set_value(self.value);
+    }
+
+    #[inline]
+    pub fn range(&self) -> (f32, f32) {
+        self.range
+    }
+
+    pub fn set_zones(&mut self, zones: Vec<GaugeZone>) {
+        self.zones = zones;
+        self.repaint();
+    }
+
+    #[inline]
+    pub fn zones(&self) -> &[GaugeZone] {
+        &self.zones
+    }
+
+    /// Changes whether the needle eases towards new values.
+    pub fn set_animated(&mut self, animated: bool) {
+        self.animated = animated;
+        if !animated {
+            self.needle.set_value(self.value);
+            self.repaint();
+        }
+    }
+
+    #[inline]
+    pub fn animated(&self) -> bool {
+        self.animated
+    }
+
+    /// Returns where `value` lies within the range, from `0.0` to `1.0`.
+    pub fn fraction(&self, value: f32) -> f32 {
+        let span = self.range.1 - self.range.0;
+        if span <= 0. {
+            0.
+        } else {
+            ((value - self.range.0) / span).max(0.).min(1.)
+        }
+    }
+}
+
+impl<T: 'static> ui::Element for Gauge<T> {
+    type Aux = T;
+
+    #[inline]
+    fn common(&self) -> &ui::CommonRef {
+        &self.common
+    }
+
+    fn update(&mut self, aux: &mut ui::Aux<T>) {
+        ui::dispatch_list::<kit::ReadWrite<Self>, _>((self, aux), |(x, _)| &mut x.listeners);
+
+        if self.needle.animating() {
+            self.needle.advance(aux.frame_dt());
+            self.repaint();
+        }
+    }
+
+    fn draw(&mut self, display: &mut dyn gfx::GraphicsDisplay, aux: &mut ui::Aux<T>) {
+        ui::draw(
+            self,
+            |o, a| theme::paint(o, |o| &mut o.painter, a),
+            display,
+            aux,
+            None,
+        );
+    }
+}
+
+impl<T: 'static> ui::WidgetChildren<T> for Gauge<T> {}
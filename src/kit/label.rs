@@ -1,33 +1,289 @@
 use {
-    crate::{prelude::*, theme, ui},
+    crate::{kit, prelude::*, theme, ui},
     reclutch::display as gfx,
+    std::ops::Range,
 };
 
+/// A [link](Label::set_links) of a [`Label`](Label) was clicked. Contains the index of the link.
+pub struct LinkActivatedEvent(pub usize);
+
+/// Where text is cut off when it doesn't fit within a [`Label`](Label)'s maximum width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Ellipsis {
+    /// Text is wrapped onto multiple lines instead of being cut off.
+    None,
+    /// The beginning of the text is replaced with "…".
+    Start,
+    /// The middle of the text is replaced with "…".
+    Middle,
+    /// The end of the text is replaced with "…".
+    End,
+}
+
+impl Default for Ellipsis {
+    #[inline]
+    fn default() -> Self {
+        Ellipsis::None
+    }
+}
+
+/// Horizontal alignment of each line of text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TextAlign {
+    Left,
+    Center,
+    Right,
+    /// Wrapped lines are stretched to fill the width by widening the spaces between words; the last line of each paragraph is left-aligned.
+    Justify,
+}
+
+impl TextAlign {
+    /// Returns the horizontal offset of a line `width` wide within a space `container` wide.
+    ///
+    /// Justified lines aren't offset (they're stretched instead).
+    pub fn offset(self, container: f32, width: f32) -> f32 {
+        match self {
+            TextAlign::Left | TextAlign::Justify => 0.,
+            TextAlign::Center => ((container - width) / 2.).round(),
+            TextAlign::Right => container - width,
+        }
+    }
+}
+
+impl Default for TextAlign {
+    #[inline]
+    fn default() -> Self {
+        TextAlign::Left
+    }
+}
+
+/// Vertical alignment of text within a taller rectangle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum VerticalAlign {
+    Top,
+    Middle,
+    Bottom,
+}
+
+impl VerticalAlign {
+    /// Returns the vertical offset of text `height` tall within a space `container` tall.
+    pub fn offset(self, container: f32, height: f32) -> f32 {
+        match self {
+            VerticalAlign::Top => 0.,
+            VerticalAlign::Middle => ((container - height) / 2.).round(),
+            VerticalAlign::Bottom => container - height,
+        }
+    }
+}
+
+impl Default for VerticalAlign {
+    #[inline]
+    fn default() -> Self {
+        VerticalAlign::Top
+    }
+}
+
+/// Weight of the font used to draw text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FontWeight {
+    Regular,
+    Bold,
+}
+
+impl Default for FontWeight {
+    #[inline]
+    fn default() -> Self {
+        FontWeight::Regular
+    }
+}
+
+/// A run of text within a [`Label`](Label) with its own style; unset properties fall back to those of the label.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TextSpan {
+    pub text: String,
+    pub color: Option<gfx::Color>,
+    pub size: Option<f32>,
+    pub weight: Option<FontWeight>,
+}
+
+impl TextSpan {
+    pub fn new(text: impl ToString) -> Self {
+        TextSpan {
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn color(self, color: gfx::Color) -> Self {
+        TextSpan {
+            color: Some(color),
+            ..self
+        }
+    }
+
+    pub fn size(self, size: f32) -> Self {
+        TextSpan {
+            size: Some(size),
+            ..self
+        }
+    }
+
+    pub fn weight(self, weight: FontWeight) -> Self {
+        TextSpan {
+            weight: Some(weight),
+            ..self
+        }
+    }
+}
+
+/// Shortens `text` with an ellipsis in the position given by `mode`, so that `measure` of the result is at most `max_width`.
+///
+/// `measure` should return the width of a string as it would be rendered.
+/// Themes use this to implement [`Ellipsis`](Ellipsis) for [`Label`](Label).
+pub fn ellipsize(
+    text: &str,
+    mode: Ellipsis,
+    max_width: f32,
+    mut measure: impl FnMut(&str) -> f32,
+) -> String {
+    if mode == Ellipsis::None || measure(text) <= max_width {
+        return text.to_string();
+    }
+
+    let chars: Vec<char> = text.chars().collect();
+    let candidate = |n: usize| -> String {
+        match mode {
+            Ellipsis::Start => std::iter::once('…')
+                .chain(chars[chars.len() - n..].iter().copied())
+                .collect(),
+            Ellipsis::Middle => {
+                let tail = n / 2;
+                chars[..n - tail]
+                    .iter()
+                    .copied()
+                    .chain(std::iter::once('…'))
+                    .chain(chars[chars.len() - tail..].iter().copied())
+                    .collect()
+            }
+            _ => chars[..n]
+                .iter()
+                .copied()
+                .chain(std::iter::once('…'))
+                .collect(),
+        }
+    };
+
+    // binary search for the largest number of kept characters which still fits.
+    let (mut lo, mut hi) = (0, chars.len());
+    while lo < hi {
+        let mid = (lo + hi + 1) / 2;
+        if measure(&candidate(mid)) <= max_width {
+            lo = mid;
+        } else {
+            hi = mid - 1;
+        }
+    }
+
+    candidate(lo)
+}
+
 /// Displays text.
+///
+/// If a maximum width is set, text which doesn't fit is either wrapped or, with an [`Ellipsis`](Ellipsis) mode, cut off onto a single line.
+///
+/// Ranges of the text can be made into clickable [links](Label::set_links).
 pub struct Label<T: 'static> {
     text: gfx::DisplayText,
+    spans: Vec<TextSpan>,
     size: f32,
     max_width: Option<f32>,
+    ellipsis: Ellipsis,
+    truncated: bool,
+    // whether the tooltip was set by the label to show its truncated text, rather than by the user.
+    auto_tooltip: bool,
+    align: TextAlign,
+    vertical_align: VerticalAlign,
     color: gfx::Color,
+    // whether the size and color are still those of the theme, which are re-resolved when it changes.
+    theme_size: bool,
+    theme_color: bool,
+    links: Vec<Range<usize>>,
+    hovered_link: Option<usize>,
 
     painter: theme::Painter<Self>,
     common: ui::CommonRef,
+    listeners: ui::ListenerList<kit::ReadWrite<Self>>,
 }
 
 impl<T: 'static> Label<T> {
     pub fn new(parent: ui::CommonRef, aux: &mut ui::Aux<T>) -> Self {
+        let link_listener = aux
+            .listen::<kit::ReadWrite<Self>>()
+            .and_on(aux.id, |(obj, _), event: &ui::MouseMoveEvent| {
+                if obj.links.is_empty() {
+                    return;
+                }
+
+                let link = obj.link_at(*event.0.get());
+                if link != obj.hovered_link {
+                    obj.hovered_link = link;
+                    obj.set_cursor_icon(link.map(|_| ui::CursorIcon::Pointer));
+                }
+            })
+            .and_on(aux.id, |(obj, aux), event: &ui::MousePressEvent| {
+                if obj.links.is_empty() || kit::invisible_to_input(obj.visible()) {
+                    return;
+                }
+
+                let mut link = None;
+                event.0.with(|&(btn, pos)| {
+                    link = obj.link_at(pos).filter(|_| btn == ui::MouseButton::Left);
+                    link.is_some()
+                });
+                if let Some(link) = link {
+                    obj.emit(aux, LinkActivatedEvent(link));
+                }
+            });
+
+        let theme_listener = aux.listen::<kit::ReadWrite<Self>>().and_on(
+            aux.id,
+            |(obj, aux), _: &ui::ThemeChangedEvent| {
+                theme::refresh_painter(obj, |x| &mut x.painter, aux);
+                if obj.theme_size {
+                    obj.size = aux.theme.standards().label_size;
+                }
+                if obj.theme_color {
+                    obj.color = aux.color(theme::colors::FOREGROUND);
+                }
+                obj.repaint_and_resize();
+            },
+        );
+
         Label {
             text: gfx::DisplayText::Simple(Default::default()),
+            spans: Vec::new(),
             max_width: None,
+            ellipsis: Ellipsis::None,
+            truncated: false,
+            auto_tooltip: false,
+            align: TextAlign::Left,
+            vertical_align: VerticalAlign::Top,
             size: aux.theme.standards().label_size,
-            color: aux.theme.color(theme::colors::FOREGROUND),
-            painter: theme::get_painter(aux.theme.as_ref(), theme::painters::LABEL),
+            color: aux.color(theme::colors::FOREGROUND),
+            theme_size: true,
+            theme_color: true,
+            links: Vec::new(),
+            hovered_link: None,
+            painter: theme::get_painter(aux, theme::painters::LABEL),
             common: ui::CommonRef::new(parent),
+            listeners: ui::ListenerList::new(vec![link_listener, theme_listener]),
         }
     }
 
     pub fn set_text(&mut self, text: impl Into<gfx::DisplayText>) {
         self.text = text.into();
+        self.spans.clear();
+        self.links.clear();
         self.repaint_and_resize();
     }
 
@@ -36,8 +292,25 @@ impl<T: 'static> Label<T> {
         &self.text
     }
 
+    /// Replaces the text with differently styled spans, laid out one after the other.
+    ///
+    /// The [text](Label::text) becomes the concatenated text of the spans. Spans are always wrapped rather than cut off with an ellipsis.
+    pub fn set_spans(&mut self, spans: Vec<TextSpan>) {
+        self.text = spans.iter().map(|x| &x.text[..]).collect::<String>().into();
+        self.spans = spans;
+        self.links.clear();
+        self.repaint_and_resize();
+    }
+
+    /// Returns the styled spans, which are empty unless set through [`set_spans`](Label::set_spans).
+    #[inline]
+    pub fn spans(&self) -> &[TextSpan] {
+        &self.spans
+    }
+
     pub fn set_size(&mut self, size: f32) {
         self.size = size;
+        self.theme_size = false;
         self.repaint_and_resize();
     }
 
@@ -57,8 +330,71 @@ impl<T: 'static> Label<T> {
         self.max_width
     }
 
+    /// Changes how text that doesn't fit within the maximum width is cut off.
+    ///
+    /// This has no effect if there is no maximum width.
+    pub fn set_ellipsis(&mut self, ellipsis: Ellipsis) {
+        self.ellipsis = ellipsis;
+        self.repaint_and_resize();
+    }
+
+    #[inline]
+    pub fn ellipsis(&self) -> Ellipsis {
+        self.ellipsis
+    }
+
+    /// Returns `true` if the text is currently cut off with an ellipsis.
+    ///
+    /// While it is, the full text is shown as the label's tooltip, unless the label has been given a tooltip of its own.
+    #[inline]
+    pub fn truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Changes the alignment of each line, within the maximum width or, if there is none, the widest line.
+    pub fn set_align(&mut self, align: TextAlign) {
+        self.align = align;
+        self.repaint_and_resize();
+    }
+
+    #[inline]
+    pub fn align(&self) -> TextAlign {
+        self.align
+    }
+
+    /// Changes the alignment of the text within the label's rectangle, for when it is made taller than the text (e.g. by a layout).
+    pub fn set_vertical_align(&mut self, vertical_align: VerticalAlign) {
+        self.vertical_align = vertical_align;
+        self.repaint();
+    }
+
+    #[inline]
+    pub fn vertical_align(&self) -> VerticalAlign {
+        self.vertical_align
+    }
+
+    /// Makes each range (of byte indices into the text) a link, which emits [`LinkActivatedEvent`](LinkActivatedEvent) with its index when clicked.
+    ///
+    /// Links are cleared whenever the text changes. They aren't styled differently; use [spans](Label::set_spans) for that.
+    pub fn set_links(&mut self, links: Vec<Range<usize>>) {
+        self.links = links;
+        self.hovered_link = None;
+    }
+
+    #[inline]
+    pub fn links(&self) -> &[Range<usize>] {
+        &self.links
+    }
+
+    /// Returns the index of the link under `point` (in absolute coordinates), if any.
+    pub fn link_at(&mut self, point: gfx::Point) -> Option<usize> {
+        let index = theme::hit_test(self, point, |x| &mut x.painter)?;
+        self.links.iter().position(|x| x.contains(&index))
+    }
+
     pub fn set_color(&mut self, color: gfx::Color) {
         self.color = color;
+        self.theme_color = false;
         self.repaint();
     }
 
@@ -69,8 +405,42 @@ impl<T: 'static> Label<T> {
 
     fn repaint_and_resize(&mut self) {
         self.repaint();
+
+        self.truncated = match (self.ellipsis, self.max_width) {
+            (Ellipsis::None, _) | (_, None) => false,
+            _ if !self.spans.is_empty() => false,
+            (ellipsis, Some(max_width)) => {
+                // measure the natural width of the text by temporarily lifting the constraints.
+                self.max_width = None;
+                self.ellipsis = Ellipsis::None;
+                let natural = theme::size_hint(self, |x| &mut x.painter);
+                self.max_width = Some(max_width);
+                self.ellipsis = ellipsis;
+                natural.width > max_width
+            }
+        };
+
+        // expose the full text through a tooltip whenever it's cut off, unless the label already has a tooltip of its own.
+        match &self.text {
+            gfx::DisplayText::Simple(text)
+                if self.truncated && (self.auto_tooltip || self.tooltip().is_none()) =>
+            {
+                let text = text.clone();
+                self.set_tooltip(text);
+                self.auto_tooltip = true;
+            }
+            _ if self.auto_tooltip => {
+                self.set_tooltip(None);
+                self.auto_tooltip = false;
+            }
+            _ => {}
+        }
+
         let size = theme::size_hint(self, |x| &mut x.painter);
         ElementMixin::set_size(self, size);
+
+        let baseline = self.painter.baseline(self);
+        self.common.with(|x| x.set_baseline(baseline));
     }
 }
 
@@ -82,6 +452,11 @@ impl<T: 'static> ui::Element for Label<T> {
         &self.common
     }
 
+    #[inline]
+    fn update(&mut self, aux: &mut ui::Aux<T>) {
+        ui::dispatch_list::<kit::ReadWrite<Self>, _>((self, aux), |(x, _)| &mut x.listeners);
+    }
+
     #[inline]
     fn draw(&mut self, display: &mut dyn gfx::GraphicsDisplay, aux: &mut ui::Aux<T>) {
         ui::draw(
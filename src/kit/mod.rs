@@ -1,15 +1,29 @@
 use {
     crate::ui::{self, layout, ElementMixin},
     reclutch::display as gfx,
+    std::rc::Rc,
 };
 
 pub mod button;
+pub mod calendar;
 pub mod check_box;
 pub mod combo_box;
+pub mod editable_label;
+pub mod file_browser;
+pub mod gauge;
 pub mod label;
+pub mod range_slider;
+pub mod scroll_bar;
+pub mod status;
 pub mod text_box;
+pub mod text_input;
+pub mod tooltip;
 
-pub use {button::*, check_box::*, combo_box::*, label::*, text_box::*};
+pub use {
+    button::*, calendar::*, check_box::*, combo_box::*, editable_label::*, file_browser::*,
+    gauge::*, label::*, range_slider::*, scroll_bar::*, status::*, text_box::*, text_input::*,
+    tooltip::*,
+};
 
 /// The widget was pressed.
 #[repr(transparent)]
@@ -34,6 +48,25 @@ pub struct KeyReleaseEvent(pub ui::KeyInput);
 #[repr(transparent)]
 pub struct TextEvent(pub char);
 
+/// Vector glyph shown alongside the content of a widget (e.g. the icon of a [`Button`](Button)).
+///
+/// The glyph is built to fill whichever rectangle the theme lays it out in, and is then stroked by the theme,
+/// in the same way as the glyphs in [`theme::draw`](crate::theme::draw).
+#[derive(Clone)]
+pub struct Icon(Rc<dyn Fn(gfx::Rect) -> gfx::VectorPath>);
+
+impl Icon {
+    pub fn new(glyph: impl Fn(gfx::Rect) -> gfx::VectorPath + 'static) -> Self {
+        Icon(Rc::new(glyph))
+    }
+
+    /// Returns the glyph path filling `rect`.
+    #[inline]
+    pub fn path(&self, rect: gfx::Rect) -> gfx::VectorPath {
+        (self.0)(rect)
+    }
+}
+
 /// Standard set of listener read/writes: `&mut Widget` and `&mut Aux`.
 pub type ReadWrite<E> = (ui::Write<E>, ui::Write<ui::Aux<<E as ui::Element>::Aux>>);
 
@@ -45,6 +78,61 @@ pub enum InteractionEvent {
     EndHover(gfx::Point),
 }
 
+impl InteractionEvent {
+    /// Returns the visual state a widget enters upon this event, for driving a [`StateMachine`](ui::StateMachine).
+    pub fn visual_state(&self) -> ui::VisualState {
+        match self {
+            InteractionEvent::Press(_) => ui::VisualState::Pressed,
+            InteractionEvent::Release(_) | InteractionEvent::BeginHover(_) => {
+                ui::VisualState::Hover
+            }
+            InteractionEvent::EndHover(_) => ui::VisualState::Idle,
+        }
+    }
+}
+
+/// Interaction state of a widget, which painters can use to give hover, press and focus feedback.
+///
+/// Widgets which track this expose it through [`Interactive`](Interactive).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Interaction {
+    pub hovered: bool,
+    pub pressed: bool,
+    /// Whether the widget has keyboard focus.
+    pub focused: bool,
+}
+
+impl Interaction {
+    /// Updates the state from an interaction event, returning `true` if it changed.
+    ///
+    /// Releases outside the widget aren't reported, so leaving the widget also ends a press.
+    pub fn apply(&mut self, event: InteractionEvent) -> bool {
+        let old = *self;
+        match event {
+            InteractionEvent::Press(_) => self.pressed = true,
+            InteractionEvent::Release(_) => self.pressed = false,
+            InteractionEvent::BeginHover(_) => self.hovered = true,
+            InteractionEvent::EndHover(_) => {
+                self.hovered = false;
+                self.pressed = false;
+            }
+        }
+        *self != old
+    }
+
+    /// Updates the focus from a focus event, returning `true` if it changed.
+    pub fn apply_focus(&mut self, event: FocusEvent) -> bool {
+        let old = self.focused;
+        self.focused = event == FocusEvent::Gained;
+        self.focused != old
+    }
+}
+
+/// Widgets which track their [`Interaction`](Interaction); part of the painter contract, so that painters can vary their appearance with it.
+pub trait Interactive {
+    fn interaction(&self) -> Interaction;
+}
+
 pub struct InteractionState<
     T: 'static,
     W: ui::WidgetChildren<T>,
@@ -343,6 +431,14 @@ pub fn keyboard_forwarder<E: ui::Element<Aux = T>, T: 'static>(
     }
 }
 
+/// Returns `true` if `key` activates a focused widget (i.e. acts as a click); enter or space.
+pub fn is_activation_key(key: ui::KeyInput) -> bool {
+    match key {
+        ui::KeyInput::Return | ui::KeyInput::NumpadEnter | ui::KeyInput::Space => true,
+        _ => false,
+    }
+}
+
 pub fn invisible_to_input(v: ui::Visibility) -> bool {
     v == ui::Visibility::NoSelf || v == ui::Visibility::Invisible || v == ui::Visibility::None
 }
@@ -421,6 +517,12 @@ impl<'a, T: 'static, S: 'static> ButtonRef<'a, T, S> {
         self
     }
 
+    /// Sets the icon shown beside the button text.
+    pub fn icon(self, icon: impl Into<Option<Icon>>) -> Self {
+        self.1.get_mut(self.0).unwrap().set_icon(icon);
+        self
+    }
+
     /// Handles the button press event.
     pub fn press(
         self,
@@ -0,0 +1,228 @@
+use {
+    crate::{kit, prelude::*, theme, ui},
+    reclutch::display as gfx,
+};
+
+/// The selected sub-range of a [`RangeSlider`](RangeSlider) changed. Contains the new lower and upper values.
+pub struct RangeChangedEvent(pub f32, pub f32);
+
+/// One of the two thumbs of a [`RangeSlider`](RangeSlider).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Thumb {
+    Low,
+    High,
+}
+
+/// Horizontal slider with two thumbs, selecting a sub-range of values.
+///
+/// The thumbs can't pass each other; a dragged thumb stops at the other thumb, keeping at least the [minimum gap](RangeSlider::set_min_gap) between them.
+pub struct RangeSlider<T: 'static> {
+    range: (f32, f32),
+    low: f32,
+    high: f32,
+    step: Option<f32>,
+    min_gap: f32,
+    dragging: Option<Thumb>,
+
+    painter: theme::Painter<Self>,
+    common: ui::CommonRef,
+    listeners: ui::ListenerList<kit::ReadWrite<Self>>,
+    components: ui::ComponentList<Self>,
+}
+
+impl<T: 'static> RangeSlider<T> {
+    pub fn new(parent: ui::CommonRef, aux: &mut ui::Aux<T>) -> Self {
+        let mouse_listener = aux
+            .listen::<kit::ReadWrite<Self>>()
+            .and_on(aux.id, |(obj, aux), event: &ui::MouseMoveEvent| {
+                if let Some(thumb) = obj.dragging {
+                    let value = obj.value_at(*event.0.get());
+                    obj.move_thumb(thumb, value, aux);
+                }
+            })
+            .and_on(aux.id, |(obj, _), event: &ui::MouseReleaseEvent| {
+                if event.0.get().0 == ui::MouseButton::Left {
+                    obj.dragging = None;
+                }
+            });
+
+        let theme_listener = aux.listen::<kit::ReadWrite<Self>>().and_on(
+            aux.id,
+            |(obj, aux), _: &ui::ThemeChangedEvent| {
+                theme::refresh_painter(obj, |x| &mut x.painter, aux);
+                theme::apply_size_hint(obj, |x| &mut x.painter);
+            },
+        );
+
+        let mut slider = RangeSlider {
+            range: (0., 1.),
+            low: 0.,
+            high: 1.,
+            step: None,
+            min_gap: 0.,
+            dragging: None,
+
+            painter: theme::get_painter(aux, theme::painters::RANGE_SLIDER),
+            common: ui::CommonRef::new(parent),
+            listeners: ui::ListenerList::new(vec![mouse_listener, theme_listener]),
+            components: ui::ComponentList::new().and_push(kit::InteractionState::new(
+                aux,
+                |obj: &mut Self, aux, event| {
+                    if let kit::InteractionEvent::Press(pos) = event {
+                        let value = obj.value_at(pos);
+                        let thumb = obj.nearest_thumb(value);
+                        obj.dragging = Some(thumb);
+                        obj.move_thumb(thumb, value, aux);
+                    }
+                    kit::interaction_forwarder(None)(obj, aux, event);
+                },
+                None,
+                None,
+            )),
+        };
+
+        theme::apply_size_hint(&mut slider, |x| &mut x.painter);
+
+        slider
+    }
+
+    /// Changes the selected sub-range, clamped to the range and ordered such that `low <= high`.
+    pub fn set_values(&mut self, low: f32, high: f32) {
+        let (low, high) = (low.min(high), low.max(high));
+        self.low = self.snap(low);
+        self.high = self.snap(high).max(self.low);
+        self.repaint();
+    }
+
+    #[inline]
+    pub fn values(&self) -> (f32, f32) {
+        (self.low, self.high)
+    }
+
+    /// Changes the lower and upper bounds of the values.
+    pub fn set_range(&mut self, min: f32, max: f32) {
+        self.range = (min, max);
+        self.set_values(self.low, self.high);
+    }
+
+    #[inline]
+    pub fn range(&self) -> (f32, f32) {
+        self.range
+    }
+
+    /// Changes the increment which values snap to, or allows any value if `None`.
+    pub fn set_step(&mut self, step: impl Into<Option<f32>>) {
+        self.step = step.into().filter(|&x| x > 0.);
+        self.set_values(self.low, self.high);
+    }
+
+    #[inline]
+    pub fn step(&self) -> Option<f32> {
+        self.step
+    }
+
+    /// Changes the smallest distance allowed between the two thumbs.
+    pub fn set_min_gap(&mut self, min_gap: f32) {
+        self.min_gap = min_gap.max(0.);
+    }
+
+    #[inline]
+    pub fn min_gap(&self) -> f32 {
+        self.min_gap
+    }
+
+    /// Returns the thumb currently being dragged, if any.
+    #[inline]
+    pub fn dragging(&self) -> Option<Thumb> {
+        self.dragging
+    }
+
+    /// Returns where `value` lies within the range, from `0.0` to `1.0`.
+    pub fn fraction(&self, value: f32) -> f32 {
+        let span = self.range.1 - self.range.0;
+        if span <= 0. {
+            0.
+        } else {
+            ((value - self.range.0) / span).max(0.).min(1.)
+        }
+    }
+
+    /// Returns the horizontal extent of the track that the center of a thumb can move along.
+    pub fn track(&self) -> (f32, f32) {
+        let bounds = self.bounds();
+        let inset = self
+            .painter
+            .metric(self, theme::metrics::THUMB_SIZE)
+            .unwrap_or(0.)
+            / 2.;
+        (bounds.min_x() + inset, bounds.max_x() - inset)
+    }
+
+    fn value_at(&self, point: gfx::Point) -> f32 {
+        let (left, right) = self.track();
+        let fraction = if right > left {
+            ((point.x - left) / (right - left)).max(0.).min(1.)
+        } else {
+            0.
+        };
+        self.snap(self.range.0 + fraction * (self.range.1 - self.range.0))
+    }
+
+    fn snap(&self, value: f32) -> f32 {
+        let value = match self.step {
+            Some(step) => self.range.0 + ((value - self.range.0) / step).round() * step,
+            None => value,
+        };
+        value.max(self.range.0).min(self.range.1)
+    }
+
+    fn nearest_thumb(&self, value: f32) -> Thumb {
+        if value < self.low {
+            Thumb::Low
+        } else if value > self.high {
+            Thumb::High
+        } else if value - self.low <= self.high - value {
+            Thumb::Low
+        } else {
+            Thumb::High
+        }
+    }
+
+    fn move_thumb(&mut self, thumb: Thumb, value: f32, aux: &mut ui::Aux<T>) {
+        let old = (self.low, self.high);
+        match thumb {
+            Thumb::Low => self.low = value.min(self.high - self.min_gap).max(self.range.0),
+            Thumb::High => self.high = value.max(self.low + self.min_gap).min(self.range.1),
+        }
+        if old != (self.low, self.high) {
+            self.repaint();
+            self.emit(aux, RangeChangedEvent(self.low, self.high));
+        }
+    }
+}
+
+impl<T: 'static> ui::Element for RangeSlider<T> {
+    type Aux = T;
+
+    #[inline]
+    fn common(&self) -> &ui::CommonRef {
+        &self.common
+    }
+
+    fn update(&mut self, aux: &mut ui::Aux<T>) {
+        ui::dispatch_components(self, aux, |x| &mut x.components).unwrap();
+        ui::dispatch_list::<kit::ReadWrite<Self>, _>((self, aux), |(x, _)| &mut x.listeners);
+    }
+
+    fn draw(&mut self, display: &mut dyn gfx::GraphicsDisplay, aux: &mut ui::Aux<T>) {
+        ui::draw(
+            self,
+            |o, a| theme::paint(o, |o| &mut o.painter, a),
+            display,
+            aux,
+            None,
+        );
+    }
+}
+
+impl<T: 'static> ui::WidgetChildren<T> for RangeSlider<T> {}
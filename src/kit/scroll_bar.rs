@@ -0,0 +1,286 @@
+use {
+    crate::{kit, prelude::*, theme, ui},
+    reclutch::display as gfx,
+};
+
+/// The position of a [`ScrollBar`](ScrollBar) changed. Contains the new position.
+pub struct ScrolledEvent(pub f32);
+
+/// Axis along which a [`ScrollBar`](ScrollBar) scrolls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    Horizontal,
+    Vertical,
+}
+
+/// Bar with a thumb sized and placed to show which part of some content is in view.
+///
+/// The content and the view onto it are described by their [lengths](ScrollBar::set_extent) along the bar, and the position is the offset of the view into the content.
+/// The thumb can be dragged, pressing the track on either side of the thumb scrolls by a page, and the mouse wheel scrolls by [lines](ui::InteractionConfig::scroll_line).
+pub struct ScrollBar<T: 'static> {
+    orientation: Orientation,
+    content: f32,
+    viewport: f32,
+    position: f32,
+    interaction: kit::Interaction,
+    thumb_hovered: bool,
+    grab: Option<f32>,
+
+    painter: theme::Painter<Self>,
+    common: ui::CommonRef,
+    listeners: ui::ListenerList<kit::ReadWrite<Self>>,
+    components: ui::ComponentList<Self>,
+}
+
+impl<T: 'static> ScrollBar<T> {
+    pub fn new(parent: ui::CommonRef, aux: &mut ui::Aux<T>) -> Self {
+        let mouse_listener = aux
+            .listen::<kit::ReadWrite<Self>>()
+            .and_on(aux.id, |(obj, aux), event: &ui::MouseMoveEvent| {
+                let point = *event.0.get();
+                if let Some(grab) = obj.grab {
+                    let position = obj.position_at(point, grab);
+                    obj.scroll_to(position, aux);
+                }
+
+                let thumb_hovered = obj.thumb().contains(point);
+                if thumb_hovered != obj.thumb_hovered {
+                    obj.thumb_hovered = thumb_hovered;
+                    obj.repaint();
+                }
+            })
+            .and_on(aux.id, |(obj, _), event: &ui::MouseReleaseEvent| {
+                if event.0.get().0 == ui::MouseButton::Left && obj.grab.is_some() {
+                    obj.grab = None;
+                    obj.repaint();
+                }
+            })
+            .and_on(aux.id, |(obj, aux), event: &ui::MouseScrollEvent| {
+                let bounds = obj.bounds();
+                if let Some(&(_, delta)) = event.0.with(|&(pos, _)| bounds.contains(pos)) {
+                    let delta = delta.pixels(aux.interaction.scroll_line);
+                    // mouse wheels only scroll vertically, so they also scroll horizontal bars.
+                    let delta = match obj.orientation {
+                        Orientation::Horizontal if delta.x != 0. => delta.x,
+                        _ => delta.y,
+                    };
+                    obj.scroll_to(obj.position - delta, aux);
+                }
+            });
+
+        let theme_listener = aux.listen::<kit::ReadWrite<Self>>().and_on(
+            aux.id,
+            |(obj, aux), _: &ui::ThemeChangedEvent| {
+                theme::refresh_painter(obj, |x| &mut x.painter, aux);
+                theme::apply_size_hint(obj, |x| &mut x.painter);
+            },
+        );
+
+        let mut bar = ScrollBar {
+            orientation: Orientation::Vertical,
+            content: 0.,
+            viewport: 0.,
+            position: 0.,
+            interaction: Default::default(),
+            thumb_hovered: false,
+            grab: None,
+
+            painter: theme::get_painter(aux, theme::painters::SCROLL_BAR),
+            common: ui::CommonRef::new(parent),
+            listeners: ui::ListenerList::new(vec![mouse_listener, theme_listener]),
+            components: ui::ComponentList::new().and_push(kit::InteractionState::new(
+                aux,
+                |obj: &mut Self, aux, event| {
+                    if obj.interaction.apply(event) {
+                        obj.repaint();
+                    }
+                    if let kit::InteractionEvent::Press(pos) = event {
+                        obj.press(pos, aux);
+                    }
+                    kit::interaction_forwarder(None)(obj, aux, event);
+                },
+                None,
+                None,
+            )),
+        };
+
+        theme::apply_size_hint(&mut bar, |x| &mut x.painter);
+
+        bar
+    }
+
+    /// Changes the axis of the bar, resizing it to the size hint of the new orientation.
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+        theme::apply_size_hint(self, |x| &mut x.painter);
+        self.repaint();
+    }
+
+    #[inline]
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Changes the lengths of the content and of the view onto it, keeping the position within the new bounds.
+    pub fn set_extent(&mut self, content: f32, viewport: f32) {
+        self.content = content.max(0.);
+        self.viewport = viewport.max(0.);
+        self.set_position(self.position);
+    }
+
+    /// Returns the lengths of the content and of the view onto it.
+    #[inline]
+    pub fn extent(&self) -> (f32, f32) {
+        (self.content, self.viewport)
+    }
+
+    /// Changes the position, clamped between `0.0` and the [maximum position](ScrollBar::max_position).
+    pub fn set_position(&mut self, position: f32) {
+        self.position = position.max(0.).min(self.max_position());
+        self.repaint();
+    }
+
+    #[inline]
+    pub fn position(&self) -> f32 {
+        self.position
+    }
+
+    /// Returns the furthest the view can be scrolled into the content.
+    #[inline]
+    pub fn max_position(&self) -> f32 {
+        (self.content - self.viewport).max(0.)
+    }
+
+    /// Returns `true` if the content doesn't fit in the view, and hence can be scrolled.
+    #[inline]
+    pub fn scrollable(&self) -> bool {
+        self.max_position() > 0.
+    }
+
+    /// Returns `true` if the cursor is over the thumb.
+    #[inline]
+    pub fn thumb_hovered(&self) -> bool {
+        self.thumb_hovered
+    }
+
+    /// Returns `true` if the thumb is being dragged.
+    #[inline]
+    pub fn dragging(&self) -> bool {
+        self.grab.is_some()
+    }
+
+    /// Returns the bounds of the thumb, which is at least as long as the [thumb size](theme::metrics::THUMB_SIZE) of the painter.
+    pub fn thumb(&self) -> gfx::Rect {
+        let bounds = self.bounds();
+        let length = self.along(bounds.size.to_vector());
+        let (thumb, offset) = self.thumb_span(length);
+        match self.orientation {
+            Orientation::Horizontal => gfx::Rect::new(
+                gfx::Point::new(bounds.min_x() + offset, bounds.min_y()),
+                gfx::Size::new(thumb, bounds.size.height),
+            ),
+            Orientation::Vertical => gfx::Rect::new(
+                gfx::Point::new(bounds.min_x(), bounds.min_y() + offset),
+                gfx::Size::new(bounds.size.width, thumb),
+            ),
+        }
+    }
+
+    /// Returns the length of the thumb and its offset from the start of the track, for a track of `length`.
+    fn thumb_span(&self, length: f32) -> (f32, f32) {
+        let min = self
+            .painter
+            .metric(self, theme::metrics::THUMB_SIZE)
+            .unwrap_or(0.);
+        let thumb = if self.scrollable() {
+            (length * self.viewport / self.content).max(min).min(length)
+        } else {
+            length
+        };
+        let offset = if self.scrollable() {
+            (length - thumb) * self.position / self.max_position()
+        } else {
+            0.
+        };
+        (thumb, offset)
+    }
+
+    #[inline]
+    fn along(&self, v: gfx::Vector) -> f32 {
+        match self.orientation {
+            Orientation::Horizontal => v.x,
+            Orientation::Vertical => v.y,
+        }
+    }
+
+    /// Returns the position which places the thumb `grab` before `point`.
+    fn position_at(&self, point: gfx::Point, grab: f32) -> f32 {
+        let bounds = self.bounds();
+        let length = self.along(bounds.size.to_vector());
+        let (thumb, _) = self.thumb_span(length);
+        let offset = self.along(point - bounds.origin) - grab;
+        if length > thumb {
+            offset / (length - thumb) * self.max_position()
+        } else {
+            0.
+        }
+    }
+
+    fn press(&mut self, point: gfx::Point, aux: &mut ui::Aux<T>) {
+        if !self.scrollable() {
+            return;
+        }
+
+        let thumb = self.thumb();
+        let cursor = self.along(point.to_vector());
+        if thumb.contains(point) {
+            self.grab = Some(cursor - self.along(thumb.origin.to_vector()));
+            self.repaint();
+        } else if cursor < self.along(thumb.origin.to_vector()) {
+            self.scroll_to(self.position - self.viewport, aux);
+        } else {
+            self.scroll_to(self.position + self.viewport, aux);
+        }
+    }
+
+    fn scroll_to(&mut self, position: f32, aux: &mut ui::Aux<T>) {
+        let old = self.position;
+        self.set_position(position);
+        if self.position != old {
+            self.emit(aux, ScrolledEvent(self.position));
+        }
+    }
+}
+
+impl<T: 'static> kit::Interactive for ScrollBar<T> {
+    #[inline]
+    fn interaction(&self) -> kit::Interaction {
+        self.interaction
+    }
+}
+
+impl<T: 'static> ui::Element for ScrollBar<T> {
+    type Aux = T;
+
+    #[inline]
+    fn common(&self) -> &ui::CommonRef {
+        &self.common
+    }
+
+    fn update(&mut self, aux: &mut ui::Aux<T>) {
+        ui::dispatch_components(self, aux, |x| &mut x.components).unwrap();
+        ui::dispatch_list::<kit::ReadWrite<Self>, _>((self, aux), |(x, _)| &mut x.listeners);
+    }
+
+    fn draw(&mut self, display: &mut dyn gfx::GraphicsDisplay, aux: &mut ui::Aux<T>) {
+        ui::draw(
+            self,
+            |o, a| theme::paint(o, |o| &mut o.painter, a),
+            display,
+            aux,
+            None,
+        );
+    }
+}
+
+impl<T: 'static> ui::WidgetChildren<T> for ScrollBar<T> {}
@@ -0,0 +1,88 @@
+use {
+    crate::{kit, prelude::*, theme, ui},
+    reclutch::display as gfx,
+};
+
+/// A small panel displaying a status message and optional progress bar.
+///
+/// The application root uses this to display the current [`Status`](crate::app::Status), above all other widgets.
+pub struct StatusIndicator<T: 'static> {
+    text: String,
+    progress: Option<f32>,
+
+    painter: theme::Painter<Self>,
+    common: ui::CommonRef,
+    listeners: ui::ListenerList<kit::ReadWrite<Self>>,
+}
+
+impl<T: 'static> StatusIndicator<T> {
+    pub fn new(parent: ui::CommonRef, aux: &mut ui::Aux<T>) -> Self {
+        let theme_listener = aux.listen::<kit::ReadWrite<Self>>().and_on(
+            aux.id,
+            |(obj, aux), _: &ui::ThemeChangedEvent| {
+                theme::refresh_painter(obj, |x| &mut x.painter, aux);
+                obj.resize();
+            },
+        );
+
+        StatusIndicator {
+            text: String::new(),
+            progress: None,
+
+            painter: theme::get_painter(aux, theme::painters::STATUS_INDICATOR),
+            common: ui::CommonRef::new(parent),
+            listeners: ui::ListenerList::new(vec![theme_listener]),
+        }
+    }
+
+    /// Changes the displayed message and progress (from `0.0` to `1.0`).
+    ///
+    /// If `progress` is `None`, no progress bar is shown.
+    pub fn set_status(&mut self, text: impl Into<String>, progress: impl Into<Option<f32>>) {
+        self.text = text.into();
+        self.progress = progress.into().map(|x| x.max(0.).min(1.));
+        self.resize();
+    }
+
+    #[inline]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    #[inline]
+    pub fn progress(&self) -> Option<f32> {
+        self.progress
+    }
+
+    fn resize(&mut self) {
+        let size = theme::size_hint(self, |x| &mut x.painter);
+        self.set_size(size);
+        self.repaint();
+    }
+}
+
+impl<T: 'static> ui::Element for StatusIndicator<T> {
+    type Aux = T;
+
+    #[inline]
+    fn common(&self) -> &ui::CommonRef {
+        &self.common
+    }
+
+    #[inline]
+    fn update(&mut self, aux: &mut ui::Aux<T>) {
+        ui::dispatch_list::<kit::ReadWrite<Self>, _>((self, aux), |(x, _)| &mut x.listeners);
+    }
+
+    fn draw(&mut self, display: &mut dyn gfx::GraphicsDisplay, aux: &mut ui::Aux<T>) {
+        ui::draw(
+            self,
+            |o, a| theme::paint(o, |o| &mut o.painter, a),
+            display,
+            aux,
+            gfx::ZOrder(std::i32::MAX - 1),
+        );
+    }
+}
+
+impl<T: 'static> ui::WidgetChildren<T> for StatusIndicator<T> {}
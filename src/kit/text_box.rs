@@ -3,6 +3,59 @@ use {
     reclutch::display as gfx,
 };
 
+/// Maximum number of undo steps kept by a [`TextBox`](TextBox).
+const HISTORY_LIMIT: usize = 100;
+
+/// Text and cursor position, as recorded in the undo history.
+struct EditState {
+    text: String,
+    cursor: usize,
+}
+
+/// The text of a [`TextBox`](TextBox) was changed by the user.
+pub struct TextChangedEvent {
+    /// The text prior to the edit.
+    pub old: String,
+    /// The text after the edit.
+    pub new: String,
+}
+
+/// Direction in which text is written in a [`TextBox`](TextBox).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TextDirection {
+    LeftToRight,
+    RightToLeft,
+}
+
+impl Default for TextDirection {
+    #[inline]
+    fn default() -> Self {
+        TextDirection::LeftToRight
+    }
+}
+
+/// Result of validating the contents of a [`TextBox`](TextBox).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ValidationState {
+    Valid,
+    /// The contents are invalid, with a message describing why.
+    Invalid(String),
+}
+
+impl ValidationState {
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        *self == ValidationState::Valid
+    }
+}
+
+impl Default for ValidationState {
+    #[inline]
+    fn default() -> Self {
+        ValidationState::Valid
+    }
+}
+
 /// Widget which can accept various forms of string-based user input.
 ///
 /// This widget shouldn't be used on its own. It is deliberately rendered as only the text and cursor.
@@ -15,8 +68,22 @@ pub struct TextBox<T: 'static> {
     placeholder: String,
     wrap: bool,
     censor: Option<Box<dyn FnMut(&str) -> String>>,
+    input_filter: Option<Box<dyn FnMut(char, &str) -> bool>>,
+    validator: Option<Box<dyn FnMut(&str) -> ValidationState>>,
+    validation: ValidationState,
+    max_length: Option<usize>,
     multi_line: bool,
+    composition: String,
+    align: kit::TextAlign,
+    direction: TextDirection,
     cursor: usize,
+    anchor: Option<usize>,
+    dragging: Option<gfx::Point>,
+    undo_stack: Vec<EditState>,
+    redo_stack: Vec<EditState>,
+    coalesce: bool,
+    caret_blink: theme::Blink,
+    blink_cursor: usize,
 
     painter: theme::Painter<Self>,
     common: ui::CommonRef,
@@ -27,10 +94,16 @@ pub struct TextBox<T: 'static> {
 impl<T: 'static> TextBox<T> {
     pub fn new(parent: ui::CommonRef, aux: &mut ui::Aux<T>) -> Self {
         let common = ui::CommonRef::new(parent);
+        common.with(|x| x.set_cursor(ui::CursorIcon::Text));
 
         let focus_listener = kit::focus_handler(
             aux,
-            kit::focus_forwarder(),
+            |obj: &mut Self, aux, event| {
+                if event == kit::FocusEvent::Gained {
+                    obj.update_ime_area(aux);
+                }
+                kit::focus_forwarder()(obj, aux, event);
+            },
             kit::FocusConfig {
                 mouse_trigger: Default::default(),
                 interaction_handler: common.with(|x| x.id()),
@@ -39,47 +112,221 @@ impl<T: 'static> TextBox<T> {
 
         let keyboard_listener = kit::keyboard_handler(aux, |obj: &mut Self, aux, event| {
             let mut text = obj.text().to_string();
+            let before = EditState {
+                text: text.clone(),
+                cursor: obj.cursor,
+            };
+            let mut insertion = false;
+            let mut restored = false;
+            let mods = aux.modifiers();
             match event {
-                kit::KeyboardEvent::Text(c) => {
-                    text.insert(obj.cursor, c);
-                    obj.cursor += 1;
+                // with ctrl held, characters are shortcuts rather than input.
+                // ctrl and alt together is AltGr on Windows though, which types characters on many layouts.
+                kit::KeyboardEvent::Text(_) if mods.ctrl && !mods.alt => {}
+                kit::KeyboardEvent::Text(c) if obj.accepts(c, &text) => {
+                    insertion = !obj.delete_selection(&mut text);
+                    if obj.has_room(&text) {
+                        text.insert(obj.cursor, c);
+                        obj.cursor += c.len_utf8();
+                    }
                 }
+                kit::KeyboardEvent::Text(_) => {}
                 kit::KeyboardEvent::KeyPress(key) => match key {
-                    ui::KeyInput::Back if obj.cursor > 0 => {
-                        obj.cursor -= 1;
-                        text.remove(obj.cursor);
+                    ui::KeyInput::Copy => obj.copy(aux),
+                    ui::KeyInput::C if mods.ctrl => obj.copy(aux),
+                    ui::KeyInput::Cut => obj.cut(&mut text, aux),
+                    ui::KeyInput::X if mods.ctrl => obj.cut(&mut text, aux),
+                    ui::KeyInput::Paste => obj.paste(&mut text, aux),
+                    ui::KeyInput::V if mods.ctrl => obj.paste(&mut text, aux),
+                    ui::KeyInput::A if mods.ctrl => obj.select(0, text.len()),
+                    ui::KeyInput::Z if mods.ctrl && !mods.shift => {
+                        obj.undo();
+                        text = obj.text.clone();
+                        restored = true;
                     }
-                    ui::KeyInput::Left if obj.cursor > 0 => {
-                        obj.cursor -= 1;
+                    ui::KeyInput::Z | ui::KeyInput::Y if mods.ctrl => {
+                        obj.redo();
+                        text = obj.text.clone();
+                        restored = true;
                     }
-                    ui::KeyInput::Right if obj.cursor < text.len() => {
-                        obj.cursor += 1;
+                    ui::KeyInput::Back => {
+                        if !obj.delete_selection(&mut text) && obj.cursor > 0 {
+                            obj.cursor = prev_boundary(&text, obj.cursor);
+                            text.remove(obj.cursor);
+                        }
                     }
+                    ui::KeyInput::Delete => {
+                        if !obj.delete_selection(&mut text) && obj.cursor < text.len() {
+                            text.remove(obj.cursor);
+                        }
+                    }
+                    ui::KeyInput::Left | ui::KeyInput::Right => {
+                        // in right-to-left text, the left arrow moves towards the end.
+                        let forward = (key == ui::KeyInput::Right)
+                            == (obj.direction == TextDirection::LeftToRight);
+                        match obj.selection() {
+                            Some((start, _)) if !mods.shift && !forward => {
+                                obj.move_cursor(start, false)
+                            }
+                            Some((_, end)) if !mods.shift && forward => obj.move_cursor(end, false),
+                            _ if forward => {
+                                obj.move_cursor(next_boundary(&text, obj.cursor), mods.shift)
+                            }
+                            _ => obj.move_cursor(prev_boundary(&text, obj.cursor), mods.shift),
+                        }
+                    }
+                    ui::KeyInput::Return | ui::KeyInput::NumpadEnter if obj.multi_line => {
+                        obj.delete_selection(&mut text);
+                        if obj.has_room(&text) {
+                            text.insert(obj.cursor, '\n');
+                            obj.cursor += 1;
+                        }
+                    }
+                    ui::KeyInput::Up => {
+                        let start = line_start(&text, obj.cursor);
+                        if start == 0 {
+                            obj.move_cursor(0, mods.shift);
+                        } else {
+                            let column = text[start..obj.cursor].chars().count();
+                            let prev_start = line_start(&text, start - 1);
+                            obj.move_cursor(column_index(&text, prev_start, column), mods.shift);
+                        }
+                    }
+                    ui::KeyInput::Down => {
+                        let end = line_end(&text, obj.cursor);
+                        if end == text.len() {
+                            obj.move_cursor(end, mods.shift);
+                        } else {
+                            let column = text[line_start(&text, obj.cursor)..obj.cursor]
+                                .chars()
+                                .count();
+                            obj.move_cursor(column_index(&text, end + 1, column), mods.shift);
+                        }
+                    }
+                    ui::KeyInput::Home => {
+                        obj.move_cursor(line_start(&text, obj.cursor), mods.shift)
+                    }
+                    ui::KeyInput::End => obj.move_cursor(line_end(&text, obj.cursor), mods.shift),
                     _ => {}
                 },
-                _ => {}
+                kit::KeyboardEvent::KeyRelease(_) => {}
             }
-            obj.set_text(text);
+            obj.finish_edit(before, text, insertion, restored, aux);
 
             kit::keyboard_forwarder()(obj, aux, event);
         });
 
+        let ime_listener = aux
+            .listen::<kit::ReadWrite<Self>>()
+            .and_on(aux.id, |(obj, aux), event: &ui::ImeCompositionEvent| {
+                if let Some(composition) = event.0.with(|_| aux.has_focus(obj.common())) {
+                    obj.composition = composition.clone();
+                    obj.update_label();
+                    obj.update_ime_area(aux);
+                }
+            })
+            .and_on(aux.id, |(obj, aux), event: &ui::ImeCommitEvent| {
+                if let Some(committed) = event.0.with(|_| aux.has_focus(obj.common())) {
+                    let mut text = obj.text.clone();
+                    let before = EditState {
+                        text: text.clone(),
+                        cursor: obj.cursor,
+                    };
+                    obj.composition.clear();
+                    obj.delete_selection(&mut text);
+                    obj.insert_str(&mut text, committed);
+                    obj.finish_edit(before, text, false, false, aux);
+                }
+            });
+
+        let mouse_listener = aux
+            .listen::<kit::ReadWrite<Self>>()
+            .and_on(aux.id, |(obj, aux), event: &ui::MouseMoveEvent| {
+                let pos = *event.0.get();
+                let origin = match obj.dragging {
+                    Some(origin) => origin,
+                    None => return,
+                };
+
+                // small movements during a click shouldn't select anything.
+                if (pos - origin).length() < aux.interaction.drag_threshold
+                    && obj.selection().is_none()
+                {
+                    return;
+                }
+
+                if let Some(index) = obj.index_at(pos) {
+                    obj.cursor = index;
+                    obj.repaint();
+                }
+            })
+            .and_on(aux.id, |(obj, _), event: &ui::MouseReleaseEvent| {
+                if event.0.get().0 == ui::MouseButton::Left {
+                    obj.dragging = None;
+                }
+            })
+            .and_on(aux.id, |(obj, _), event: &ui::MouseDoubleClickEvent| {
+                let bounds = obj.bounds();
+                if let Some(&(_, pos)) = event
+                    .0
+                    .with(|&(btn, pos)| btn == ui::MouseButton::Left && bounds.contains(pos))
+                {
+                    // dragging on from a double-click would otherwise undo the word selection.
+                    obj.dragging = None;
+                    obj.select_word_at(pos);
+                }
+            });
+
+        // the label resolves its own themed properties, so only the layout around it needs updating.
+        let theme_listener = aux.listen::<kit::ReadWrite<Self>>().and_on(
+            aux.id,
+            |(obj, aux), _: &ui::ThemeChangedEvent| {
+                theme::refresh_painter(obj, |x| &mut x.painter, aux);
+                obj.update_label();
+            },
+        );
+
         TextBox {
             text_label: kit::Label::new(common.clone(), aux),
             text: Default::default(),
             placeholder: Default::default(),
             wrap: false,
             censor: None,
+            input_filter: None,
+            validator: None,
+            validation: ValidationState::Valid,
+            max_length: None,
             multi_line: false,
+            composition: String::new(),
+            align: kit::TextAlign::Left,
+            direction: TextDirection::LeftToRight,
             cursor: 0,
-
-            painter: theme::get_painter(aux.theme.as_ref(), theme::painters::TEXT_BOX),
+            anchor: None,
+            dragging: None,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            coalesce: false,
+            caret_blink: Default::default(),
+            blink_cursor: 0,
+
+            painter: theme::get_painter(aux, theme::painters::TEXT_BOX),
             common,
-            listeners: ui::ListenerList::new(vec![focus_listener, keyboard_listener]),
+            listeners: ui::ListenerList::new(vec![
+                focus_listener,
+                keyboard_listener,
+                mouse_listener,
+                ime_listener,
+                theme_listener,
+            ]),
             components: ui::ComponentList::new().and_push(
                 kit::InteractionState::<T, Self, _>::new(
                     aux,
-                    kit::interaction_forwarder(None),
+                    |obj: &mut Self, aux, event| {
+                        if let kit::InteractionEvent::Press(pos) = event {
+                            obj.begin_drag(pos, aux.modifiers().shift);
+                        }
+                        kit::interaction_forwarder(None)(obj, aux, event);
+                    },
                     None,
                     None,
                 ),
@@ -89,7 +336,9 @@ impl<T: 'static> TextBox<T> {
 
     pub fn set_text(&mut self, text: impl ToString) {
         self.text = text.to_string();
-        self.cursor = self.cursor.min(self.text.len());
+        self.cursor = floor_boundary(&self.text, self.cursor);
+        self.anchor = self.anchor.map(|x| floor_boundary(&self.text, x));
+        self.validate();
         self.update_label();
     }
 
@@ -108,6 +357,18 @@ impl<T: 'static> TextBox<T> {
         &self.placeholder
     }
 
+    /// Changes the maximum number of characters which can be entered, or removes the limit if `None`.
+    ///
+    /// The limit only applies to user input; existing text and text set through [`set_text`](TextBox::set_text) isn't truncated.
+    pub fn set_max_length(&mut self, max_length: impl Into<Option<usize>>) {
+        self.max_length = max_length.into();
+    }
+
+    #[inline]
+    pub fn max_length(&self) -> Option<usize> {
+        self.max_length
+    }
+
     /// Changes whether text which overflows the width can be rendered on a separate line.
     ///
     /// Note: This is not the same as physical newlines; it is merely a modification in rendering.
@@ -121,6 +382,29 @@ impl<T: 'static> TextBox<T> {
         self.wrap
     }
 
+    /// Changes the horizontal alignment of the text within the text box.
+    pub fn set_align(&mut self, align: kit::TextAlign) {
+        self.align = align;
+        self.update_label();
+    }
+
+    #[inline]
+    pub fn align(&self) -> kit::TextAlign {
+        self.align
+    }
+
+    /// Changes the direction in which text is written, which decides how the left and right arrow keys move the cursor.
+    ///
+    /// Note: Text is still laid out in logical order; bidirectional reordering isn't performed.
+    pub fn set_direction(&mut self, direction: TextDirection) {
+        self.direction = direction;
+    }
+
+    #[inline]
+    pub fn direction(&self) -> TextDirection {
+        self.direction
+    }
+
     /// Changes the (optional) censor function.
     /// The censor function can take a string slice and return a new string with arbitrary censorship applied (e.g. password field).
     ///
@@ -142,11 +426,51 @@ impl<T: 'static> TextBox<T> {
         self.censor.as_ref().map(|x| x.as_ref())
     }
 
+    /// Changes the input filter, which is given each character the user attempts to insert along with the current text.
+    /// Characters for which it returns `false` are rejected.
+    ///
+    /// For example, `|c, _| c.is_ascii_digit()` restricts input to digits.
+    pub fn set_input_filter(&mut self, filter: impl FnMut(char, &str) -> bool + 'static) {
+        self.input_filter = Some(Box::new(filter));
+    }
+
+    /// Resets the input filter; all characters will be accepted.
+    pub fn reset_input_filter(&mut self) {
+        self.input_filter = None;
+    }
+
+    /// Changes the validator, which is run whenever the text changes to update the [`validation`](TextBox::validation) state.
+    pub fn set_validator(&mut self, validator: impl FnMut(&str) -> ValidationState + 'static) {
+        self.validator = Some(Box::new(validator));
+        self.validate();
+    }
+
+    /// Resets the validator, and the validation state to valid.
+    pub fn reset_validator(&mut self) {
+        self.validator = None;
+        self.set_validation(ValidationState::Valid);
+    }
+
+    /// Changes the validation state directly.
+    ///
+    /// This will be overwritten by the validator on the next edit, if there is one.
+    pub fn set_validation(&mut self, validation: ValidationState) {
+        self.validation = validation;
+        self.repaint();
+    }
+
+    #[inline]
+    pub fn validation(&self) -> &ValidationState {
+        &self.validation
+    }
+
     /// Changes the multi-line ability of this textbox.
     ///
     /// This differs from the wrapping mode ([`set_wrap`](TextBox::set_wrap)), in that wrapping is
     /// the ability to overflow text without clipping, whereas multi-line is the ability to
     /// receive newline inputs to create physical newlines (e.g. user pressing enter/return key).
+    ///
+    /// When enabled, up/down move the cursor between lines and home/end move to the start/end of the current line.
     pub fn set_multi_line(&mut self, multi_line: bool) {
         self.multi_line = multi_line;
         self.update_label();
@@ -158,9 +482,14 @@ impl<T: 'static> TextBox<T> {
         self.multi_line
     }
 
+    /// Moves the cursor, clearing the selection.
+    ///
+    /// The cursor is kept within the text, on a character boundary.
     #[inline]
     pub fn set_cursor(&mut self, cursor: usize) {
-        self.cursor = cursor;
+        self.cursor = floor_boundary(&self.text, cursor);
+        self.anchor = None;
+        self.repaint();
     }
 
     #[inline]
@@ -168,11 +497,308 @@ impl<T: 'static> TextBox<T> {
         self.cursor
     }
 
+    /// Returns `true` if the caret is in the visible phase of its blink, which restarts whenever the cursor moves.
+    #[inline]
+    pub fn caret_visible(&self) -> bool {
+        self.caret_blink.visible()
+    }
+
+    /// Selects the text between `anchor` and `cursor`, leaving the cursor at `cursor`.
+    ///
+    /// The anchor may come after the cursor (i.e. a selection made backwards). Both are kept within the text, on character boundaries.
+    pub fn select(&mut self, anchor: usize, cursor: usize) {
+        self.anchor = Some(floor_boundary(&self.text, anchor));
+        self.cursor = floor_boundary(&self.text, cursor);
+        self.repaint();
+    }
+
+    pub fn select_all(&mut self) {
+        self.select(0, self.text.len());
+    }
+
+    /// Returns the selected range as `(start, end)`, if there is a non-empty selection.
+    pub fn selection(&self) -> Option<(usize, usize)> {
+        let anchor = self.anchor?;
+        if anchor == self.cursor {
+            None
+        } else {
+            Some((anchor.min(self.cursor), anchor.max(self.cursor)))
+        }
+    }
+
+    /// Returns the selected text, which is empty if there is no selection.
+    pub fn selected_text(&self) -> &str {
+        self.selection()
+            .map(|(start, end)| &self.text[start..end])
+            .unwrap_or_default()
+    }
+
+    /// Reverts the last edit made by the user, restoring the text and cursor position.
+    pub fn undo(&mut self) {
+        if let Some(state) = self.undo_stack.pop() {
+            let current = self.swap_state(state);
+            self.redo_stack.push(current);
+        }
+    }
+
+    /// Re-applies the last edit reverted by [`undo`](TextBox::undo).
+    pub fn redo(&mut self) {
+        if let Some(state) = self.redo_stack.pop() {
+            let current = self.swap_state(state);
+            self.undo_stack.push(current);
+        }
+    }
+
+    #[inline]
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    #[inline]
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Returns the text currently being composed through an input method, which hasn't been committed yet.
+    #[inline]
+    pub fn composition(&self) -> &str {
+        &self.composition
+    }
+
+    /// Returns the text as displayed, with any [composition](TextBox::composition) inserted at the cursor.
+    pub fn composed_text(&self) -> String {
+        let mut text = self.text.clone();
+        text.insert_str(self.cursor, &self.composition);
+        text
+    }
+
+    /// Returns the cursor position closest to `point` (in absolute coordinates), as measured by the theme.
+    ///
+    /// This is what clicking and dragging within the text box uses to position the cursor.
+    pub fn index_at(&mut self, point: gfx::Point) -> Option<usize> {
+        let index = theme::hit_test(self, point, |x| &mut x.painter)?;
+        Some(floor_boundary(&self.text, index))
+    }
+
+    /// Selects the word under `point` (in absolute coordinates), or the run of whitespace or punctuation if there's no word there; as done by double-clicking.
+    pub fn select_word_at(&mut self, point: gfx::Point) {
+        let index = match self.index_at(point) {
+            Some(index) => index,
+            None => return,
+        };
+
+        let is_word = |c: char| c.is_alphanumeric() || c == '_';
+        // the character after the index decides the kind of run, unless the index is at the end.
+        let kind = match self.text[index..]
+            .chars()
+            .next()
+            .or_else(|| self.text[..index].chars().next_back())
+        {
+            Some(c) => is_word(c),
+            None => return,
+        };
+
+        let start = self.text[..index]
+            .char_indices()
+            .rev()
+            .take_while(|&(_, c)| is_word(c) == kind)
+            .last()
+            .map_or(index, |(i, _)| i);
+        let end = self.text[index..]
+            .char_indices()
+            .find(|&(_, c)| is_word(c) != kind)
+            .map_or(self.text.len(), |(i, _)| index + i);
+
+        self.select(start, end);
+        self.coalesce = false;
+    }
+
+    /// Returns the rectangle (in absolute coordinates) of the caret, after any [composition](TextBox::composition), as measured by the theme.
+    pub fn caret_rect(&mut self) -> Option<gfx::Rect> {
+        let index = self.cursor + self.composition.len();
+        theme::caret_rect(self, index, |x| &mut x.painter)
+    }
+
+    /// Reports the caret to [`Aux::ime_area`](ui::Aux::ime_area), so that input method candidates are shown next to it.
+    fn update_ime_area(&mut self, aux: &mut ui::Aux<T>) {
+        if let Some(area) = self.caret_rect() {
+            aux.ime_area = Some(area);
+        }
+    }
+
+    /// Forgets all undo and redo steps.
+    pub fn clear_history(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.coalesce = false;
+    }
+
+    /// Records the state prior to an edit.
+    ///
+    /// Consecutive character insertions are coalesced into a single undo step.
+    fn record_edit(&mut self, before: EditState, insertion: bool) {
+        if !(insertion && self.coalesce) {
+            self.undo_stack.push(before);
+            if self.undo_stack.len() > HISTORY_LIMIT {
+                self.undo_stack.remove(0);
+            }
+        }
+        self.redo_stack.clear();
+        self.coalesce = insertion;
+    }
+
+    fn swap_state(&mut self, state: EditState) -> EditState {
+        let current = EditState {
+            text: std::mem::replace(&mut self.text, state.text),
+            cursor: self.cursor,
+        };
+        self.cursor = state.cursor.min(self.text.len());
+        self.anchor = None;
+        self.coalesce = false;
+        self.update_label();
+        current
+    }
+
+    /// Moves the cursor, extending the selection if `extend` (i.e. shift is held) and clearing it otherwise.
+    fn move_cursor(&mut self, cursor: usize, extend: bool) {
+        if extend {
+            self.anchor.get_or_insert(self.cursor);
+        } else {
+            self.anchor = None;
+        }
+        self.cursor = cursor;
+        // typing somewhere else starts a new undo step.
+        self.coalesce = false;
+        self.repaint();
+    }
+
+    /// Removes the selected range from `text`, returning `false` if there was no selection.
+    fn delete_selection(&mut self, text: &mut String) -> bool {
+        let selection = self.selection();
+        self.anchor = None;
+        if let Some((start, end)) = selection {
+            text.replace_range(start..end, "");
+            self.cursor = start;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn accepts(&mut self, c: char, text: &str) -> bool {
+        self.input_filter
+            .as_mut()
+            .map(|filter| filter(c, text))
+            .unwrap_or(true)
+    }
+
+    /// Returns `true` if another character can be inserted without exceeding the maximum length.
+    fn has_room(&self, text: &str) -> bool {
+        self.max_length
+            .map(|max| text.chars().count() < max)
+            .unwrap_or(true)
+    }
+
+    fn validate(&mut self) {
+        if let Some(validator) = &mut self.validator {
+            let validation = validator(&self.text);
+            if validation != self.validation {
+                self.validation = validation;
+                self.repaint();
+            }
+        }
+    }
+
+    /// Copies the selected text to the clipboard.
+    fn copy(&self, aux: &mut ui::Aux<T>) {
+        if self.selection().is_some() && self.censor.is_none() {
+            aux.clipboard().set_text(self.selected_text().to_string());
+        }
+    }
+
+    /// Moves the selected text into the clipboard.
+    fn cut(&mut self, text: &mut String, aux: &mut ui::Aux<T>) {
+        if self.censor.is_none() {
+            self.copy(aux);
+            self.delete_selection(text);
+        }
+    }
+
+    /// Replaces the selection with (or inserts at the cursor) the clipboard text.
+    fn paste(&mut self, text: &mut String, aux: &mut ui::Aux<T>) {
+        if let Some(mut pasted) = aux.clipboard().get_text() {
+            if !self.multi_line {
+                pasted = pasted.replace(&['\n', '\r'][..], "");
+            }
+            self.delete_selection(text);
+            self.insert_str(text, &pasted);
+        }
+    }
+
+    /// Inserts `s` into `text` at the cursor, skipping filtered characters and stopping at the maximum length.
+    fn insert_str(&mut self, text: &mut String, s: &str) {
+        for c in s.chars() {
+            if !self.has_room(text) {
+                break;
+            }
+            if self.accepts(c, text) {
+                text.insert(self.cursor, c);
+                self.cursor += c.len_utf8();
+            }
+        }
+    }
+
+    /// Applies the edited `text`, recording it in the undo history (unless it was `restored` from the history) and emitting [`TextChangedEvent`](TextChangedEvent).
+    fn finish_edit(
+        &mut self,
+        before: EditState,
+        text: String,
+        insertion: bool,
+        restored: bool,
+        aux: &mut ui::Aux<T>,
+    ) {
+        let changed = text != before.text;
+        if !restored && changed {
+            self.record_edit(
+                EditState {
+                    text: before.text.clone(),
+                    cursor: before.cursor,
+                },
+                insertion,
+            );
+        }
+        self.set_text(text);
+        if changed {
+            let new = self.text.clone();
+            self.emit(
+                aux,
+                TextChangedEvent {
+                    old: before.text,
+                    new,
+                },
+            );
+        }
+    }
+
+    /// Starts selecting from `pos`, or extends the selection to it if `extend` (i.e. shift is held).
+    fn begin_drag(&mut self, pos: gfx::Point, extend: bool) {
+        if let Some(index) = self.index_at(pos) {
+            if extend {
+                self.move_cursor(index, true);
+            } else {
+                // anchor at the press so that dragging selects from here.
+                self.select(index, index);
+                self.coalesce = false;
+            }
+            self.dragging = Some(pos);
+        }
+    }
+
     fn update_label(&mut self) {
-        let mut text = if self.text.is_empty() {
+        let mut text = if self.text.is_empty() && self.composition.is_empty() {
             self.placeholder.clone()
         } else {
-            self.text.clone()
+            self.composed_text()
         };
 
         if let Some(censor) = &mut self.censor {
@@ -184,11 +810,24 @@ impl<T: 'static> TextBox<T> {
         }
 
         self.text_label.set_text(text);
+        self.text_label.set_align(self.align);
         self.text_label.set_max_width(if self.wrap {
             Some(self.bounds().size.width)
         } else {
             None
         });
+
+        // wrapped text is aligned by the label itself, within the maximum width.
+        let x = if self.wrap {
+            0.
+        } else {
+            self.align
+                .offset(self.bounds().size.width, self.text_label.rect().size.width)
+        };
+        self.text_label.set_position(gfx::Point::new(x, 0.));
+
+        let baseline = self.text_label.common().with(|x| x.baseline());
+        self.common.with(|x| x.set_baseline(baseline));
     }
 }
 
@@ -201,16 +840,30 @@ impl<T: 'static> ui::Element for TextBox<T> {
     }
 
     fn update(&mut self, aux: &mut ui::Aux<T>) {
-        self.text_label
-            .set_color(aux.theme.color(if self.text.is_empty() {
+        self.text_label.set_color(aux.color(
+            if self.text.is_empty() && self.composition.is_empty() {
                 theme::colors::WEAK_FOREGROUND
             } else {
                 theme::colors::FOREGROUND
-            }));
+            },
+        ));
 
         ui::dispatch_components(self, aux, |x| &mut x.components).unwrap();
         ui::dispatch_list::<kit::ReadWrite<Self>, _>((self, aux), |(x, _)| &mut x.listeners);
 
+        if aux.has_focus(&self.common) {
+            if self.cursor != self.blink_cursor {
+                self.blink_cursor = self.cursor;
+                self.update_ime_area(aux);
+                if !self.caret_blink.visible() {
+                    self.repaint();
+                }
+                self.caret_blink.restart();
+            } else if self.caret_blink.tick(aux) {
+                self.repaint();
+            }
+        }
+
         ui::propagate_repaint(self);
     }
 
@@ -229,6 +882,53 @@ impl<T: 'static> ui::WidgetChildren<T> for TextBox<T> {
     crate::children![for <T>; text_label];
 }
 
+/// Returns the closest character boundary at or before `index`, which may be past the end of the text.
+fn floor_boundary(text: &str, index: usize) -> usize {
+    let mut index = index.min(text.len());
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+/// Returns the index of the character before `index`, or `0` if there is none.
+fn prev_boundary(text: &str, index: usize) -> usize {
+    text[..index]
+        .chars()
+        .next_back()
+        .map_or(0, |c| index - c.len_utf8())
+}
+
+/// Returns the index after the character at `index`, or the end of the text if there is none.
+fn next_boundary(text: &str, index: usize) -> usize {
+    text[index..]
+        .chars()
+        .next()
+        .map_or(text.len(), |c| index + c.len_utf8())
+}
+
+/// Returns the index of the start of the line containing `index`.
+fn line_start(text: &str, index: usize) -> usize {
+    text[..index].rfind('\n').map(|x| x + 1).unwrap_or(0)
+}
+
+/// Returns the index of the end of the line containing `index` (i.e. the index of the newline, or the end of the text).
+fn line_end(text: &str, index: usize) -> usize {
+    text[index..]
+        .find('\n')
+        .map(|x| index + x)
+        .unwrap_or(text.len())
+}
+
+/// Returns the index of the character `column` characters into the line starting at `start`, or the end of the line if it's shorter.
+fn column_index(text: &str, start: usize, column: usize) -> usize {
+    let end = line_end(text, start);
+    text[start..end]
+        .char_indices()
+        .nth(column)
+        .map_or(end, |(i, _)| start + i)
+}
+
 /// Censor function for [`TextBox`](TextBox), appropriate for password fields.
 #[inline]
 pub fn password_censor(s: &str) -> String {
@@ -0,0 +1,220 @@
+use {
+    crate::{kit, prelude::*, theme, ui},
+    reclutch::display as gfx,
+};
+
+/// Complete single-line text input; a [`TextBox`](kit::TextBox) decorated with a themed background, border and focus ring.
+///
+/// An icon can be shown on either side of the text (e.g. a magnifying glass for a search field).
+///
+/// Events of the inner text box ([`TextChangedEvent`](kit::TextChangedEvent), key presses and focus changes) are re-emitted on behalf of the text input.
+pub struct TextInput<T: 'static> {
+    text_box: kit::TextBox<T>,
+    leading_icon: Option<kit::Icon>,
+    trailing_icon: Option<kit::Icon>,
+    laid_out: gfx::Size,
+
+    painter: theme::Painter<Self>,
+    common: ui::CommonRef,
+    listeners: ui::ListenerList<kit::ReadWrite<Self>>,
+    components: ui::ComponentList<Self>,
+}
+
+impl<T: 'static> TextInput<T> {
+    pub fn new(parent: ui::CommonRef, aux: &mut ui::Aux<T>) -> Self {
+        let common = ui::CommonRef::new(parent);
+
+        let text_box = kit::TextBox::new(common.clone(), aux);
+
+        let text_box_listener = aux
+            .listen::<kit::ReadWrite<Self>>()
+            .and_on(
+                text_box.id(),
+                |(obj, aux), event: &kit::TextChangedEvent| {
+                    obj.emit(
+                        aux,
+                        kit::TextChangedEvent {
+                            old: event.old.clone(),
+                            new: event.new.clone(),
+                        },
+                    )
+                },
+            )
+            .and_on(text_box.id(), |(obj, aux), event: &kit::KeyPressEvent| {
+                obj.emit(aux, kit::KeyPressEvent(event.0))
+            })
+            .and_on(text_box.id(), |(obj, aux), event: &kit::KeyReleaseEvent| {
+                obj.emit(aux, kit::KeyReleaseEvent(event.0))
+            })
+            .and_on(text_box.id(), |(obj, aux), _: &kit::FocusGainedEvent| {
+                obj.repaint();
+                obj.emit(aux, kit::FocusGainedEvent)
+            })
+            .and_on(text_box.id(), |(obj, aux), _: &kit::FocusLostEvent| {
+                obj.repaint();
+                obj.emit(aux, kit::FocusLostEvent)
+            });
+
+        let theme_listener = aux.listen::<kit::ReadWrite<Self>>().and_on(
+            aux.id,
+            |(obj, aux), _: &ui::ThemeChangedEvent| {
+                theme::refresh_painter(obj, |x| &mut x.painter, aux);
+                theme::apply_size_hint(obj, |x| &mut x.painter);
+                obj.layout_text_box();
+            },
+        );
+
+        let mut input = TextInput {
+            text_box,
+            leading_icon: None,
+            trailing_icon: None,
+            laid_out: Default::default(),
+
+            painter: theme::get_painter(aux, theme::painters::TEXT_INPUT),
+            common,
+            listeners: ui::ListenerList::new(vec![text_box_listener, theme_listener]),
+            components: ui::ComponentList::new().and_push(kit::InteractionState::new(
+                aux,
+                |obj: &mut Self, aux, event| {
+                    // presses on the decoration (rather than the text itself) still focus the text.
+                    if let kit::InteractionEvent::Press(_) = event {
+                        aux.grab_focus(obj.text_box.common().clone());
+                    }
+                    kit::interaction_forwarder(None)(obj, aux, event);
+                },
+                None,
+                None,
+            )),
+        };
+
+        theme::apply_size_hint(&mut input, |x| &mut x.painter);
+        input.layout_text_box();
+
+        input
+    }
+
+    pub fn set_text(&mut self, text: impl ToString) {
+        self.text_box.set_text(text);
+    }
+
+    #[inline]
+    pub fn text(&self) -> &str {
+        self.text_box.text()
+    }
+
+    pub fn set_placeholder(&mut self, placeholder: impl ToString) {
+        self.text_box.set_placeholder(placeholder);
+    }
+
+    #[inline]
+    pub fn placeholder(&self) -> &str {
+        self.text_box.placeholder()
+    }
+
+    /// Changes the icon shown before the text, or removes it if `None`.
+    pub fn set_leading_icon(&mut self, icon: impl Into<Option<kit::Icon>>) {
+        self.leading_icon = icon.into();
+        self.layout_text_box();
+    }
+
+    #[inline]
+    pub fn leading_icon(&self) -> Option<&kit::Icon> {
+        self.leading_icon.as_ref()
+    }
+
+    /// Changes the icon shown after the text, or removes it if `None`.
+    pub fn set_trailing_icon(&mut self, icon: impl Into<Option<kit::Icon>>) {
+        self.trailing_icon = icon.into();
+        self.layout_text_box();
+    }
+
+    #[inline]
+    pub fn trailing_icon(&self) -> Option<&kit::Icon> {
+        self.trailing_icon.as_ref()
+    }
+
+    /// Returns the inner text box, for access to the rest of its options (censoring, validation, etc).
+    #[inline]
+    pub fn text_box(&self) -> &kit::TextBox<T> {
+        &self.text_box
+    }
+
+    #[inline]
+    pub fn text_box_mut(&mut self) -> &mut kit::TextBox<T> {
+        &mut self.text_box
+    }
+
+    /// Returns `true` if the inner text box has focus.
+    #[inline]
+    pub fn focused(&self, aux: &ui::Aux<T>) -> bool {
+        aux.has_focus(self.text_box.common())
+    }
+
+    /// Fits the text box within the padding, leaving room for the icons.
+    fn layout_text_box(&mut self) {
+        let padding = self.painter.padding(self);
+        let icon_space = self.painter.icon_size(self) + self.painter.icon_spacing(self);
+        let size = self.size();
+
+        let mut left = padding.left;
+        let mut right = size.width - padding.right;
+        if self.leading_icon.is_some() {
+            left += icon_space;
+        }
+        if self.trailing_icon.is_some() {
+            right -= icon_space;
+        }
+
+        self.text_box.set_rect(gfx::Rect::new(
+            gfx::Point::new(left, padding.top),
+            gfx::Size::new(
+                (right - left).max(0.),
+                (size.height - padding.vertical()).max(0.),
+            ),
+        ));
+        self.laid_out = size;
+        self.repaint();
+
+        let baseline = self
+            .text_box
+            .common()
+            .with(|x| x.baseline())
+            .map(|x| x + padding.top);
+        self.common.with(|x| x.set_baseline(baseline));
+    }
+}
+
+impl<T: 'static> ui::Element for TextInput<T> {
+    type Aux = T;
+
+    #[inline]
+    fn common(&self) -> &ui::CommonRef {
+        &self.common
+    }
+
+    fn update(&mut self, aux: &mut ui::Aux<T>) {
+        // the size may have been changed by a layout since the last update.
+        if self.size() != self.laid_out {
+            self.layout_text_box();
+        }
+
+        ui::dispatch_components(self, aux, |x| &mut x.components).unwrap();
+        ui::dispatch_list::<kit::ReadWrite<Self>, _>((self, aux), |(x, _)| &mut x.listeners);
+
+        ui::propagate_repaint(self);
+    }
+
+    fn draw(&mut self, display: &mut dyn gfx::GraphicsDisplay, aux: &mut ui::Aux<T>) {
+        ui::draw(
+            self,
+            |o, aux| theme::paint(o, |o| &mut o.painter, aux),
+            display,
+            aux,
+            None,
+        );
+    }
+}
+
+impl<T: 'static> ui::WidgetChildren<T> for TextInput<T> {
+    crate::children![for <T>; text_box];
+}
@@ -0,0 +1,77 @@
+use {
+    crate::{kit, prelude::*, theme, ui},
+    reclutch::display as gfx,
+};
+
+/// A small floating panel of text, shown by the application root over a widget which has a [tooltip](ui::Common::set_tooltip).
+///
+/// This is drawn above all other widgets.
+pub struct Tooltip<T: 'static> {
+    text: String,
+
+    painter: theme::Painter<Self>,
+    common: ui::CommonRef,
+    listeners: ui::ListenerList<kit::ReadWrite<Self>>,
+}
+
+impl<T: 'static> Tooltip<T> {
+    pub fn new(parent: ui::CommonRef, aux: &mut ui::Aux<T>) -> Self {
+        let theme_listener = aux.listen::<kit::ReadWrite<Self>>().and_on(
+            aux.id,
+            |(obj, aux), _: &ui::ThemeChangedEvent| {
+                theme::refresh_painter(obj, |x| &mut x.painter, aux);
+                obj.resize();
+            },
+        );
+
+        Tooltip {
+            text: String::new(),
+
+            painter: theme::get_painter(aux, theme::painters::TOOLTIP),
+            common: ui::CommonRef::new(parent),
+            listeners: ui::ListenerList::new(vec![theme_listener]),
+        }
+    }
+
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        self.resize();
+    }
+
+    #[inline]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    fn resize(&mut self) {
+        let size = theme::size_hint(self, |x| &mut x.painter);
+        self.set_size(size);
+        self.repaint();
+    }
+}
+
+impl<T: 'static> ui::Element for Tooltip<T> {
+    type Aux = T;
+
+    #[inline]
+    fn common(&self) -> &ui::CommonRef {
+        &self.common
+    }
+
+    #[inline]
+    fn update(&mut self, aux: &mut ui::Aux<T>) {
+        ui::dispatch_list::<kit::ReadWrite<Self>, _>((self, aux), |(x, _)| &mut x.listeners);
+    }
+
+    fn draw(&mut self, display: &mut dyn gfx::GraphicsDisplay, aux: &mut ui::Aux<T>) {
+        ui::draw(
+            self,
+            |o, a| theme::paint(o, |o| &mut o.painter, a),
+            display,
+            aux,
+            gfx::ZOrder(std::i32::MAX - 1),
+        );
+    }
+}
+
+impl<T: 'static> ui::WidgetChildren<T> for Tooltip<T> {}
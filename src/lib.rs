@@ -10,10 +10,18 @@
 //!
 //! - `ui`; Defines the core interface and is the primary module.
 //!     - `ui::view`; Modern and simple interface to compose a UI.
+//!     - `ui::layout::spec`; Loading layout trees from a RON description. Feature `spec` required.
 //! - `theme`; Defines the theme interface.
-//!     - `theme::flat`; An implementation of the theme interface for a simple, dark, flat-style theme. Feature `themes` required.
+//!     - `theme::draw`; Drawing helpers shared between theme implementations.
+//!     - `theme::animation`; Transitions of colors and metrics over time, for use by painters.
+//!     - `theme::tokens`; Typed names of theme colors and metrics.
+//!     - `theme::overrides`; A theme which replaces some of the painters, colors and metrics of another.
+//!     - `theme::flat`; An implementation of the theme interface for a simple, flat-style theme with dark and light palettes. Feature `themes` required.
+//!     - `theme::soft`; A theme of rounded, raised controls with soft shadows and animated elevations, built upon `theme::flat`. Feature `themes` required.
+//!     - `theme::file`; A theme loaded from a RON description of its colors, metrics, fonts and standards, painted by `theme::flat`. Features `themes` and `spec` required.
 //! - `kit`; Toolkit of widgets. Feature `kit` required.
-//! - `app`; Application helper utility. Quick one-liner way to load a window and run a UI using Glutin/Winit and Skia, however offers minimal control in return.
+//! - `app`; Application helper utility. Quick one-liner way to load a window and run a UI using Glutin/Winit and Skia, however offers minimal control in return (see `app::Runner` for embedding into an existing event loop, and `app::render_to_image` for rendering without a window).
+//!     - `app::gamepad`; Gamepad input through gilrs, also usable for navigating widgets. Feature `gamepad` required.
 
 #[cfg(feature = "app")]
 pub mod app;
@@ -24,14 +32,16 @@ pub mod ui;
 
 pub mod prelude {
     pub use crate::{
-        theme::{Theme, TypedPainter},
+        theme::{PainterMetrics, Theme, TypedPainter},
         ui::{layout::Layout, AnyElement, Element, ElementMixin, Id, WidgetChildren},
     };
 
     #[cfg(feature = "kit")]
-    pub use crate::kit::ViewMixin;
+    pub use crate::kit::{Interactive, ViewMixin};
 }
 
+#[cfg(feature = "app")]
+pub use raw_window_handle;
 pub use reclutch;
 
 #[macro_use]
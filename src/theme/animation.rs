@@ -0,0 +1,196 @@
+//! Transitions of colors and metrics over time, for painters to animate changes in appearance (e.g. hover fades),
+//! and blinking for elements which flash on and off (e.g. text carets).
+
+use {crate::ui, reclutch::display as gfx, std::time::Duration};
+
+/// A value which can be blended towards another.
+pub trait Interpolate: Copy {
+    /// Returns the value a fraction `t` (from `0.0` to `1.0`) of the way from `self` to `to`.
+    fn interpolate(self, to: Self, t: f32) -> Self;
+}
+
+impl Interpolate for f32 {
+    #[inline]
+    fn interpolate(self, to: f32, t: f32) -> f32 {
+        self + (to - self) * t
+    }
+}
+
+impl Interpolate for gfx::Color {
+    fn interpolate(self, to: gfx::Color, t: f32) -> gfx::Color {
+        gfx::Color::new(
+            self.red.interpolate(to.red, t),
+            self.green.interpolate(to.green, t),
+            self.blue.interpolate(to.blue, t),
+            self.alpha.interpolate(to.alpha, t),
+        )
+    }
+}
+
+/// Linear progression, for use as the easing of an [`Animator`](Animator).
+#[inline]
+pub fn linear(t: f32) -> f32 {
+    t
+}
+
+/// Progression which starts and ends slowly, for use as the easing of an [`Animator`](Animator).
+#[inline]
+pub fn ease_in_out(t: f32) -> f32 {
+    t * t * (3. - 2. * t)
+}
+
+/// Transitions a value towards a target over a fixed duration, driven by the [frame clock](ui::Aux::frame_dt).
+///
+/// Painters can keep animators as state and query them through [`animate`](Animator::animate) while painting,
+/// which also keeps the widget redrawing until the transition is finished.
+#[derive(Debug, Clone, Copy)]
+pub struct Animator<V: Interpolate> {
+    from: V,
+    to: V,
+    elapsed: Duration,
+    duration: Duration,
+    easing: fn(f32) -> f32,
+}
+
+impl<V: Interpolate + PartialEq> Animator<V> {
+    /// Creates an animator resting at `value`, with transitions lasting `duration` and [eased in and out](ease_in_out).
+    pub fn new(value: V, duration: Duration) -> Self {
+        Animator {
+            from: value,
+            to: value,
+            elapsed: duration,
+            duration,
+            easing: ease_in_out,
+        }
+    }
+
+    /// Changes the easing function, which maps the linear progress of a transition (from `0.0` to `1.0`) to the fraction of the way to the target.
+    pub fn with_easing(mut self, easing: fn(f32) -> f32) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Starts transitioning from the current value to `target`, unless `target` is already the target.
+    pub fn set_target(&mut self, target: V) {
+        if target != self.to {
+            self.from = self.value();
+            self.to = target;
+            self.elapsed = Duration::default();
+        }
+    }
+
+    #[inline]
+    pub fn target(&self) -> V {
+        self.to
+    }
+
+    /// Changes the value immediately, without a transition.
+    pub fn set_value(&mut self, value: V) {
+        self.from = value;
+        self.to = value;
+        self.elapsed = self.duration;
+    }
+
+    /// Returns the current value.
+    pub fn value(&self) -> V {
+        if self.animating() {
+            let t = self.elapsed.as_secs_f32() / self.duration.as_secs_f32();
+            self.from.interpolate(self.to, (self.easing)(t))
+        } else {
+            self.to
+        }
+    }
+
+    /// Returns `true` if the value hasn't reached the target yet.
+    #[inline]
+    pub fn animating(&self) -> bool {
+        self.elapsed < self.duration
+    }
+
+    /// Moves the transition forward by `dt`, returning the new value.
+    pub fn advance(&mut self, dt: Duration) -> V {
+        self.elapsed = (self.elapsed + dt).min(self.duration);
+        self.value()
+    }
+
+    /// Transitions towards `target` by the time elapsed since the last frame, returning the new value.
+    ///
+    /// Until the target is reached, the widget of `common` is [redrawn](ui::Common::request_redraw) every frame.
+    pub fn animate<T: 'static>(
+        &mut self,
+        target: V,
+        common: &ui::CommonRef,
+        aux: &ui::Aux<T>,
+    ) -> V {
+        self.set_target(target);
+        let value = self.advance(aux.frame_dt());
+        if self.animating() {
+            common.with(|x| x.request_redraw());
+        }
+        value
+    }
+}
+
+/// Time for which a [`Blink`](Blink) is shown, then hidden, by default; that of a text caret.
+pub const CARET_BLINK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Alternates between a visible and a hidden phase of equal length, driven by the [frame clock](ui::Aux::frame_dt).
+///
+/// Being driven by elapsed time rather than by frames, the rate of blinking doesn't depend on the frame rate.
+#[derive(Debug, Clone, Copy)]
+pub struct Blink {
+    elapsed: Duration,
+    interval: Duration,
+    visible: bool,
+}
+
+impl Default for Blink {
+    #[inline]
+    fn default() -> Self {
+        Blink::new(CARET_BLINK_INTERVAL)
+    }
+}
+
+impl Blink {
+    /// Creates a blink starting at the beginning of its visible phase, with each phase lasting `interval`.
+    pub fn new(interval: Duration) -> Self {
+        Blink {
+            elapsed: Duration::default(),
+            interval,
+            visible: true,
+        }
+    }
+
+    #[inline]
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Returns to the beginning of the visible phase (e.g. when a caret moves, so that it's seen straight away).
+    pub fn restart(&mut self) {
+        self.elapsed = Duration::default();
+        self.visible = true;
+    }
+
+    /// Returns `true` if the blink is in its visible phase.
+    #[inline]
+    pub fn visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Moves the blink forward by `dt`, returning `true` if it changed phase.
+    pub fn advance(&mut self, dt: Duration) -> bool {
+        self.elapsed += dt;
+        let interval = self.interval.as_nanos().max(1);
+        let visible = self.elapsed.as_nanos() % (interval * 2) < interval;
+        let changed = visible != self.visible;
+        self.visible = visible;
+        changed
+    }
+
+    /// Moves the blink forward by the time elapsed since the last frame, returning `true` if it changed phase.
+    #[inline]
+    pub fn tick<T: 'static>(&mut self, aux: &ui::Aux<T>) -> bool {
+        self.advance(aux.frame_dt())
+    }
+}
@@ -0,0 +1,416 @@
+//! Theme-agnostic drawing helpers.
+//!
+//! These cover geometry which most themes need in some form (bordered rectangles, focus rings, glyphs),
+//! so that painters only have to decide on colors and dimensions.
+
+use reclutch::display as gfx;
+
+/// Direction a [`chevron`](chevron) points in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// Returns uniform corner radii.
+#[inline]
+pub fn radii(radius: f32) -> [f32; 4] {
+    [radius; 4]
+}
+
+/// Returns `c` with its alpha replaced by `a`.
+#[inline]
+pub fn with_alpha(mut c: gfx::Color, a: f32) -> gfx::Color {
+    c.alpha = a;
+    c
+}
+
+/// Returns a stroke paint of a given color and thickness.
+#[inline]
+pub fn stroke(color: gfx::Color, thickness: f32) -> gfx::GraphicsDisplayPaint {
+    gfx::GraphicsDisplayPaint::Stroke(gfx::GraphicsDisplayStroke {
+        thickness,
+        color: color.into(),
+        ..Default::default()
+    })
+}
+
+/// Returns a fill paint of a given color.
+#[inline]
+pub fn fill(color: gfx::Color) -> gfx::GraphicsDisplayPaint {
+    gfx::GraphicsDisplayPaint::Fill(gfx::StyleColor::Color(color))
+}
+
+/// Returns a linear gradient running from the top to the bottom of `rect`, through `stops` of `(offset, color)`
+/// where the offset is from `0.0` (the top) to `1.0` (the bottom).
+pub fn vertical_gradient(rect: gfx::Rect, stops: Vec<(f64, gfx::Color)>) -> gfx::StyleColor {
+    gfx::StyleColor::LinearGradient(gfx::Gradient {
+        start: gfx::Point::new(rect.center().x, rect.min_y()),
+        end: gfx::Point::new(rect.center().x, rect.max_y()),
+        stops,
+    })
+}
+
+/// Applies `f` to a flat color, or to every stop of a gradient.
+pub fn map_style_color(
+    color: gfx::StyleColor,
+    f: impl Fn(gfx::Color) -> gfx::Color,
+) -> gfx::StyleColor {
+    match color {
+        gfx::StyleColor::Color(color) => gfx::StyleColor::Color(f(color)),
+        gfx::StyleColor::LinearGradient(mut gradient) => {
+            for stop in &mut gradient.stops {
+                stop.1 = f(stop.1);
+            }
+            gfx::StyleColor::LinearGradient(gradient)
+        }
+        gfx::StyleColor::RadialGradient(mut gradient) => {
+            for stop in &mut gradient.stops {
+                stop.1 = f(stop.1);
+            }
+            gfx::StyleColor::RadialGradient(gradient)
+        }
+    }
+}
+
+/// Pushes a filled rounded rectangle with an optional inner border of `(color, thickness)`.
+pub fn bordered_rect(
+    out: &mut gfx::DisplayListBuilder,
+    rect: gfx::Rect,
+    radii: [f32; 4],
+    background: gfx::Color,
+    border: Option<(gfx::Color, f32)>,
+) {
+    out.push_round_rectangle(rect, radii, fill(background), None);
+
+    if let Some((color, thickness)) = border {
+        // strokes are centered on the path, so inset by half to keep the border within `rect`.
+        let inset = thickness / 2.;
+        out.push_round_rectangle(
+            rect.inflate(-inset, -inset),
+            shrink_radii(radii, inset),
+            stroke(color, thickness),
+            None,
+        );
+    }
+}
+
+/// Pushes a ring around `rect`, separated from it by `offset`; for indicating keyboard focus.
+pub fn focus_ring(
+    out: &mut gfx::DisplayListBuilder,
+    rect: gfx::Rect,
+    radii: [f32; 4],
+    color: gfx::Color,
+    thickness: f32,
+    offset: f32,
+) {
+    let grow = offset + thickness / 2.;
+    out.push_round_rectangle(
+        rect.inflate(grow, grow),
+        grow_radii(radii, grow),
+        stroke(color, thickness),
+        None,
+    );
+}
+
+/// Pushes a soft shadow for `rect`, which should then be drawn over the top.
+///
+/// The shadow is approximated by `layers` concentric rounded rectangles of decreasing opacity,
+/// spreading `spread` units out from `rect` shifted by `offset`.
+pub fn shadow(
+    out: &mut gfx::DisplayListBuilder,
+    rect: gfx::Rect,
+    radii: [f32; 4],
+    color: gfx::Color,
+    offset: gfx::Vector,
+    spread: f32,
+    layers: u32,
+) {
+    let layers = layers.max(1);
+    let rect = gfx::Rect::new(rect.origin + offset, rect.size);
+    let alpha = color.alpha / layers as f32;
+    for i in (1..=layers).rev() {
+        let grow = spread * i as f32 / layers as f32;
+        let mut color = color;
+        color.alpha = alpha;
+        out.push_round_rectangle(
+            rect.inflate(grow, grow),
+            grow_radii(radii, grow),
+            fill(color),
+            None,
+        );
+    }
+}
+
+/// Pushes a vertical text caret of `height` from `top`.
+pub fn caret(out: &mut gfx::DisplayListBuilder, top: gfx::Point, height: f32, color: gfx::Color) {
+    out.push_line(
+        top,
+        gfx::Point::new(top.x, top.y + height),
+        gfx::GraphicsDisplayStroke {
+            thickness: 1.,
+            color: color.into(),
+            ..Default::default()
+        },
+        None,
+    );
+}
+
+/// Pushes a selection highlight filling `rect`, which is fainter if the selection isn't `focused` (i.e. it's kept by a widget without focus).
+pub fn selection(
+    out: &mut gfx::DisplayListBuilder,
+    rect: gfx::Rect,
+    radii: [f32; 4],
+    mut color: gfx::Color,
+    focused: bool,
+) {
+    if !focused {
+        color.alpha *= 0.5;
+    }
+    out.push_round_rectangle(rect, radii, fill(color), None);
+}
+
+/// Pushes the [`shadow`](shadow) cast by `rect` raised to `elevation` (see [`elevations`](crate::theme::elevations)),
+/// which grows and drops further with the elevation.
+pub fn elevation_shadow(
+    out: &mut gfx::DisplayListBuilder,
+    rect: gfx::Rect,
+    radii: [f32; 4],
+    color: gfx::Color,
+    elevation: f32,
+) {
+    if elevation <= 0. {
+        return;
+    }
+
+    shadow(
+        out,
+        rect,
+        radii,
+        color,
+        gfx::Vector::new(0., elevation / 2.),
+        elevation,
+        (elevation.ceil() as u32).max(1),
+    );
+}
+
+/// Pushes a softer [`elevation_shadow`](elevation_shadow), composed of a wide, faint ambient shadow and a tighter, dropped key shadow.
+pub fn soft_shadow(
+    out: &mut gfx::DisplayListBuilder,
+    rect: gfx::Rect,
+    radii: [f32; 4],
+    color: gfx::Color,
+    elevation: f32,
+) {
+    if elevation <= 0. {
+        return;
+    }
+
+    let layers = |spread: f32| ((spread * 2.).ceil() as u32).max(1).min(24);
+
+    let mut ambient = color;
+    ambient.alpha *= 0.4;
+    let spread = elevation * 1.5;
+    shadow(
+        out,
+        rect,
+        radii,
+        ambient,
+        gfx::Vector::new(0., elevation / 6.),
+        spread,
+        layers(spread),
+    );
+
+    let mut key = color;
+    key.alpha *= 0.6;
+    let spread = elevation * 0.75;
+    shadow(
+        out,
+        rect,
+        radii,
+        key,
+        gfx::Vector::new(0., elevation / 2.),
+        spread,
+        layers(spread),
+    );
+}
+
+/// Pushes a shadowed panel; an [`elevation_shadow`](elevation_shadow) with a filled rounded rectangle on top.
+pub fn shadowed_panel(
+    out: &mut gfx::DisplayListBuilder,
+    rect: gfx::Rect,
+    radii: [f32; 4],
+    background: gfx::Color,
+    shadow_color: gfx::Color,
+    elevation: f32,
+) {
+    elevation_shadow(out, rect, radii, shadow_color, elevation);
+    out.push_round_rectangle(rect, radii, fill(background), None);
+}
+
+/// Returns a check mark glyph path filling `r`.
+pub fn check_mark(r: gfx::Rect) -> gfx::VectorPath {
+    let mut path = gfx::VectorPathBuilder::new();
+
+    path.move_to(r.origin + gfx::Size::new(r.size.width, 0.));
+    path.line_to(r.origin + gfx::Size::new(r.size.width / 2., r.size.height));
+    path.line_to(r.origin + gfx::Size::new(0., r.size.height / 2.));
+
+    path.build()
+}
+
+/// Returns a chevron glyph path (`^`, `v`, `<` or `>`) centered in `r`.
+pub fn chevron(r: gfx::Rect, direction: Direction) -> gfx::VectorPath {
+    let c = r.center();
+    let v = r.size.width.min(r.size.height) / 3.;
+    let d = v / 2.;
+
+    let (a, b, e) = match direction {
+        Direction::Up => (
+            gfx::Vector::new(-v, d),
+            gfx::Vector::new(0., -d),
+            gfx::Vector::new(v, d),
+        ),
+        Direction::Down => (
+            gfx::Vector::new(-v, -d),
+            gfx::Vector::new(0., d),
+            gfx::Vector::new(v, -d),
+        ),
+        Direction::Left => (
+            gfx::Vector::new(d, -v),
+            gfx::Vector::new(-d, 0.),
+            gfx::Vector::new(d, v),
+        ),
+        Direction::Right => (
+            gfx::Vector::new(-d, -v),
+            gfx::Vector::new(d, 0.),
+            gfx::Vector::new(-d, v),
+        ),
+    };
+
+    let mut path = gfx::VectorPathBuilder::new();
+    path.move_to(c + a);
+    path.line_to(c + b);
+    path.line_to(c + e);
+
+    path.build()
+}
+
+/// Returns a pair of up and down chevrons stacked in `rect`, as typically seen on combo boxes.
+pub fn up_down_arrows(rect: gfx::Rect) -> gfx::VectorPath {
+    let c = rect.center();
+    let v = if rect.size.width > rect.size.height {
+        rect.size.height
+    } else {
+        rect.size.width
+    } / 3.;
+    let d = v / 2.;
+
+    let mut path = gfx::VectorPathBuilder::new();
+    path.move_to(c + gfx::Vector::new(-v, -v + d));
+    path.line_to(c + gfx::Vector::new(0., 2. * -v + d));
+    path.line_to(c + gfx::Vector::new(v, -v + d));
+
+    path.move_to(c + gfx::Vector::new(-v, v - d));
+    path.line_to(c + gfx::Vector::new(0., 2. * v - d));
+    path.line_to(c + gfx::Vector::new(v, v - d));
+
+    path.build()
+}
+
+/// Returns a folder glyph path filling `r`.
+pub fn folder(r: gfx::Rect) -> gfx::VectorPath {
+    let mut path = gfx::VectorPathBuilder::new();
+
+    let tab = r.size.width * 0.4;
+    let top = r.origin.y + r.size.height * 0.2;
+
+    path.move_to(gfx::Point::new(r.min_x(), r.max_y()));
+    path.line_to(gfx::Point::new(r.min_x(), r.min_y()));
+    path.line_to(gfx::Point::new(r.min_x() + tab, r.min_y()));
+    path.line_to(gfx::Point::new(r.min_x() + tab, top));
+    path.line_to(gfx::Point::new(r.max_x(), top));
+    path.line_to(gfx::Point::new(r.max_x(), r.max_y()));
+    path.line_to(gfx::Point::new(r.min_x(), r.max_y()));
+
+    path.build()
+}
+
+/// Returns a file (page with a folded corner) glyph path filling `r`.
+pub fn file(r: gfx::Rect) -> gfx::VectorPath {
+    let mut path = gfx::VectorPathBuilder::new();
+
+    let fold = r.size.width * 0.35;
+
+    path.move_to(gfx::Point::new(r.min_x(), r.min_y()));
+    path.line_to(gfx::Point::new(r.max_x() - fold, r.min_y()));
+    path.line_to(gfx::Point::new(r.max_x(), r.min_y() + fold));
+    path.line_to(gfx::Point::new(r.max_x(), r.max_y()));
+    path.line_to(gfx::Point::new(r.min_x(), r.max_y()));
+    path.line_to(gfx::Point::new(r.min_x(), r.min_y()));
+
+    path.build()
+}
+
+/// Returns a cross (`x`) glyph path centered in `r`, as seen on the close button of a window.
+pub fn cross(r: gfx::Rect) -> gfx::VectorPath {
+    let c = r.center();
+    let v = r.size.width.min(r.size.height) / 3.;
+
+    let mut path = gfx::VectorPathBuilder::new();
+    path.move_to(c + gfx::Vector::new(-v, -v));
+    path.line_to(c + gfx::Vector::new(v, v));
+    path.move_to(c + gfx::Vector::new(v, -v));
+    path.line_to(c + gfx::Vector::new(-v, v));
+
+    path.build()
+}
+
+/// Returns a horizontal bar glyph path centered in `r`, as seen on the minimize button of a window.
+pub fn minimize(r: gfx::Rect) -> gfx::VectorPath {
+    let c = r.center();
+    let v = r.size.width.min(r.size.height) / 3.;
+
+    let mut path = gfx::VectorPathBuilder::new();
+    path.move_to(c + gfx::Vector::new(-v, 0.));
+    path.line_to(c + gfx::Vector::new(v, 0.));
+
+    path.build()
+}
+
+/// Returns a square glyph path centered in `r`, as seen on the maximize button of a window.
+pub fn maximize(r: gfx::Rect) -> gfx::VectorPath {
+    let c = r.center();
+    let v = r.size.width.min(r.size.height) / 3.;
+
+    let mut path = gfx::VectorPathBuilder::new();
+    path.move_to(c + gfx::Vector::new(-v, -v));
+    path.line_to(c + gfx::Vector::new(v, -v));
+    path.line_to(c + gfx::Vector::new(v, v));
+    path.line_to(c + gfx::Vector::new(-v, v));
+    path.line_to(c + gfx::Vector::new(-v, -v));
+
+    path.build()
+}
+
+#[inline]
+fn grow_radii(radii: [f32; 4], amount: f32) -> [f32; 4] {
+    [
+        radii[0] + amount,
+        radii[1] + amount,
+        radii[2] + amount,
+        radii[3] + amount,
+    ]
+}
+
+#[inline]
+fn shrink_radii(radii: [f32; 4], amount: f32) -> [f32; 4] {
+    [
+        (radii[0] - amount).max(0.),
+        (radii[1] - amount).max(0.),
+        (radii[2] - amount).max(0.),
+        (radii[3] - amount).max(0.),
+    ]
+}
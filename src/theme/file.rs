@@ -0,0 +1,301 @@
+use {
+    crate::{
+        theme::{flat, *},
+        ui,
+    },
+    reclutch::display as gfx,
+    serde::Deserialize,
+    std::{collections::HashMap, rc::Rc},
+};
+
+/// Description of a [`FileTheme`](FileTheme), as deserialized from a theme file.
+///
+/// Anything left unspecified falls back to the [flat theme](flat::FlatTheme) of the chosen brightness.
+///
+/// For example, in RON;
+/// ```text
+/// (
+///     brightness: Light,
+///     colors: {
+///         "active": (230, 90, 40, 1.0),
+///     },
+///     gradients: {
+///         "strong_background": [(0.0, (70, 70, 70, 1.0)), (1.0, (52, 52, 52, 1.0))],
+///     },
+///     metrics: {
+///         "button": { "padding_x": 20.0, "padding_y": 6.0 },
+///     },
+///     fonts: (
+///         regular: "Noto Sans",
+///         size: 13.0,
+///         files: { "Noto Sans JP": "fonts/NotoSansJP-Regular.otf" },
+///         fallbacks: { Kana: ["Noto Sans JP"], Han: ["Noto Sans JP", "Noto Sans CJK JP"] },
+///     ),
+///     standards: (button_text_alignment: Begin),
+///     classes: {
+///         "danger": (colors: { "active": (209, 52, 56, 1.0) }),
+///     },
+/// )
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ThemeSpec {
+    /// Palette of the underlying flat theme.
+    pub brightness: flat::Brightness,
+    /// Colors by name (see [`colors`](colors)), as `(r, g, b, alpha)`.
+    pub colors: HashMap<String, (u8, u8, u8, f32)>,
+    /// Vertical gradients by color name, as stops of `(offset, color)`, which fill controls in place of the flat color.
+    ///
+    /// The flat color should still be given in `colors` for everything else that uses it (e.g. borders).
+    pub gradients: HashMap<String, Vec<(f64, (u8, u8, u8, f32))>>,
+    /// Metrics by name (see [`metrics`](metrics)), for each painter by name (see [`painters`](painters)).
+    pub metrics: HashMap<String, HashMap<String, f32>>,
+    pub fonts: FontSpec,
+    pub standards: StandardsSpec,
+    /// Colors and metrics for widgets with a [style class](crate::ui::Common::add_class), by class name.
+    pub classes: HashMap<String, ClassSpec>,
+}
+
+/// Colors and metrics of a style class within a [`ThemeSpec`](ThemeSpec), which take precedence over those of the theme.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ClassSpec {
+    pub colors: HashMap<String, (u8, u8, u8, f32)>,
+    pub metrics: HashMap<String, HashMap<String, f32>>,
+}
+
+/// Fonts of a [`ThemeSpec`](ThemeSpec), which are looked up by name among the font files, then among the system fonts.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct FontSpec {
+    pub regular: Option<String>,
+    pub bold: Option<String>,
+    pub size: Option<f32>,
+    /// Paths of font files (relative to the working directory) to [register](Theme::register_font), by name.
+    pub files: HashMap<String, String>,
+    /// [Fallback chains](Theme::set_font_fallback) of font names, by script.
+    pub fallbacks: HashMap<Script, Vec<String>>,
+}
+
+/// [`Standards`](Standards) of a [`ThemeSpec`](ThemeSpec).
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct StandardsSpec {
+    pub label_size: Option<f32>,
+    pub button_text_alignment: Option<ui::layout::Alignment>,
+}
+
+/// Theme loaded from a [description](ThemeSpec) of its colors, metrics, fonts and standards.
+///
+/// Painting is delegated to the [flat theme](flat::FlatTheme), with the described values taking precedence over its own.
+pub struct FileTheme {
+    base: flat::FlatTheme,
+    style: Style,
+    gradients: HashMap<String, Vec<(f64, gfx::Color)>>,
+    classes: HashMap<String, Style>,
+    standards: StandardsSpec,
+}
+
+#[inline]
+fn color((r, g, b, a): (u8, u8, u8, f32)) -> gfx::Color {
+    gfx::Color::new(r as f32 / 255., g as f32 / 255., b as f32 / 255., a)
+}
+
+#[derive(Default)]
+struct Style {
+    colors: HashMap<String, gfx::Color>,
+    metrics: HashMap<String, Rc<HashMap<String, f32>>>,
+}
+
+impl Style {
+    fn new(
+        colors: HashMap<String, (u8, u8, u8, f32)>,
+        metrics: HashMap<String, HashMap<String, f32>>,
+    ) -> Self {
+        Style {
+            colors: colors
+                .into_iter()
+                .map(|(name, x)| (name, color(x)))
+                .collect(),
+            metrics: metrics
+                .into_iter()
+                .map(|(painter, metrics)| (painter, Rc::new(metrics)))
+                .collect(),
+        }
+    }
+
+    fn painter<T: 'static>(
+        &self,
+        p: &'static str,
+        painter: Box<dyn AnyPainter<T>>,
+    ) -> Box<dyn AnyPainter<T>> {
+        match self.metrics.get(p) {
+            Some(metrics) => Box::new(MetricsPainter {
+                painter,
+                metrics: Rc::clone(metrics),
+            }),
+            None => painter,
+        }
+    }
+}
+
+impl FileTheme {
+    /// Reads and loads a theme from a RON file.
+    pub fn load(
+        display: &mut dyn gfx::GraphicsDisplay,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<Self, ThemeError> {
+        FileTheme::parse(display, &std::fs::read_to_string(path)?)
+    }
+
+    /// Loads a theme from a RON description.
+    pub fn parse(display: &mut dyn gfx::GraphicsDisplay, spec: &str) -> Result<Self, ThemeError> {
+        FileTheme::from_spec(display, ron::de::from_str(spec)?)
+    }
+
+    pub fn from_spec(
+        display: &mut dyn gfx::GraphicsDisplay,
+        spec: ThemeSpec,
+    ) -> Result<Self, ThemeError> {
+        let mut files = HashMap::new();
+        for (name, path) in &spec.fonts.files {
+            files.insert(name.as_str(), flat::load_font_file(display, path)?);
+        }
+
+        let font = |display: &mut dyn gfx::GraphicsDisplay, name: &str| match files.get(name) {
+            Some(font) => Ok(font.clone()),
+            None => flat::load_font(display, name, &[]),
+        };
+
+        let fonts = match &spec.fonts.regular {
+            Some(regular) => Some(flat::Fonts::new(
+                font(display, regular)?,
+                match &spec.fonts.bold {
+                    Some(bold) => Some(font(display, bold)?),
+                    None => None,
+                },
+            )),
+            None => None,
+        };
+
+        let base = flat::FlatTheme::with_brightness(
+            display,
+            fonts,
+            spec.fonts.size.map(flat::FontSizes::new),
+            spec.brightness,
+        )?;
+
+        for (name, loaded) in files {
+            base.register_font(name, loaded);
+        }
+        for (script, chain) in &spec.fonts.fallbacks {
+            let chain: Vec<_> = chain.iter().map(String::as_str).collect();
+            base.set_fallback(display, *script, &chain)?;
+        }
+
+        Ok(FileTheme {
+            base,
+            style: Style::new(spec.colors, spec.metrics),
+            gradients: spec
+                .gradients
+                .into_iter()
+                .map(|(name, stops)| {
+                    let stops = stops
+                        .into_iter()
+                        .map(|(offset, x)| (offset, color(x)))
+                        .collect();
+                    (name, stops)
+                })
+                .collect(),
+            classes: spec
+                .classes
+                .into_iter()
+                .map(|(name, class)| (name, Style::new(class.colors, class.metrics)))
+                .collect(),
+            standards: spec.standards,
+        })
+    }
+}
+
+impl<T: 'static> Theme<T> for FileTheme {
+    fn painter(&self, p: &'static str) -> Box<dyn AnyPainter<T>> {
+        self.style.painter(p, self.base.painter(p))
+    }
+
+    fn color(&self, c: &'static str) -> gfx::Color {
+        match self.style.colors.get(c) {
+            Some(color) => *color,
+            None => Theme::<T>::color(&self.base, c),
+        }
+    }
+
+    fn style_color(&self, c: &'static str, bounds: gfx::Rect) -> gfx::StyleColor {
+        match self.gradients.get(c) {
+            Some(stops) => draw::vertical_gradient(bounds, stops.clone()),
+            None => gfx::StyleColor::Color(Theme::<T>::color(self, c)),
+        }
+    }
+
+    fn class_color(&self, class: &str, c: &'static str) -> Option<gfx::Color> {
+        self.classes.get(class)?.colors.get(c).copied()
+    }
+
+    fn class_painter(
+        &self,
+        class: &str,
+        p: &'static str,
+        painter: Box<dyn AnyPainter<T>>,
+    ) -> Box<dyn AnyPainter<T>> {
+        match self.classes.get(class) {
+            Some(style) => style.painter(p, painter),
+            None => painter,
+        }
+    }
+
+    fn shadow(
+        &self,
+        out: &mut gfx::DisplayListBuilder,
+        rect: gfx::Rect,
+        radii: [f32; 4],
+        elevation: f32,
+    ) {
+        draw::soft_shadow(
+            out,
+            rect,
+            radii,
+            Theme::<T>::color(self, colors::SHADOW),
+            elevation,
+        );
+    }
+
+    #[inline]
+    fn register_font(
+        &self,
+        display: &mut dyn gfx::GraphicsDisplay,
+        name: &str,
+        data: Vec<u8>,
+    ) -> Result<(), ThemeError> {
+        Theme::<T>::register_font(&self.base, display, name, data)
+    }
+
+    #[inline]
+    fn set_font_fallback(
+        &self,
+        display: &mut dyn gfx::GraphicsDisplay,
+        script: Script,
+        fonts: &[&str],
+    ) -> Result<(), ThemeError> {
+        self.base.set_fallback(display, script, fonts)
+    }
+
+    fn standards(&self) -> Standards {
+        let base = Theme::<T>::standards(&self.base);
+        Standards {
+            label_size: self.standards.label_size.unwrap_or(base.label_size),
+            button_text_alignment: self
+                .standards
+                .button_text_alignment
+                .unwrap_or(base.button_text_alignment),
+        }
+    }
+}
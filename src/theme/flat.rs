@@ -1,17 +1,75 @@
 use {
     crate::{kit, prelude::*, theme::*, ui},
     reclutch::display as gfx,
-    std::rc::Rc,
+    std::{cell::RefCell, collections::HashMap, rc::Rc},
 };
 
 #[inline]
-fn rgba(r: u8, g: u8, b: u8, a: f32) -> gfx::Color {
+pub(crate) fn rgba(r: u8, g: u8, b: u8, a: f32) -> gfx::Color {
     gfx::Color::new(r as f32 / 255., g as f32 / 255., b as f32 / 255., a)
 }
 
-fn with_alpha(mut c: gfx::Color, a: f32) -> gfx::Color {
-    c.alpha = a;
-    c
+pub(crate) fn mix(a: gfx::Color, b: gfx::Color, t: f32) -> gfx::Color {
+    gfx::Color::new(
+        a.red + (b.red - a.red) * t,
+        a.green + (b.green - a.green) * t,
+        a.blue + (b.blue - a.blue) * t,
+        a.alpha,
+    )
+}
+
+/// Shades a fill for hover and press feedback; towards the foreground while hovered, and towards the background while pressed.
+///
+/// Thus in the dark palette controls lighten on hover and darken on press, and vice versa in the light palette.
+pub(crate) fn interaction_fill<T: 'static>(
+    color: gfx::Color,
+    interaction: kit::Interaction,
+    aux: &ui::Aux<T>,
+) -> gfx::Color {
+    if interaction.pressed {
+        mix(color, aux.color(colors::BACKGROUND), 0.3)
+    } else if interaction.hovered {
+        mix(color, aux.color(colors::FOREGROUND), 0.1)
+    } else {
+        color
+    }
+}
+
+pub(crate) const FADE_DURATION: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Fades a fill towards `color` (such as when the interaction changes), returning the color to paint with.
+///
+/// The animator is created upon first use so that the initial color doesn't fade in.
+pub(crate) fn fade<T: 'static>(
+    animator: &mut Option<Animator<gfx::Color>>,
+    color: gfx::Color,
+    common: &ui::CommonRef,
+    aux: &ui::Aux<T>,
+) -> gfx::Color {
+    animator
+        .get_or_insert_with(|| Animator::new(color, FADE_DURATION))
+        .animate(color, common, aux)
+}
+
+/// Shades a control fill for its [interaction](interaction_fill), fading flat colors between states.
+///
+/// Gradients are shaded stop by stop without fading.
+fn control_fill<T: 'static>(
+    animator: &mut Option<Animator<gfx::Color>>,
+    fill: gfx::StyleColor,
+    interaction: kit::Interaction,
+    common: &ui::CommonRef,
+    aux: &ui::Aux<T>,
+) -> gfx::StyleColor {
+    match fill {
+        gfx::StyleColor::Color(color) => gfx::StyleColor::Color(fade(
+            animator,
+            interaction_fill(color, interaction, aux),
+            common,
+            aux,
+        )),
+        fill => draw::map_style_color(fill, |color| interaction_fill(color, interaction, aux)),
+    }
 }
 
 const CORNER_RADIUS: f32 = 5.;
@@ -25,6 +83,43 @@ pub type FontRef = (gfx::ResourceReference, gfx::FontInfo);
 #[derive(Clone)]
 pub struct Fonts {
     ui_regular: FontRef,
+    ui_bold: Option<FontRef>,
+    registered: HashMap<String, FontRef>,
+    fallbacks: HashMap<Script, FontRef>,
+}
+
+impl Fonts {
+    /// Creates a font set; without a bold font, bold text is drawn with the regular font.
+    pub fn new(ui_regular: FontRef, ui_bold: impl Into<Option<FontRef>>) -> Self {
+        Fonts {
+            ui_regular,
+            ui_bold: ui_bold.into(),
+            registered: HashMap::new(),
+            fallbacks: HashMap::new(),
+        }
+    }
+
+    /// Returns the font for drawing `text`.
+    ///
+    /// Text containing a script with a fallback font is drawn entirely in the fallback font of the first such character,
+    /// since fonts of other scripts generally cover Latin as well.
+    fn select(&self, text: &str, bold: bool) -> &FontRef {
+        if !self.fallbacks.is_empty() {
+            let fallback = text
+                .chars()
+                .filter_map(Script::of)
+                .find_map(|script| self.fallbacks.get(&script));
+            if let Some(font) = fallback {
+                return font;
+            }
+        }
+
+        if bold {
+            self.ui_bold.as_ref().unwrap_or(&self.ui_regular)
+        } else {
+            &self.ui_regular
+        }
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -32,53 +127,207 @@ pub struct FontSizes {
     ui: f32,
 }
 
+impl FontSizes {
+    #[inline]
+    pub fn new(ui: f32) -> Self {
+        FontSizes { ui }
+    }
+}
+
+/// Finds a system font by name (or the first available of the fallbacks) and loads it into the display.
+pub fn load_font(
+    display: &mut dyn gfx::GraphicsDisplay,
+    name: &str,
+    fallbacks: &[&str],
+) -> Result<FontRef, ThemeError> {
+    let info = gfx::FontInfo::from_name(name, fallbacks, None)?;
+
+    let data = info.data().ok_or(ThemeError::ResourceError(
+        reclutch::error::ResourceError::InvalidData,
+    ))?;
+
+    let reference = display.new_resource(gfx::ResourceDescriptor::Font(
+        gfx::ResourceData::Data(gfx::SharedData::RefCount(std::sync::Arc::new(data))),
+    ))?;
+
+    Ok((reference, info))
+}
+
+/// Loads a font from the data of a font file (e.g. a font bundled with the application) into the display.
+pub fn load_font_data(
+    display: &mut dyn gfx::GraphicsDisplay,
+    data: Vec<u8>,
+) -> Result<FontRef, ThemeError> {
+    let data = std::sync::Arc::new(data);
+    let info = gfx::FontInfo::from_data(std::sync::Arc::clone(&data), 0)?;
+
+    let reference = display.new_resource(gfx::ResourceDescriptor::Font(
+        gfx::ResourceData::Data(gfx::SharedData::RefCount(data)),
+    ))?;
+
+    Ok((reference, info))
+}
+
+/// Reads a font file and loads it into the display.
+#[inline]
+pub fn load_font_file(
+    display: &mut dyn gfx::GraphicsDisplay,
+    path: impl AsRef<std::path::Path>,
+) -> Result<FontRef, ThemeError> {
+    load_font_data(display, std::fs::read(path)?)
+}
+
+/// Overall brightness of the color palette of a [`FlatTheme`](FlatTheme).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spec", derive(serde::Deserialize))]
+pub enum Brightness {
+    /// Light text on dark backgrounds.
+    Dark,
+    /// Dark text on light backgrounds.
+    Light,
+}
+
+impl Default for Brightness {
+    #[inline]
+    fn default() -> Self {
+        Brightness::Dark
+    }
+}
+
 struct Inner {
-    fonts: Fonts,
+    fonts: RefCell<Fonts>,
     font_sizes: FontSizes,
+    brightness: Brightness,
+}
+
+impl Inner {
+    /// Returns the font for drawing `text` (see [`Fonts::select`](Fonts::select)).
+    fn font(&self, text: &str, bold: bool) -> FontRef {
+        self.fonts.borrow().select(text, bold).clone()
+    }
+
+    fn ui_text(&self, text: &str, color: gfx::Color) -> gfx::TextDisplayItem {
+        let font = self.font(text, false);
+        gfx::TextDisplayItem {
+            text: text.to_string().into(),
+            font: font.0,
+            font_info: font.1,
+            size: self.font_sizes.ui,
+            bottom_left: Default::default(),
+            color: gfx::StyleColor::Color(color),
+        }
+    }
 }
 
 pub struct FlatTheme(Rc<Inner>);
 
 impl FlatTheme {
+    /// Creates the theme with its dark palette.
+    #[inline]
     pub fn new(
         display: &mut dyn gfx::GraphicsDisplay,
         fonts: Option<Fonts>,
         font_sizes: Option<FontSizes>,
+    ) -> Result<Self, ThemeError> {
+        FlatTheme::with_brightness(display, fonts, font_sizes, Brightness::Dark)
+    }
+
+    /// Creates the theme with its light palette.
+    #[inline]
+    pub fn new_light(
+        display: &mut dyn gfx::GraphicsDisplay,
+        fonts: Option<Fonts>,
+        font_sizes: Option<FontSizes>,
+    ) -> Result<Self, ThemeError> {
+        FlatTheme::with_brightness(display, fonts, font_sizes, Brightness::Light)
+    }
+
+    pub fn with_brightness(
+        display: &mut dyn gfx::GraphicsDisplay,
+        fonts: Option<Fonts>,
+        font_sizes: Option<FontSizes>,
+        brightness: Brightness,
     ) -> Result<Self, ThemeError> {
         let fonts = if let Some(fonts) = fonts {
             fonts
         } else {
             Fonts {
-                ui_regular: {
-                    let info = gfx::FontInfo::from_name(
-                        "Segoe UI", // Windows
-                        &[
-                            "SF Display",      // MacOS
-                            "Helvetica",       // MacOS
-                            "Lucida Grande",   // MacOS
-                            "Noto Sans",       // Linux
-                            "Liberation Sans", // Linux
-                            "Cantarell",       // Linux
-                        ],
-                        None,
-                    )?;
+                ui_regular: load_font(
+                    display,
+                    "Segoe UI", // Windows
+                    &[
+                        "SF Display",      // MacOS
+                        "Helvetica",       // MacOS
+                        "Lucida Grande",   // MacOS
+                        "Noto Sans",       // Linux
+                        "Liberation Sans", // Linux
+                        "Cantarell",       // Linux
+                    ],
+                )?,
+                ui_bold: None,
+                registered: HashMap::new(),
+                fallbacks: HashMap::new(),
+            }
+        };
 
-                    let reference = display.new_resource(gfx::ResourceDescriptor::Font(
-                        gfx::ResourceData::Data(gfx::SharedData::RefCount(std::sync::Arc::new(
-                            info.data().ok_or(ThemeError::ResourceError(
-                                reclutch::error::ResourceError::InvalidData,
-                            ))?,
-                        ))),
-                    ))?;
+        let font_sizes = font_sizes.unwrap_or_else(|| FontSizes::new(14.0));
 
-                    (reference, info)
-                },
+        Ok(FlatTheme(Rc::new(Inner {
+            fonts: RefCell::new(fonts),
+            font_sizes,
+            brightness,
+        })))
+    }
+
+    #[inline]
+    pub fn brightness(&self) -> Brightness {
+        self.0.brightness
+    }
+
+    /// Registers a loaded font (see [`load_font_data`](load_font_data) and [`load_font_file`](load_font_file)) under `name`,
+    /// replacing any font already registered under that name.
+    pub fn register_font(&self, name: impl ToString, font: FontRef) {
+        self.0
+            .fonts
+            .borrow_mut()
+            .registered
+            .insert(name.to_string(), font);
+    }
+
+    /// Returns the font registered under `name`.
+    pub fn registered_font(&self, name: &str) -> Option<FontRef> {
+        self.0.fonts.borrow().registered.get(name).cloned()
+    }
+
+    /// Sets the fonts, in order of preference, for drawing text of `script`.
+    ///
+    /// Each font is looked up among the [registered](FlatTheme::register_font) fonts, then among the system fonts.
+    /// If none of them are available, the error of the last is returned and the previous fallback is kept.
+    /// An empty chain removes the fallback.
+    pub fn set_fallback(
+        &self,
+        display: &mut dyn gfx::GraphicsDisplay,
+        script: Script,
+        fonts: &[&str],
+    ) -> Result<(), ThemeError> {
+        let mut result = Ok(None);
+        for name in fonts {
+            result = match self.registered_font(name) {
+                Some(font) => Ok(Some(font)),
+                None => load_font(display, name, &[]).map(Some),
+            };
+            if let Ok(Some(_)) = result {
+                break;
             }
-        };
+        }
 
-        let font_sizes = font_sizes.unwrap_or_else(|| FontSizes { ui: 14.0 });
+        let mut registry = self.0.fonts.borrow_mut();
+        match result? {
+            Some(font) => registry.fallbacks.insert(script, font),
+            None => registry.fallbacks.remove(&script),
+        };
 
-        Ok(FlatTheme(Rc::new(Inner { fonts, font_sizes })))
+        Ok(())
     }
 }
 
@@ -87,20 +336,24 @@ impl<T: 'static> Theme<T> for FlatTheme {
         match p {
             painters::BUTTON => Box::new(ButtonPainter {
                 _theme: Rc::clone(&self.0),
+                fill: None,
             }),
             painters::LABEL => Box::new(LabelPainter {
                 theme: Rc::clone(&self.0),
             }),
             painters::TEXT_BOX => Box::new(TextBoxPainter {
                 theme: Rc::clone(&self.0),
-                count: 0,
-                last_cur: std::usize::MAX,
+            }),
+            painters::TEXT_INPUT => Box::new(TextInputPainter {
+                theme: Rc::clone(&self.0),
             }),
             painters::CHECK_MARK_BOX => Box::new(CheckMarkBoxPainter {
                 _theme: Rc::clone(&self.0),
+                fill: None,
             }),
             painters::COMBO_BOX => Box::new(ComboBoxPainter {
                 _theme: Rc::clone(&self.0),
+                fill: None,
             }),
             painters::COMBO_LIST => Box::new(ComboListPainter {
                 _theme: Rc::clone(&self.0),
@@ -108,19 +361,104 @@ impl<T: 'static> Theme<T> for FlatTheme {
             painters::COMBO_LIST_ITEM => Box::new(ComboListItemPainter {
                 _theme: Rc::clone(&self.0),
             }),
-            _ => unimplemented!(),
+            painters::FILE_BROWSER => Box::new(FileBrowserPainter {
+                _theme: Rc::clone(&self.0),
+            }),
+            painters::FILE_BROWSER_ITEM => Box::new(FileBrowserItemPainter {
+                _theme: Rc::clone(&self.0),
+            }),
+            painters::GAUGE => Box::new(GaugePainter {
+                _theme: Rc::clone(&self.0),
+            }),
+            painters::CALENDAR => Box::new(CalendarPainter {
+                _theme: Rc::clone(&self.0),
+            }),
+            painters::CALENDAR_DAY => Box::new(CalendarDayPainter {
+                _theme: Rc::clone(&self.0),
+            }),
+            painters::TOOLTIP => Box::new(TooltipPainter {
+                theme: Rc::clone(&self.0),
+            }),
+            painters::STATUS_INDICATOR => Box::new(StatusIndicatorPainter {
+                theme: Rc::clone(&self.0),
+            }),
+            painters::RANGE_SLIDER => Box::new(RangeSliderPainter {
+                _theme: Rc::clone(&self.0),
+            }),
+            painters::SCROLL_BAR => Box::new(ScrollBarPainter {
+                _theme: Rc::clone(&self.0),
+            }),
+            _ => Box::new(FallbackPainter(p)),
         }
     }
 
+    fn shadow(
+        &self,
+        out: &mut gfx::DisplayListBuilder,
+        rect: gfx::Rect,
+        radii: [f32; 4],
+        elevation: f32,
+    ) {
+        draw::soft_shadow(
+            out,
+            rect,
+            radii,
+            Theme::<T>::color(self, colors::SHADOW),
+            elevation,
+        );
+    }
+
+    fn register_font(
+        &self,
+        display: &mut dyn gfx::GraphicsDisplay,
+        name: &str,
+        data: Vec<u8>,
+    ) -> Result<(), ThemeError> {
+        self.register_font(name, load_font_data(display, data)?);
+        Ok(())
+    }
+
+    #[inline]
+    fn set_font_fallback(
+        &self,
+        display: &mut dyn gfx::GraphicsDisplay,
+        script: Script,
+        fonts: &[&str],
+    ) -> Result<(), ThemeError> {
+        self.set_fallback(display, script, fonts)
+    }
+
     fn color(&self, c: &'static str) -> gfx::Color {
-        match c {
-            colors::FOREGROUND => rgba(180, 180, 180, 1.0),
-            colors::BACKGROUND => rgba(38, 38, 38, 1.0),
-            colors::WEAK_FOREGROUND => rgba(109, 109, 109, 1.0),
-            colors::STRONG_BACKGROUND => rgba(58, 58, 58, 1.0),
-            colors::TEXT_CONTROL => rgba(26, 26, 26, 1.0),
-            colors::ACTIVE => rgba(25, 78, 197, 1.0),
-            _ => unimplemented!(),
+        match self.0.brightness {
+            Brightness::Dark => match c {
+                colors::FOREGROUND => rgba(180, 180, 180, 1.0),
+                colors::BACKGROUND => rgba(38, 38, 38, 1.0),
+                colors::WEAK_FOREGROUND => rgba(109, 109, 109, 1.0),
+                colors::STRONG_BACKGROUND => rgba(58, 58, 58, 1.0),
+                colors::TEXT_CONTROL => rgba(26, 26, 26, 1.0),
+                colors::ACTIVE => rgba(25, 78, 197, 1.0),
+                colors::ERROR => rgba(209, 52, 56, 1.0),
+                colors::DISABLED_FOREGROUND => rgba(82, 82, 82, 1.0),
+                colors::DISABLED_BACKGROUND => rgba(46, 46, 46, 1.0),
+                colors::SHADOW => rgba(0, 0, 0, 0.45),
+                colors::SELECTION => rgba(25, 78, 197, 0.5),
+                // unknown colors (e.g. of third-party widgets) are treated as foreground.
+                _ => rgba(180, 180, 180, 1.0),
+            },
+            Brightness::Light => match c {
+                colors::FOREGROUND => rgba(45, 45, 45, 1.0),
+                colors::BACKGROUND => rgba(240, 240, 240, 1.0),
+                colors::WEAK_FOREGROUND => rgba(140, 140, 140, 1.0),
+                colors::STRONG_BACKGROUND => rgba(215, 215, 215, 1.0),
+                colors::TEXT_CONTROL => rgba(255, 255, 255, 1.0),
+                colors::ACTIVE => rgba(46, 108, 230, 1.0),
+                colors::ERROR => rgba(196, 43, 28, 1.0),
+                colors::DISABLED_FOREGROUND => rgba(170, 170, 170, 1.0),
+                colors::DISABLED_BACKGROUND => rgba(228, 228, 228, 1.0),
+                colors::SHADOW => rgba(0, 0, 0, 0.25),
+                colors::SELECTION => rgba(46, 108, 230, 0.35),
+                _ => rgba(45, 45, 45, 1.0),
+            },
         }
     }
 
@@ -134,6 +472,7 @@ impl<T: 'static> Theme<T> for FlatTheme {
 
 struct ButtonPainter {
     _theme: Rc<Inner>,
+    fill: Option<Animator<gfx::Color>>,
 }
 
 impl<T: 'static> TypedPainter<T> for ButtonPainter {
@@ -146,15 +485,43 @@ impl<T: 'static> TypedPainter<T> for ButtonPainter {
     ) -> Vec<gfx::DisplayCommand> {
         let mut out = gfx::DisplayListBuilder::new();
 
+        let fill = aux.style_color(
+            if obj.toggled() {
+                colors::ACTIVE
+            } else {
+                colors::STRONG_BACKGROUND
+            },
+            obj.bounds(),
+        );
+        let fill = control_fill(&mut self.fill, fill, obj.interaction(), obj.common(), aux);
+
         out.push_round_rectangle(
             obj.bounds(),
             CORNER_RADII,
-            gfx::GraphicsDisplayPaint::Fill(gfx::StyleColor::Color(
-                aux.theme.color(colors::STRONG_BACKGROUND),
-            )),
+            gfx::GraphicsDisplayPaint::Fill(fill),
             None,
         );
 
+        if aux.has_focus(obj.common()) {
+            draw::focus_ring(
+                &mut out,
+                obj.bounds(),
+                CORNER_RADII,
+                draw::with_alpha(aux.color(colors::ACTIVE), 0.5),
+                2.,
+                1.,
+            );
+        }
+
+        if let (Some(icon), Some(rect)) = (obj.icon(), obj.icon_bounds()) {
+            out.push_path(
+                icon.path(rect),
+                false,
+                draw::stroke(aux.color(colors::FOREGROUND), 1.5),
+                None,
+            );
+        }
+
         out.build()
     }
 
@@ -167,6 +534,8 @@ impl<T: 'static> TypedPainter<T> for ButtonPainter {
         match metric {
             metrics::PADDING_X => Some(30.),
             metrics::PADDING_Y => Some(3.),
+            metrics::ICON_SIZE => Some(14.),
+            metrics::ICON_SPACING => Some(6.),
             _ => None,
         }
     }
@@ -177,23 +546,259 @@ struct LabelPainter {
 }
 
 impl LabelPainter {
-    fn text_bounds(&self, text: gfx::DisplayText, size: f32, max_width: Option<f32>) -> gfx::Size {
-        let item = gfx::TextDisplayItem {
+    fn text_item(
+        &self,
+        text: gfx::DisplayText,
+        size: f32,
+        color: gfx::Color,
+    ) -> gfx::TextDisplayItem {
+        let font = match &text {
+            gfx::DisplayText::Simple(text) => self.theme.font(text, false),
+            _ => self.theme.font("", false),
+        };
+
+        gfx::TextDisplayItem {
             text,
-            font: self.theme.fonts.ui_regular.0,
-            font_info: self.theme.fonts.ui_regular.1.clone(),
+            font: font.0,
+            font_info: font.1,
             size,
             bottom_left: Default::default(),
-            color: gfx::StyleColor::Color(Default::default()),
+            color: gfx::StyleColor::Color(color),
+        }
+    }
+
+    fn ellipsize(
+        &self,
+        text: gfx::DisplayText,
+        size: f32,
+        max_width: f32,
+        mode: kit::Ellipsis,
+    ) -> gfx::DisplayText {
+        match text {
+            gfx::DisplayText::Simple(text) => kit::ellipsize(&text, mode, max_width, |x| {
+                self.text_item(x.to_string().into(), size, Default::default())
+                    .bounds()
+                    .unwrap()
+                    .size
+                    .width
+            })
+            .into(),
+            // shaped text can't be cut up without reshaping.
+            text => text,
+        }
+    }
+
+    fn span_item(
+        &self,
+        span: &kit::TextSpan,
+        text: &str,
+        size: f32,
+        color: gfx::Color,
+    ) -> gfx::TextDisplayItem {
+        let bold = span.weight.unwrap_or_default() == kit::FontWeight::Bold;
+        let font = self.theme.font(text, bold);
+
+        gfx::TextDisplayItem {
+            text: text.to_string().into(),
+            font: font.0,
+            font_info: font.1,
+            size: span.size.unwrap_or(size),
+            bottom_left: Default::default(),
+            color: gfx::StyleColor::Color(span.color.unwrap_or(color)),
+        }
+    }
+
+    /// Lays out the styled spans of a label word by word, sharing a baseline on each line.
+    fn layout_spans<T: 'static>(&self, obj: &kit::Label<T>) -> (Vec<gfx::TextDisplayItem>, f32) {
+        let origin = obj.bounds().origin;
+
+        let mut lines: Vec<(Vec<gfx::TextDisplayItem>, f32)> = vec![(Vec::new(), 0.)];
+        for span in obj.spans() {
+            for (i, paragraph) in span.text.split('\n').enumerate() {
+                if i > 0 {
+                    lines.push((Vec::new(), 0.));
+                }
+
+                for word in paragraph.split_inclusive(' ') {
+                    let mut item = self.span_item(span, word, obj.size(), obj.color());
+                    let width = item.bounds().unwrap().size.width;
+
+                    let (_, x) = lines.last().unwrap();
+                    let overflows = obj
+                        .max_width()
+                        .map_or(false, |max_width| *x > 0. && x + width > max_width);
+                    if overflows {
+                        lines.push((Vec::new(), 0.));
+                    }
+
+                    let (line, x) = lines.last_mut().unwrap();
+                    item.bottom_left.x = origin.x + *x;
+                    *x += width;
+                    line.push(item);
+                }
+            }
+        }
+
+        let empty = self
+            .text_item(" ".to_string().into(), obj.size(), Default::default())
+            .bounds()
+            .unwrap();
+        let container = obj
+            .max_width()
+            .unwrap_or_else(|| lines.iter().map(|(_, x)| *x).fold(0., f32::max));
+
+        let mut items = Vec::new();
+        let mut y = origin.y;
+        for (line, width) in lines {
+            // items are still on a baseline of 0, so their bounds give the ascent (above) and descent (below).
+            let (ascent, descent) = if line.is_empty() {
+                (-empty.min_y(), empty.max_y())
+            } else {
+                line.iter()
+                    .map(|x| x.bounds().unwrap())
+                    .fold((0., 0.), |(a, d), b| {
+                        (f32::max(a, -b.min_y()), f32::max(d, b.max_y()))
+                    })
+            };
+
+            let offset = obj.align().offset(container, width);
+            for mut item in line {
+                item.bottom_left.x += offset;
+                item.bottom_left.y = y + ascent;
+                items.push(item);
+            }
+            y += ascent + descent;
+        }
+
+        (items, y - origin.y)
+    }
+
+    /// Lays out the text of a label, line by line, returning the text items and the total height.
+    fn layout<T: 'static>(&self, obj: &kit::Label<T>) -> (Vec<gfx::TextDisplayItem>, f32) {
+        if !obj.spans().is_empty() {
+            return self.layout_spans(obj);
+        }
+
+        let origin = obj.bounds().origin;
+        let line_height = self
+            .text_item(" ".to_string().into(), obj.size(), Default::default())
+            .bounds()
+            .unwrap()
+            .size
+            .height;
+
+        let lines: Vec<gfx::DisplayText> = match obj.text() {
+            gfx::DisplayText::Simple(text) => {
+                text.split('\n').map(|x| x.to_string().into()).collect()
+            }
+            text => vec![text.clone()],
         };
 
-        if let Some(max_width) = max_width {
-            let height = item.bounds().unwrap().size.height;
-            let items = item.linebreak(max_width, height, true).unwrap();
-            gfx::Size::new(max_width, height * items.len() as f32)
-        } else {
-            item.bounds().unwrap().size
+        let mut items = Vec::new();
+        // whether each item is the last line of its paragraph, which justified text leaves as is.
+        let mut ends = Vec::new();
+        let mut y = origin.y;
+        for line in lines {
+            let mut text = self.text_item(line, obj.size(), obj.color());
+            match obj.max_width() {
+                Some(max_width) if obj.ellipsis() != kit::Ellipsis::None => {
+                    text.text = self.ellipsize(text.text, obj.size(), max_width, obj.ellipsis());
+                    text.set_top_left(gfx::Point::new(origin.x, y));
+                    items.push(text);
+                    ends.push(true);
+                    y += line_height;
+                }
+                Some(max_width) => {
+                    text.set_top_left(gfx::Point::new(origin.x, y));
+                    let wrapped = text.linebreak(max_width, line_height, true).unwrap();
+                    y += line_height * wrapped.len().max(1) as f32;
+                    let count = wrapped.len();
+                    ends.extend((1..=count).map(|i| i == count));
+                    items.extend(wrapped);
+                }
+                None => {
+                    text.set_top_left(gfx::Point::new(origin.x, y));
+                    items.push(text);
+                    ends.push(true);
+                    y += line_height;
+                }
+            }
+        }
+
+        if obj.align() == kit::TextAlign::Justify {
+            if let Some(max_width) = obj.max_width() {
+                items = items
+                    .into_iter()
+                    .zip(ends)
+                    .flat_map(|(item, end)| {
+                        if end {
+                            vec![item]
+                        } else {
+                            Self::justify(item, max_width)
+                        }
+                    })
+                    .collect();
+            }
+        } else if obj.align() != kit::TextAlign::Left {
+            let widths: Vec<f32> = items
+                .iter()
+                .map(|x| x.bounds().unwrap().size.width)
+                .collect();
+            let container = obj
+                .max_width()
+                .unwrap_or_else(|| widths.iter().copied().fold(0., f32::max));
+            for (item, width) in items.iter_mut().zip(widths) {
+                item.bottom_left.x += obj.align().offset(container, width);
+            }
+        }
+
+        (items, y - origin.y)
+    }
+
+    /// Lays out the text of a label as drawn, aligned vertically within the label.
+    fn positioned<T: 'static>(&self, obj: &kit::Label<T>) -> Vec<gfx::TextDisplayItem> {
+        let (mut items, height) = self.layout(obj);
+        let offset = obj
+            .vertical_align()
+            .offset(obj.bounds().size.height, height);
+        for item in &mut items {
+            item.bottom_left.y += offset;
+        }
+        items
+    }
+
+    /// Splits a line into its words, spaced out to fill `width`.
+    fn justify(line: gfx::TextDisplayItem, width: f32) -> Vec<gfx::TextDisplayItem> {
+        let text = match &line.text {
+            gfx::DisplayText::Simple(text) => text.clone(),
+            _ => return vec![line],
+        };
+
+        let mut words: Vec<gfx::TextDisplayItem> = text
+            .split_whitespace()
+            .map(|x| {
+                let mut word = line.clone();
+                word.text = x.to_string().into();
+                word
+            })
+            .collect();
+        if words.len() < 2 {
+            return vec![line];
+        }
+
+        let widths: Vec<f32> = words
+            .iter()
+            .map(|x| x.bounds().unwrap().size.width)
+            .collect();
+        let gap = (width - widths.iter().sum::<f32>()) / (words.len() - 1) as f32;
+
+        let mut x = line.bottom_left.x;
+        for (word, width) in words.iter_mut().zip(widths) {
+            word.bottom_left.x = x;
+            x += width + gap;
         }
+
+        words
     }
 }
 
@@ -207,41 +812,150 @@ impl<T: 'static> TypedPainter<T> for LabelPainter {
     ) -> Vec<gfx::DisplayCommand> {
         let mut out = gfx::DisplayListBuilder::new();
 
-        let mut text = gfx::TextDisplayItem {
-            text: obj.text().clone(),
-            font: self.theme.fonts.ui_regular.0,
-            font_info: self.theme.fonts.ui_regular.1.clone(),
-            size: obj.size(),
-            bottom_left: Default::default(),
-            color: gfx::StyleColor::Color(obj.color()),
+        for item in self.positioned(obj) {
+            out.push_text(item, None);
+        }
+
+        out.build()
+    }
+
+    fn size_hint(&mut self, obj: &mut kit::Label<T>) -> gfx::Size {
+        let (items, height) = self.layout(obj);
+        let width = match obj.max_width() {
+            Some(max_width) if obj.ellipsis() == kit::Ellipsis::None => max_width,
+            _ => {
+                let left = obj.bounds().origin.x;
+                items
+                    .iter()
+                    .map(|x| x.bounds().unwrap().max_x() - left)
+                    .fold(0., f32::max)
+            }
         };
+        gfx::Size::new(width, height)
+    }
 
-        text.set_top_left(obj.bounds().origin);
+    fn metrics(&self, obj: &kit::Label<T>, metric: &'static str) -> Option<f32> {
+        match metric {
+            // with a baseline of 0, the top of the text is at minus the ascent.
+            metrics::BASELINE => Some(
+                -self
+                    .text_item(" ".to_string().into(), obj.size(), Default::default())
+                    .bounds()
+                    .unwrap()
+                    .min_y(),
+            ),
+            _ => None,
+        }
+    }
 
-        let items = if let Some(max_width) = obj.max_width() {
-            let height = text.bounds().unwrap().size.height;
-            text.linebreak(max_width, height, true).unwrap()
-        } else {
-            vec![text]
+    fn hit_test(&self, obj: &kit::Label<T>, point: gfx::Point) -> Option<usize> {
+        let text = match obj.text() {
+            gfx::DisplayText::Simple(text) => text,
+            _ => return None,
         };
 
-        for item in items {
-            out.push_text(item, None);
+        // each item shows a piece of the text in order, so its position within the text is found by searching onwards.
+        // pieces which don't appear in the text (i.e. ellipsized lines) are skipped.
+        let mut from = 0;
+        for item in self.positioned(obj) {
+            let piece = match &item.text {
+                gfx::DisplayText::Simple(piece) => piece,
+                _ => continue,
+            };
+            let start = match text[from..].find(piece.as_str()) {
+                Some(start) => from + start,
+                None => continue,
+            };
+            from = start + piece.len();
+
+            let bounds = item.bounds().unwrap();
+            if !bounds.contains(point) {
+                continue;
+            }
+
+            let left = |i: usize| bounds.origin.x + item.limited_bounds(i).unwrap().size.width;
+            if let Some((i, _)) = piece
+                .char_indices()
+                .find(|&(i, c)| point.x >= left(i) && point.x < left(i + c.len_utf8()))
+            {
+                return Some(start + i);
+            }
         }
 
-        out.build()
+        None
     }
 
     #[inline]
-    fn size_hint(&mut self, obj: &mut kit::Label<T>) -> gfx::Size {
-        self.text_bounds(obj.text().clone(), obj.size(), obj.max_width())
+    fn shared(&self) -> bool {
+        true
     }
 }
 
 struct TextBoxPainter {
     theme: Rc<Inner>,
-    count: usize,
-    last_cur: usize,
+}
+
+impl TextBoxPainter {
+    fn text_item(&self, text: &str) -> gfx::TextDisplayItem {
+        let font = self.theme.font(text, false);
+        gfx::TextDisplayItem {
+            text: text.into(),
+            font: font.0,
+            font_info: font.1,
+            size: self.theme.font_sizes.ui,
+            bottom_left: Default::default(),
+            color: gfx::StyleColor::Color(Default::default()),
+        }
+    }
+
+    #[inline]
+    fn line_height(&self) -> f32 {
+        self.text_item(" ").bounds().unwrap().size.height
+    }
+
+    /// Returns each line of `text` along with the index it starts at.
+    fn lines(text: &str) -> Vec<(usize, &str)> {
+        let mut start = 0;
+        text.split('\n')
+            .map(|line| {
+                let out = (start, line);
+                start += line.len() + 1;
+                out
+            })
+            .collect()
+    }
+
+    /// Returns the horizontal offset of `line` given the alignment of the text box.
+    fn line_offset<T: 'static>(&self, obj: &kit::TextBox<T>, line: &str) -> f32 {
+        let width = self.text_item(line).bounds().unwrap().size.width;
+        obj.align().offset(obj.bounds().size.width, width)
+    }
+
+    /// Returns the position of the caret at `index` within `text`, relative to the top-left of the text box.
+    fn caret_offset<T: 'static>(
+        &self,
+        obj: &kit::TextBox<T>,
+        text: &str,
+        index: usize,
+    ) -> gfx::Vector {
+        let start = text[..index].rfind('\n').map(|x| x + 1).unwrap_or(0);
+        let end = text[index..]
+            .find('\n')
+            .map(|x| index + x)
+            .unwrap_or(text.len());
+        let line = text[..start].matches('\n').count();
+        let x = self
+            .text_item(&text[start..end])
+            .limited_bounds(index - start)
+            .unwrap()
+            .size
+            .width
+            .round();
+        gfx::Vector::new(
+            x + self.line_offset(obj, &text[start..end]),
+            line as f32 * self.line_height(),
+        )
+    }
 }
 
 impl<T: 'static> TypedPainter<T> for TextBoxPainter {
@@ -252,51 +966,69 @@ impl<T: 'static> TypedPainter<T> for TextBoxPainter {
         obj: &mut kit::TextBox<T>,
         aux: &mut ui::Aux<T>,
     ) -> Vec<gfx::DisplayCommand> {
-        if !aux.has_focus(obj.common()) {
-            return Default::default();
-        }
-
-        self.count += 1;
+        let mut out = gfx::DisplayListBuilder::new();
 
-        if self.last_cur != obj.cursor() {
-            self.count = 0;
+        if !obj.validation().is_valid() {
+            draw::focus_ring(
+                &mut out,
+                obj.bounds(),
+                CORNER_RADII,
+                aux.color(colors::ERROR),
+                1.,
+                2.,
+            );
         }
 
-        self.last_cur = obj.cursor();
+        if !aux.has_focus(obj.common()) {
+            return out.build();
+        }
 
-        if self.count > 60 {
-            self.count = 0;
-            return Default::default();
-        } else if self.count > 30 {
-            return Default::default();
+        let text = obj.composed_text();
+        let pos = obj.bounds().origin;
+        let line_height = self.line_height();
+        let composing = !obj.composition().is_empty();
+
+        if let (Some((start, end)), false) = (obj.selection(), composing) {
+            // highlight the selected part of each line separately.
+            for (line_start, line) in Self::lines(&text) {
+                let line_end = line_start + line.len();
+                if line_end < start || line_start > end {
+                    continue;
+                }
+
+                let a = self.caret_offset(obj, &text, start.max(line_start));
+                let b = self.caret_offset(obj, &text, end.min(line_end));
+                aux.theme.selection(
+                    &mut out,
+                    gfx::Rect::new(pos + a, gfx::Size::new(b.x - a.x, line_height)),
+                    draw::radii(0.),
+                    true,
+                );
+            }
         }
 
-        let mut out = gfx::DisplayListBuilder::new();
+        if composing {
+            // underline the uncommitted input method text.
+            let a = pos + self.caret_offset(obj, &text, obj.cursor());
+            let b = pos + self.caret_offset(obj, &text, obj.cursor() + obj.composition().len());
+            out.push_line(
+                gfx::Point::new(a.x, a.y + line_height),
+                gfx::Point::new(b.x, b.y + line_height),
+                gfx::GraphicsDisplayStroke {
+                    thickness: 1.,
+                    color: aux.color(colors::FOREGROUND).into(),
+                    ..Default::default()
+                },
+                None,
+            );
+        }
 
-        let cur = gfx::TextDisplayItem {
-            text: obj.text().into(),
-            font: self.theme.fonts.ui_regular.0,
-            font_info: self.theme.fonts.ui_regular.1.clone(),
-            size: self.theme.font_sizes.ui,
-            bottom_left: Default::default(),
-            color: gfx::StyleColor::Color(Default::default()),
+        if !obj.caret_visible() {
+            return out.build();
         }
-        .limited_bounds(obj.cursor())
-        .unwrap()
-        .size
-        .round();
 
-        let pos = obj.bounds().origin;
-        out.push_line(
-            gfx::Point::new(pos.x + cur.width, pos.y),
-            gfx::Point::new(pos.x + cur.width, pos.y + cur.height),
-            gfx::GraphicsDisplayStroke {
-                thickness: 1.,
-                color: aux.theme.color(colors::FOREGROUND).into(),
-                ..Default::default()
-            },
-            None,
-        );
+        let cur = pos + self.caret_offset(obj, &text, obj.cursor() + obj.composition().len());
+        aux.theme.caret(&mut out, cur, line_height);
 
         out.build()
     }
@@ -304,54 +1036,194 @@ impl<T: 'static> TypedPainter<T> for TextBoxPainter {
     fn size_hint(&mut self, _obj: &mut kit::TextBox<T>) -> gfx::Size {
         Default::default()
     }
-}
 
-fn check_mark(r: gfx::Rect) -> gfx::VectorPath {
-    let mut path = gfx::VectorPathBuilder::new();
+    fn hit_test(&self, obj: &kit::TextBox<T>, point: gfx::Point) -> Option<usize> {
+        let local = point - obj.bounds().origin;
+        let lines = Self::lines(obj.text());
+
+        let line = ((local.y / self.line_height()).floor().max(0.) as usize).min(lines.len() - 1);
+        let (start, line) = lines[line];
+        let item = self.text_item(line);
+        let x = local.x - self.line_offset(obj, line);
+
+        // the closest character boundary to the point within the line.
+        line.char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(line.len()))
+            .map(|i| (i, (item.limited_bounds(i).unwrap().size.width - x).abs()))
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(i, _)| start + i)
+    }
 
-    path.move_to(r.origin + gfx::Size::new(r.size.width, 0.));
-    path.line_to(r.origin + gfx::Size::new(r.size.width / 2., r.size.height));
-    path.line_to(r.origin + gfx::Size::new(0., r.size.height / 2.));
+    fn caret_rect(&self, obj: &kit::TextBox<T>, index: usize) -> Option<gfx::Rect> {
+        let text = obj.composed_text();
+        if index > text.len() || !text.is_char_boundary(index) {
+            return None;
+        }
+        Some(gfx::Rect::new(
+            obj.bounds().origin + self.caret_offset(obj, &text, index),
+            gfx::Size::new(1., self.line_height()),
+        ))
+    }
 
-    path.build()
+    #[inline]
+    fn shared(&self) -> bool {
+        true
+    }
 }
 
-struct CheckMarkBoxPainter {
-    _theme: Rc<Inner>,
+struct TextInputPainter {
+    theme: Rc<Inner>,
 }
 
-impl<T: 'static> TypedPainter<T> for CheckMarkBoxPainter {
-    type Object = kit::CheckMarkBox<T>;
+impl TextInputPainter {
+    const PADDING_X: f32 = 12.;
+    const PADDING_Y: f32 = 8.;
+    const ICON_SIZE: f32 = 14.;
+    const ICON_SPACING: f32 = 6.;
+}
 
-    fn paint(
-        &mut self,
-        obj: &mut kit::CheckMarkBox<T>,
-        aux: &mut ui::Aux<T>,
-    ) -> Vec<gfx::DisplayCommand> {
-        let mut out = gfx::DisplayListBuilder::new();
+impl<T: 'static> TypedPainter<T> for TextInputPainter {
+    type Object = kit::TextInput<T>;
 
-        let color = aux.theme.color(if obj.checked() {
-            colors::ACTIVE
-        } else {
-            colors::STRONG_BACKGROUND
-        });
+    fn paint(&mut self, obj: &mut Self::Object, aux: &mut ui::Aux<T>) -> Vec<gfx::DisplayCommand> {
+        let mut out = gfx::DisplayListBuilder::new();
 
         let bounds = obj.bounds();
+        let focused = obj.focused(aux);
 
-        out.push_round_rectangle(
+        draw::bordered_rect(
+            &mut out,
             bounds,
             CORNER_RADII,
-            gfx::GraphicsDisplayPaint::Fill(gfx::StyleColor::Color(color)),
+            aux.color(colors::TEXT_CONTROL),
+            Some((
+                aux.color(if focused {
+                    colors::ACTIVE
+                } else {
+                    colors::STRONG_BACKGROUND
+                }),
+                1.,
+            )),
+        );
+
+        if focused {
+            draw::focus_ring(
+                &mut out,
+                bounds,
+                CORNER_RADII,
+                draw::with_alpha(aux.color(colors::ACTIVE), 0.5),
+                2.,
+                1.,
+            );
+        }
+
+        let icon_rect = |x: f32| {
+            gfx::Rect::new(
+                gfx::Point::new(x, bounds.center().y - Self::ICON_SIZE / 2.),
+                gfx::Size::new(Self::ICON_SIZE, Self::ICON_SIZE),
+            )
+        };
+        let icons = [
+            (obj.leading_icon(), bounds.min_x() + Self::PADDING_X / 2.),
+            (
+                obj.trailing_icon(),
+                bounds.max_x() - Self::PADDING_X / 2. - Self::ICON_SIZE,
+            ),
+        ];
+        for (icon, x) in icons.iter() {
+            if let Some(icon) = icon {
+                out.push_path(
+                    icon.path(icon_rect(*x)),
+                    false,
+                    draw::stroke(aux.color(colors::WEAK_FOREGROUND), 1.5),
+                    None,
+                );
+            }
+        }
+
+        out.build()
+    }
+
+    fn size_hint(&mut self, _obj: &mut Self::Object) -> gfx::Size {
+        let line_height = self
+            .theme
+            .ui_text(" ", Default::default())
+            .bounds()
+            .unwrap()
+            .size
+            .height;
+        gfx::Size::new(150., line_height + Self::PADDING_Y)
+    }
+
+    fn metrics(&self, _obj: &Self::Object, metric: &'static str) -> Option<f32> {
+        match metric {
+            metrics::PADDING_X => Some(Self::PADDING_X),
+            metrics::PADDING_Y => Some(Self::PADDING_Y),
+            metrics::ICON_SIZE => Some(Self::ICON_SIZE),
+            metrics::ICON_SPACING => Some(Self::ICON_SPACING),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn shared(&self) -> bool {
+        true
+    }
+}
+
+struct CheckMarkBoxPainter {
+    _theme: Rc<Inner>,
+    fill: Option<Animator<gfx::Color>>,
+}
+
+impl<T: 'static> TypedPainter<T> for CheckMarkBoxPainter {
+    type Object = kit::CheckMarkBox<T>;
+
+    fn paint(
+        &mut self,
+        obj: &mut kit::CheckMarkBox<T>,
+        aux: &mut ui::Aux<T>,
+    ) -> Vec<gfx::DisplayCommand> {
+        let mut out = gfx::DisplayListBuilder::new();
+
+        let bounds = obj.bounds();
+
+        let fill = aux.style_color(
+            if obj.checked() {
+                colors::ACTIVE
+            } else {
+                colors::STRONG_BACKGROUND
+            },
+            bounds,
+        );
+        let fill = control_fill(&mut self.fill, fill, obj.interaction(), obj.common(), aux);
+
+        out.push_round_rectangle(
+            bounds,
+            CORNER_RADII,
+            gfx::GraphicsDisplayPaint::Fill(fill),
             None,
         );
 
+        if aux.has_focus(obj.common()) {
+            draw::focus_ring(
+                &mut out,
+                bounds,
+                CORNER_RADII,
+                draw::with_alpha(aux.color(colors::ACTIVE), 0.5),
+                2.,
+                1.,
+            );
+        }
+
         if obj.checked() {
             out.push_path(
-                check_mark(bounds.inflate(-4., -4.)),
+                aux.theme.icon(icons::CHECK_MARK, bounds.inflate(-4., -4.)),
                 false,
                 gfx::GraphicsDisplayPaint::Stroke(gfx::GraphicsDisplayStroke {
                     thickness: 2.,
-                    color: aux.theme.color(colors::FOREGROUND).into(),
+                    color: aux.color(colors::FOREGROUND).into(),
                     ..Default::default()
                 }),
                 None,
@@ -374,30 +1246,9 @@ impl<T: 'static> TypedPainter<T> for CheckMarkBoxPainter {
     }
 }
 
-fn up_down_arrows(rect: gfx::Rect) -> [gfx::VectorPath; 2] {
-    let c = rect.center();
-    let v = if rect.size.width > rect.size.height {
-        rect.size.height
-    } else {
-        rect.size.width
-    } / 3.;
-    let d = v / 2.;
-
-    let mut path1 = gfx::VectorPathBuilder::new();
-    path1.move_to(c + gfx::Vector::new(-v, -v + d));
-    path1.line_to(c + gfx::Vector::new(0., 2. * -v + d));
-    path1.line_to(c + gfx::Vector::new(v, -v + d));
-
-    let mut path2 = gfx::VectorPathBuilder::new();
-    path2.move_to(c + gfx::Vector::new(-v, v - d));
-    path2.line_to(c + gfx::Vector::new(0., 2. * v - d));
-    path2.line_to(c + gfx::Vector::new(v, v - d));
-
-    [path1.build(), path2.build()]
-}
-
 struct ComboBoxPainter {
     _theme: Rc<Inner>,
+    fill: Option<Animator<gfx::Color>>,
 }
 
 impl<T: 'static> TypedPainter<T> for ComboBoxPainter {
@@ -417,9 +1268,7 @@ impl<T: 'static> TypedPainter<T> for ComboBoxPainter {
 
         out.push_rectangle(
             bounds,
-            gfx::GraphicsDisplayPaint::Fill(gfx::StyleColor::Color(
-                aux.theme.color(colors::TEXT_CONTROL),
-            )),
+            gfx::GraphicsDisplayPaint::Fill(aux.style_color(colors::TEXT_CONTROL, bounds)),
             None,
         );
 
@@ -427,29 +1276,30 @@ impl<T: 'static> TypedPainter<T> for ComboBoxPainter {
         icon_bg.size.width = 15.;
         icon_bg.origin.x = ui::layout::align_x(icon_bg, bounds, ui::layout::Alignment::End, 0.);
 
+        let icon_fill = aux.style_color(colors::ACTIVE, icon_bg);
         out.push_rectangle(
             icon_bg,
-            gfx::GraphicsDisplayPaint::Fill(gfx::StyleColor::Color(
-                aux.theme.color(colors::ACTIVE),
+            gfx::GraphicsDisplayPaint::Fill(control_fill(
+                &mut self.fill,
+                icon_fill,
+                obj.interaction(),
+                obj.common(),
+                aux,
             )),
             None,
         );
 
-        for v in up_down_arrows(icon_bg.inflate(-1., -1.))
-            .to_vec()
-            .into_iter()
-        {
-            out.push_path(
-                v,
-                false,
-                gfx::GraphicsDisplayPaint::Stroke(gfx::GraphicsDisplayStroke {
-                    thickness: 2.,
-                    color: aux.theme.color(colors::FOREGROUND).into(),
-                    ..Default::default()
-                }),
-                None,
-            );
-        }
+        out.push_path(
+            aux.theme
+                .icon(icons::UP_DOWN_ARROWS, icon_bg.inflate(-1., -1.)),
+            false,
+            gfx::GraphicsDisplayPaint::Stroke(gfx::GraphicsDisplayStroke {
+                thickness: 2.,
+                color: aux.color(colors::FOREGROUND).into(),
+                ..Default::default()
+            }),
+            None,
+        );
 
         out.restore();
 
@@ -482,6 +1332,9 @@ impl<T: 'static> TypedPainter<T> for ComboListPainter {
 
         let bounds = obj.bounds();
 
+        aux.theme
+            .shadow(&mut out, bounds, CORNER_RADII, elevations::POPUP);
+
         out.push_round_rectangle_backdrop(
             bounds,
             CORNER_RADII,
@@ -491,8 +1344,8 @@ impl<T: 'static> TypedPainter<T> for ComboListPainter {
         out.push_round_rectangle(
             bounds,
             CORNER_RADII,
-            gfx::GraphicsDisplayPaint::Fill(gfx::StyleColor::Color(with_alpha(
-                aux.theme.color(colors::TEXT_CONTROL),
+            gfx::GraphicsDisplayPaint::Fill(gfx::StyleColor::Color(draw::with_alpha(
+                aux.color(colors::TEXT_CONTROL),
                 TRANSLUCENCY,
             ))),
             None,
@@ -504,6 +1357,11 @@ impl<T: 'static> TypedPainter<T> for ComboListPainter {
     fn size_hint(&mut self, _obj: &mut Self::Object) -> gfx::Size {
         Default::default()
     }
+
+    #[inline]
+    fn shared(&self) -> bool {
+        true
+    }
 }
 
 struct ComboListItemPainter {
@@ -513,6 +1371,237 @@ struct ComboListItemPainter {
 impl<T: 'static> TypedPainter<T> for ComboListItemPainter {
     type Object = kit::ComboListItem<T>;
 
+    fn paint(&mut self, obj: &mut Self::Object, aux: &mut ui::Aux<T>) -> Vec<gfx::DisplayCommand> {
+        let mut out = gfx::DisplayListBuilder::new();
+
+        if obj.highlighted() {
+            aux.theme
+                .selection(&mut out, obj.bounds(), CORNER_RADII, true);
+        }
+
+        out.build()
+    }
+
+    fn size_hint(&mut self, _obj: &mut Self::Object) -> gfx::Size {
+        Default::default()
+    }
+
+    #[inline]
+    fn shared(&self) -> bool {
+        true
+    }
+}
+
+struct FileBrowserPainter {
+    _theme: Rc<Inner>,
+}
+
+impl<T: 'static> TypedPainter<T> for FileBrowserPainter {
+    type Object = kit::FileBrowser<T>;
+
+    fn paint(&mut self, obj: &mut Self::Object, aux: &mut ui::Aux<T>) -> Vec<gfx::DisplayCommand> {
+        let mut out = gfx::DisplayListBuilder::new();
+
+        out.push_round_rectangle(
+            obj.bounds(),
+            CORNER_RADII,
+            gfx::GraphicsDisplayPaint::Fill(gfx::StyleColor::Color(
+                aux.color(colors::TEXT_CONTROL),
+            )),
+            None,
+        );
+
+        out.build()
+    }
+
+    fn size_hint(&mut self, _obj: &mut Self::Object) -> gfx::Size {
+        Default::default()
+    }
+
+    #[inline]
+    fn shared(&self) -> bool {
+        true
+    }
+}
+
+struct FileBrowserItemPainter {
+    _theme: Rc<Inner>,
+}
+
+impl<T: 'static> TypedPainter<T> for FileBrowserItemPainter {
+    type Object = kit::FileBrowserItem<T>;
+
+    fn paint(&mut self, obj: &mut Self::Object, aux: &mut ui::Aux<T>) -> Vec<gfx::DisplayCommand> {
+        let mut out = gfx::DisplayListBuilder::new();
+
+        let bounds = obj.bounds();
+        let mut icon = gfx::Rect::new(bounds.origin, gfx::Size::new(16., 16.));
+        icon.origin.y = ui::layout::align_y(icon, bounds, ui::layout::Alignment::Middle, 0.);
+
+        let path = match obj.kind() {
+            kit::FileKind::Parent | kit::FileKind::Directory => {
+                aux.theme.icon(icons::FOLDER, icon.inflate(-2., -3.))
+            }
+            kit::FileKind::File => aux.theme.icon(icons::FILE, icon.inflate(-3., -2.)),
+        };
+
+        out.push_path(
+            path,
+            false,
+            gfx::GraphicsDisplayPaint::Stroke(gfx::GraphicsDisplayStroke {
+                thickness: 1.5,
+                color: aux
+                    .theme
+                    .color(match obj.kind() {
+                        kit::FileKind::File => colors::FOREGROUND,
+                        _ => colors::ACTIVE,
+                    })
+                    .into(),
+                ..Default::default()
+            }),
+            None,
+        );
+
+        out.build()
+    }
+
+    fn size_hint(&mut self, _obj: &mut Self::Object) -> gfx::Size {
+        gfx::Size::new(16., 16.)
+    }
+
+    fn metrics(&self, _obj: &Self::Object, metric: &'static str) -> Option<f32> {
+        match metric {
+            metrics::ICON_SIZE => Some(16.),
+            metrics::ICON_SPACING => Some(5.),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn shared(&self) -> bool {
+        true
+    }
+}
+
+const GAUGE_START_ANGLE: f32 = std::f32::consts::PI * 0.75;
+const GAUGE_SWEEP: f32 = std::f32::consts::PI * 1.5;
+
+fn gauge_point(center: gfx::Point, radius: f32, fraction: f32) -> gfx::Point {
+    let angle = GAUGE_START_ANGLE + GAUGE_SWEEP * fraction;
+    center + gfx::Vector::new(angle.cos() * radius, angle.sin() * radius)
+}
+
+fn gauge_arc(center: gfx::Point, radius: f32, from: f32, to: f32) -> gfx::VectorPath {
+    let mut path = gfx::VectorPathBuilder::new();
+
+    let segments = ((to - from) * 48.).ceil().max(1.) as usize;
+    path.move_to(gauge_point(center, radius, from));
+    for i in 1..=segments {
+        path.line_to(gauge_point(
+            center,
+            radius,
+            from + (to - from) * (i as f32 / segments as f32),
+        ));
+    }
+
+    path.build()
+}
+
+struct GaugePainter {
+    _theme: Rc<Inner>,
+}
+
+impl<T: 'static> TypedPainter<T> for GaugePainter {
+    type Object = kit::Gauge<T>;
+
+    fn paint(&mut self, obj: &mut Self::Object, aux: &mut ui::Aux<T>) -> Vec<gfx::DisplayCommand> {
+        let mut out = gfx::DisplayListBuilder::new();
+
+        let bounds = obj.bounds();
+        let center = bounds.center();
+        let thickness = 8.;
+        let radius = bounds.size.width.min(bounds.size.height) / 2. - thickness;
+
+        let arc_stroke = |color: gfx::Color| {
+            gfx::GraphicsDisplayPaint::Stroke(gfx::GraphicsDisplayStroke {
+                thickness,
+                color: color.into(),
+                ..Default::default()
+            })
+        };
+
+        out.push_path(
+            gauge_arc(center, radius, 0., 1.),
+            false,
+            arc_stroke(aux.color(colors::STRONG_BACKGROUND)),
+            None,
+        );
+
+        if obj.zones().is_empty() {
+            let needle = obj.fraction(obj.needle());
+            if needle > 0. {
+                out.push_path(
+                    gauge_arc(center, radius, 0., needle),
+                    false,
+                    arc_stroke(aux.color(colors::ACTIVE)),
+                    None,
+                );
+            }
+        } else {
+            for zone in obj.zones() {
+                let (from, to) = (obj.fraction(zone.start), obj.fraction(zone.end));
+                if to > from {
+                    out.push_path(
+                        gauge_arc(center, radius, from, to),
+                        false,
+                        arc_stroke(zone.color),
+                        None,
+                    );
+                }
+            }
+        }
+
+        let foreground = aux.color(colors::FOREGROUND);
+
+        out.push_line(
+            center,
+            gauge_point(center, radius - thickness, obj.fraction(obj.needle())),
+            gfx::GraphicsDisplayStroke {
+                thickness: 2.,
+                color: foreground.into(),
+                ..Default::default()
+            },
+            None,
+        );
+
+        out.push_round_rectangle(
+            gfx::Rect::new(center - gfx::Vector::new(4., 4.), gfx::Size::new(8., 8.)),
+            [4.; 4],
+            gfx::GraphicsDisplayPaint::Fill(gfx::StyleColor::Color(foreground)),
+            None,
+        );
+
+        out.build()
+    }
+
+    #[inline]
+    fn size_hint(&mut self, _obj: &mut Self::Object) -> gfx::Size {
+        gfx::Size::new(100., 100.)
+    }
+
+    #[inline]
+    fn shared(&self) -> bool {
+        true
+    }
+}
+
+struct CalendarPainter {
+    _theme: Rc<Inner>,
+}
+
+impl<T: 'static> TypedPainter<T> for CalendarPainter {
+    type Object = kit::Calendar<T>;
+
     fn paint(
         &mut self,
         _obj: &mut Self::Object,
@@ -524,4 +1613,361 @@ impl<T: 'static> TypedPainter<T> for ComboListItemPainter {
     fn size_hint(&mut self, _obj: &mut Self::Object) -> gfx::Size {
         Default::default()
     }
+
+    fn metrics(&self, _obj: &Self::Object, metric: &'static str) -> Option<f32> {
+        match metric {
+            metrics::SPACING => Some(5.),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn shared(&self) -> bool {
+        true
+    }
+}
+
+struct CalendarDayPainter {
+    _theme: Rc<Inner>,
+}
+
+impl<T: 'static> TypedPainter<T> for CalendarDayPainter {
+    type Object = kit::CalendarDay<T>;
+
+    fn paint(&mut self, obj: &mut Self::Object, aux: &mut ui::Aux<T>) -> Vec<gfx::DisplayCommand> {
+        let mut out = gfx::DisplayListBuilder::new();
+
+        if obj.selected() {
+            out.push_round_rectangle(
+                obj.bounds().inflate(-1., -1.),
+                CORNER_RADII,
+                draw::fill(aux.color(if obj.enabled() {
+                    colors::ACTIVE
+                } else {
+                    colors::DISABLED_BACKGROUND
+                })),
+                None,
+            );
+        }
+
+        out.build()
+    }
+
+    #[inline]
+    fn size_hint(&mut self, _obj: &mut Self::Object) -> gfx::Size {
+        gfx::Size::new(30., 24.)
+    }
+
+    #[inline]
+    fn shared(&self) -> bool {
+        true
+    }
+}
+
+struct TooltipPainter {
+    theme: Rc<Inner>,
+}
+
+impl TooltipPainter {
+    const PADDING_X: f32 = 12.;
+    const PADDING_Y: f32 = 6.;
+}
+
+impl<T: 'static> TypedPainter<T> for TooltipPainter {
+    type Object = kit::Tooltip<T>;
+
+    fn paint(&mut self, obj: &mut Self::Object, aux: &mut ui::Aux<T>) -> Vec<gfx::DisplayCommand> {
+        let mut out = gfx::DisplayListBuilder::new();
+
+        let bounds = obj.bounds();
+
+        aux.theme
+            .shadow(&mut out, bounds, CORNER_RADII, elevations::TOOLTIP);
+        out.push_round_rectangle(
+            bounds,
+            CORNER_RADII,
+            draw::fill(aux.color(colors::STRONG_BACKGROUND)),
+            None,
+        );
+
+        let mut text = self
+            .theme
+            .ui_text(obj.text(), aux.color(colors::FOREGROUND));
+        text.set_top_left(
+            bounds.origin + gfx::Vector::new(Self::PADDING_X / 2., Self::PADDING_Y / 2.),
+        );
+        out.push_text(text, None);
+
+        out.build()
+    }
+
+    fn size_hint(&mut self, obj: &mut Self::Object) -> gfx::Size {
+        self.theme
+            .ui_text(obj.text(), Default::default())
+            .bounds()
+            .unwrap()
+            .size
+            + gfx::Size::new(Self::PADDING_X, Self::PADDING_Y)
+    }
+
+    fn metrics(&self, _obj: &Self::Object, metric: &'static str) -> Option<f32> {
+        match metric {
+            metrics::PADDING_X => Some(Self::PADDING_X),
+            metrics::PADDING_Y => Some(Self::PADDING_Y),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn shared(&self) -> bool {
+        true
+    }
+}
+
+struct StatusIndicatorPainter {
+    theme: Rc<Inner>,
+}
+
+impl StatusIndicatorPainter {
+    const PADDING_X: f32 = 12.;
+    const PADDING_Y: f32 = 6.;
+    const PROGRESS_HEIGHT: f32 = 3.;
+    const PROGRESS_SPACING: f32 = 4.;
+    const MIN_PROGRESS_WIDTH: f32 = 160.;
+}
+
+impl<T: 'static> TypedPainter<T> for StatusIndicatorPainter {
+    type Object = kit::StatusIndicator<T>;
+
+    fn paint(&mut self, obj: &mut Self::Object, aux: &mut ui::Aux<T>) -> Vec<gfx::DisplayCommand> {
+        let mut out = gfx::DisplayListBuilder::new();
+
+        let bounds = obj.bounds();
+
+        aux.theme
+            .shadow(&mut out, bounds, CORNER_RADII, elevations::TOOLTIP);
+        out.push_round_rectangle(
+            bounds,
+            CORNER_RADII,
+            draw::fill(aux.color(colors::STRONG_BACKGROUND)),
+            None,
+        );
+
+        let mut text = self
+            .theme
+            .ui_text(obj.text(), aux.color(colors::WEAK_FOREGROUND));
+        let text_height = text.bounds().unwrap().size.height;
+        text.set_top_left(
+            bounds.origin + gfx::Vector::new(Self::PADDING_X / 2., Self::PADDING_Y / 2.),
+        );
+        out.push_text(text, None);
+
+        if let Some(progress) = obj.progress() {
+            let track = gfx::Rect::new(
+                gfx::Point::new(
+                    bounds.origin.x + Self::PADDING_X / 2.,
+                    bounds.origin.y + Self::PADDING_Y / 2. + text_height + Self::PROGRESS_SPACING,
+                ),
+                gfx::Size::new(bounds.size.width - Self::PADDING_X, Self::PROGRESS_HEIGHT),
+            );
+            let radii = draw::radii(Self::PROGRESS_HEIGHT / 2.);
+
+            out.push_round_rectangle(
+                track,
+                radii,
+                draw::fill(aux.color(colors::TEXT_CONTROL)),
+                None,
+            );
+            out.push_round_rectangle(
+                gfx::Rect::new(
+                    track.origin,
+                    gfx::Size::new(track.size.width * progress, track.size.height),
+                ),
+                radii,
+                draw::fill(aux.color(colors::ACTIVE)),
+                None,
+            );
+        }
+
+        out.build()
+    }
+
+    fn size_hint(&mut self, obj: &mut Self::Object) -> gfx::Size {
+        let mut size = self
+            .theme
+            .ui_text(obj.text(), Default::default())
+            .bounds()
+            .unwrap()
+            .size;
+
+        if obj.progress().is_some() {
+            size.width = size.width.max(Self::MIN_PROGRESS_WIDTH);
+            size.height += Self::PROGRESS_SPACING + Self::PROGRESS_HEIGHT;
+        }
+
+        size + gfx::Size::new(Self::PADDING_X, Self::PADDING_Y)
+    }
+
+    fn metrics(&self, _obj: &Self::Object, metric: &'static str) -> Option<f32> {
+        match metric {
+            metrics::PADDING_X => Some(Self::PADDING_X),
+            metrics::PADDING_Y => Some(Self::PADDING_Y),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn shared(&self) -> bool {
+        true
+    }
+}
+
+const SLIDER_THUMB_SIZE: f32 = 16.;
+const SLIDER_TRACK_THICKNESS: f32 = 4.;
+
+struct RangeSliderPainter {
+    _theme: Rc<Inner>,
+}
+
+impl<T: 'static> TypedPainter<T> for RangeSliderPainter {
+    type Object = kit::RangeSlider<T>;
+
+    fn paint(&mut self, obj: &mut Self::Object, aux: &mut ui::Aux<T>) -> Vec<gfx::DisplayCommand> {
+        let mut out = gfx::DisplayListBuilder::new();
+
+        let bounds = obj.bounds();
+        let center_y = bounds.center().y;
+        let (left, right) = obj.track();
+        let x = |value: f32| left + obj.fraction(value) * (right - left);
+        let (low, high) = obj.values();
+
+        let track = |from: f32, to: f32| {
+            gfx::Rect::new(
+                gfx::Point::new(from, center_y - SLIDER_TRACK_THICKNESS / 2.),
+                gfx::Size::new(to - from, SLIDER_TRACK_THICKNESS),
+            )
+        };
+
+        out.push_round_rectangle(
+            track(left, right),
+            draw::radii(SLIDER_TRACK_THICKNESS / 2.),
+            draw::fill(aux.color(colors::STRONG_BACKGROUND)),
+            None,
+        );
+
+        out.push_round_rectangle(
+            track(x(low), x(high)),
+            draw::radii(SLIDER_TRACK_THICKNESS / 2.),
+            draw::fill(aux.color(colors::ACTIVE)),
+            None,
+        );
+
+        for (thumb, value) in [(kit::Thumb::Low, low), (kit::Thumb::High, high)].iter() {
+            let color = if obj.dragging() == Some(*thumb) {
+                colors::ACTIVE
+            } else {
+                colors::FOREGROUND
+            };
+            out.push_round_rectangle(
+                gfx::Rect::new(
+                    gfx::Point::new(
+                        x(*value) - SLIDER_THUMB_SIZE / 2.,
+                        center_y - SLIDER_THUMB_SIZE / 2.,
+                    ),
+                    gfx::Size::new(SLIDER_THUMB_SIZE, SLIDER_THUMB_SIZE),
+                ),
+                draw::radii(SLIDER_THUMB_SIZE / 2.),
+                draw::fill(aux.color(color)),
+                None,
+            );
+        }
+
+        out.build()
+    }
+
+    #[inline]
+    fn size_hint(&mut self, _obj: &mut Self::Object) -> gfx::Size {
+        gfx::Size::new(200., SLIDER_THUMB_SIZE)
+    }
+
+    fn metrics(&self, _obj: &Self::Object, metric: &'static str) -> Option<f32> {
+        match metric {
+            metrics::THUMB_SIZE => Some(SLIDER_THUMB_SIZE),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn shared(&self) -> bool {
+        true
+    }
+}
+
+const SCROLL_BAR_THICKNESS: f32 = 10.;
+const SCROLL_BAR_MIN_THUMB: f32 = 24.;
+const SCROLL_BAR_THUMB_INSET: f32 = 2.;
+
+struct ScrollBarPainter {
+    _theme: Rc<Inner>,
+}
+
+impl<T: 'static> TypedPainter<T> for ScrollBarPainter {
+    type Object = kit::ScrollBar<T>;
+
+    fn paint(&mut self, obj: &mut Self::Object, aux: &mut ui::Aux<T>) -> Vec<gfx::DisplayCommand> {
+        let mut out = gfx::DisplayListBuilder::new();
+
+        let bounds = obj.bounds();
+        let thickness = bounds.size.width.min(bounds.size.height);
+        out.push_round_rectangle(
+            bounds,
+            draw::radii(thickness / 2.),
+            draw::fill(draw::with_alpha(aux.color(colors::STRONG_BACKGROUND), 0.5)),
+            None,
+        );
+
+        if obj.scrollable() {
+            let color = if obj.dragging() {
+                aux.color(colors::ACTIVE)
+            } else if obj.thumb_hovered() {
+                mix(
+                    aux.color(colors::WEAK_FOREGROUND),
+                    aux.color(colors::FOREGROUND),
+                    0.4,
+                )
+            } else {
+                aux.color(colors::WEAK_FOREGROUND)
+            };
+            let thumb = obj
+                .thumb()
+                .inflate(-SCROLL_BAR_THUMB_INSET, -SCROLL_BAR_THUMB_INSET);
+            out.push_round_rectangle(
+                thumb,
+                draw::radii(thumb.size.width.min(thumb.size.height) / 2.),
+                draw::fill(color),
+                None,
+            );
+        }
+
+        out.build()
+    }
+
+    fn size_hint(&mut self, obj: &mut Self::Object) -> gfx::Size {
+        match obj.orientation() {
+            kit::Orientation::Horizontal => gfx::Size::new(100., SCROLL_BAR_THICKNESS),
+            kit::Orientation::Vertical => gfx::Size::new(SCROLL_BAR_THICKNESS, 100.),
+        }
+    }
+
+    fn metrics(&self, _obj: &Self::Object, metric: &'static str) -> Option<f32> {
+        match metric {
+            metrics::THUMB_SIZE => Some(SCROLL_BAR_MIN_THUMB),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn shared(&self) -> bool {
+        true
+    }
 }
@@ -2,13 +2,36 @@
 //!
 //! This API aims to be very generalized, hence the stringly-typed semantics.
 //! However, there are some predefined string values should be handled (see `painters` and `colors`).
+//! Colors and metrics can also be named by [typed tokens](tokens), which check the standard names at compile time.
 //!
 //! Themes can be extended upon be implementing a new theme type which uses composition and delegation to extend an existing theme.
+//!
+//! Widgets can be given [style classes](crate::ui::Common::add_class) (e.g. `"danger"`), for which themes can resolve different colors and painters
+//! (see [`Theme::class_color`](Theme::class_color) and [`Theme::class_painter`](Theme::class_painter)).
 
+pub mod animation;
+pub mod draw;
+#[cfg(all(feature = "themes", feature = "spec"))]
+pub mod file;
 #[cfg(feature = "themes")]
 pub mod flat;
+pub mod overrides;
+#[cfg(feature = "themes")]
+pub mod soft;
+pub mod tokens;
 
-use {crate::ui, reclutch::display as gfx, thiserror::Error};
+pub use animation::{Animator, Blink, Interpolate};
+#[cfg(all(feature = "themes", feature = "spec"))]
+pub use file::{FileTheme, ThemeSpec};
+pub use overrides::Override;
+pub use tokens::{ColorToken, MetricToken};
+
+use {
+    crate::ui,
+    reclutch::display as gfx,
+    std::{cell::RefCell, collections::HashMap, rc::Rc},
+    thiserror::Error,
+};
 
 #[derive(Debug, Error)]
 pub enum ThemeError {
@@ -16,21 +39,164 @@ pub enum ThemeError {
     ResourceError(#[from] reclutch::error::ResourceError),
     #[error("failed to load theme font: {0}")]
     FontError(#[from] reclutch::error::FontError),
+    #[error("failed to read theme file: {0}")]
+    IoError(#[from] std::io::Error),
+    #[cfg(feature = "spec")]
+    #[error("failed to parse theme file: {0}")]
+    ParseError(#[from] ron::Error),
+    #[error("the theme doesn't support custom fonts")]
+    FontsUnsupported,
+}
+
+/// Writing system of text, by which themes choose [fallback fonts](Theme::set_font_fallback).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spec", derive(serde::Deserialize))]
+pub enum Script {
+    Latin,
+    Greek,
+    Cyrillic,
+    Armenian,
+    Hebrew,
+    Arabic,
+    Devanagari,
+    Bengali,
+    Thai,
+    Georgian,
+    Hangul,
+    /// Hiragana and katakana.
+    Kana,
+    /// Chinese characters, including kanji and hanja.
+    Han,
+}
+
+impl Script {
+    /// Returns the script of `c`, or `None` for characters shared between scripts (digits, punctuation, symbols, etc).
+    pub fn of(c: char) -> Option<Script> {
+        Some(match c as u32 {
+            0x41..=0x5A | 0x61..=0x7A | 0xC0..=0x24F | 0x1E00..=0x1EFF => Script::Latin,
+            0x370..=0x3FF | 0x1F00..=0x1FFF => Script::Greek,
+            0x400..=0x52F | 0x2DE0..=0x2DFF | 0xA640..=0xA69F => Script::Cyrillic,
+            0x530..=0x58F => Script::Armenian,
+            0x590..=0x5FF => Script::Hebrew,
+            0x600..=0x6FF | 0x750..=0x77F | 0x8A0..=0x8FF | 0xFB50..=0xFDFF | 0xFE70..=0xFEFF => {
+                Script::Arabic
+            }
+            0x900..=0x97F => Script::Devanagari,
+            0x980..=0x9FF => Script::Bengali,
+            0xE00..=0xE7F => Script::Thai,
+            0x10A0..=0x10FF => Script::Georgian,
+            0x1100..=0x11FF | 0x3130..=0x318F | 0xAC00..=0xD7AF => Script::Hangul,
+            0x3040..=0x30FF | 0x31F0..=0x31FF | 0xFF66..=0xFF9F => Script::Kana,
+            0x2E80..=0x2FDF
+            | 0x3400..=0x4DBF
+            | 0x4E00..=0x9FFF
+            | 0xF900..=0xFAFF
+            | 0x20000..=0x2FA1F => Script::Han,
+            _ => return None,
+        })
+    }
 }
 
+/// Painter which may be [shared](TypedPainter::shared) between widgets.
+pub type SharedPainter<T> = Rc<RefCell<Box<dyn AnyPainter<T>>>>;
+
 pub struct Painter<E: ui::Element>(
-    Option<Box<dyn AnyPainter<E::Aux>>>,
+    Option<SharedPainter<E::Aux>>,
+    &'static str,
+    Vec<String>,
     std::marker::PhantomData<E>,
 );
 
+impl<E: ui::Element + 'static> Painter<E> {
+    /// Replaces the painter with a new one of the same kind from the theme in `aux`.
+    pub fn refresh(&mut self, aux: &ui::Aux<E::Aux>) {
+        self.0 = Some(aux.painters.get(aux.theme.as_ref(), self.1, &self.2));
+    }
+}
+
+/// Painters which keep no per-widget state (see [`TypedPainter::shared`](TypedPainter::shared)), by name and style classes,
+/// so that widgets share a single instance of each rather than retrieving their own from the theme.
+///
+/// This is held by [`Aux`](ui::Aux) and cleared whenever the theme is replaced.
+pub struct PainterCache<T: 'static>(
+    RefCell<HashMap<(&'static str, Vec<String>), SharedPainter<T>>>,
+);
+
+impl<T: 'static> Default for PainterCache<T> {
+    #[inline]
+    fn default() -> Self {
+        PainterCache(Default::default())
+    }
+}
+
+impl<T: 'static> PainterCache<T> {
+    /// Returns the painter `p` as changed by `classes` (see [`class_painter`](class_painter)),
+    /// retrieving it from `theme` unless a shared instance already exists.
+    pub fn get(
+        &self,
+        theme: &dyn Theme<T>,
+        p: &'static str,
+        classes: &[String],
+    ) -> SharedPainter<T> {
+        let key = (p, classes.to_vec());
+        if let Some(painter) = self.0.borrow().get(&key) {
+            return Rc::clone(painter);
+        }
+
+        let painter = class_painter(theme, p, classes);
+        let shared = painter.shared();
+        let painter = Rc::new(RefCell::new(painter));
+        if shared {
+            self.0.borrow_mut().insert(key, Rc::clone(&painter));
+        }
+        painter
+    }
+
+    /// Forgets every shared painter, so that they're retrieved from the theme again.
+    #[inline]
+    pub fn clear(&self) {
+        self.0.borrow_mut().clear();
+    }
+}
+
+/// Retrieves a painter from `theme` as changed by each of the [style classes](ui::Common::add_class) in turn.
+pub fn class_painter<T: 'static>(
+    theme: &dyn Theme<T>,
+    p: &'static str,
+    classes: &[String],
+) -> Box<dyn AnyPainter<T>> {
+    classes.iter().fold(theme.painter(p), |painter, class| {
+        theme.class_painter(class, p, painter)
+    })
+}
+
 pub trait TypedPainter<T: 'static>: AnyPainter<T> {
     type Object: 'static;
 
     fn paint(&mut self, obj: &mut Self::Object, aux: &mut ui::Aux<T>) -> Vec<gfx::DisplayCommand>;
     fn size_hint(&mut self, obj: &mut Self::Object) -> gfx::Size;
+    /// Returns a metric by name.
+    ///
+    /// Metrics may vary with the state of the object, such as the [interaction](crate::kit::Interactive) of `kit` widgets
+    /// (e.g. a [content offset](metrics::CONTENT_OFFSET_Y) while pressed), which widgets re-query when it changes.
     fn metrics(&self, _obj: &Self::Object, _metric: &'static str) -> Option<f32> {
         None
     }
+    /// Maps a point (in absolute coordinates) to an index within the object, for objects which have such a notion
+    /// (e.g. the character index under the cursor in a text box).
+    fn hit_test(&self, _obj: &Self::Object, _point: gfx::Point) -> Option<usize> {
+        None
+    }
+    /// Returns the rectangle (in absolute coordinates) of the caret at an index within the object; the inverse of [`hit_test`](TypedPainter::hit_test)
+    /// (e.g. for placing the candidate window of an input method next to the caret of a text box).
+    fn caret_rect(&self, _obj: &Self::Object, _index: usize) -> Option<gfx::Rect> {
+        None
+    }
+    /// Returns `true` if the painter keeps no state of its own for each widget (e.g. animations),
+    /// so that one instance can be [shared](PainterCache) by every widget using it.
+    fn shared(&self) -> bool {
+        false
+    }
 }
 
 pub trait AnyPainter<T: 'static>: as_any::AsAny {
@@ -41,6 +207,9 @@ pub trait AnyPainter<T: 'static>: as_any::AsAny {
     ) -> Vec<gfx::DisplayCommand>;
     fn size_hint(&mut self, obj: &mut dyn std::any::Any) -> gfx::Size;
     fn metrics(&self, obj: &dyn std::any::Any, metrics: &'static str) -> Option<f32>;
+    fn hit_test(&self, obj: &dyn std::any::Any, point: gfx::Point) -> Option<usize>;
+    fn caret_rect(&self, obj: &dyn std::any::Any, index: usize) -> Option<gfx::Rect>;
+    fn shared(&self) -> bool;
 }
 
 impl<T: 'static, P: TypedPainter<T>> AnyPainter<T> for P {
@@ -62,29 +231,349 @@ impl<T: 'static, P: TypedPainter<T>> AnyPainter<T> for P {
     fn metrics(&self, obj: &dyn std::any::Any, metric: &'static str) -> Option<f32> {
         TypedPainter::metrics(self, obj.downcast_ref::<P::Object>().unwrap(), metric)
     }
+
+    #[inline]
+    fn hit_test(&self, obj: &dyn std::any::Any, point: gfx::Point) -> Option<usize> {
+        TypedPainter::hit_test(self, obj.downcast_ref::<P::Object>().unwrap(), point)
+    }
+
+    #[inline]
+    fn caret_rect(&self, obj: &dyn std::any::Any, index: usize) -> Option<gfx::Rect> {
+        TypedPainter::caret_rect(self, obj.downcast_ref::<P::Object>().unwrap(), index)
+    }
+
+    #[inline]
+    fn shared(&self) -> bool {
+        TypedPainter::shared(self)
+    }
 }
 
 impl<T: 'static> as_any::Downcast for dyn AnyPainter<T> {}
 
+/// Painter for objects which a theme doesn't support, drawing a placeholder over their bounds instead of panicking.
+///
+/// Themes should return this from [`Theme::painter`](Theme::painter) for unknown painters (e.g. those of third-party widgets),
+/// along with the name of the painter which was requested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FallbackPainter(pub &'static str);
+
+impl FallbackPainter {
+    fn placeholder<T: 'static>(
+        &self,
+        bounds: gfx::Rect,
+        aux: &ui::Aux<T>,
+    ) -> Vec<gfx::DisplayCommand> {
+        let color = aux.color(colors::ERROR);
+        let mut out = gfx::DisplayListBuilder::new();
+        out.push_rectangle(bounds, draw::fill(draw::with_alpha(color, 0.2)), None);
+        out.push_rectangle(bounds.inflate(-0.5, -0.5), draw::stroke(color, 1.), None);
+        out.build()
+    }
+}
+
+impl<T: 'static> AnyPainter<T> for FallbackPainter {
+    /// The placeholder is drawn by [`paint`](paint), which knows the bounds of the object.
+    #[inline]
+    fn paint(
+        &mut self,
+        _obj: &mut dyn std::any::Any,
+        _aux: &mut ui::Aux<T>,
+    ) -> Vec<gfx::DisplayCommand> {
+        Vec::new()
+    }
+
+    #[inline]
+    fn size_hint(&mut self, _obj: &mut dyn std::any::Any) -> gfx::Size {
+        Default::default()
+    }
+
+    #[inline]
+    fn metrics(&self, _obj: &dyn std::any::Any, _metric: &'static str) -> Option<f32> {
+        None
+    }
+
+    #[inline]
+    fn hit_test(&self, _obj: &dyn std::any::Any, _point: gfx::Point) -> Option<usize> {
+        None
+    }
+
+    #[inline]
+    fn caret_rect(&self, _obj: &dyn std::any::Any, _index: usize) -> Option<gfx::Rect> {
+        None
+    }
+
+    #[inline]
+    fn shared(&self) -> bool {
+        true
+    }
+}
+
+/// Wraps a painter to override some of its metrics.
+pub(crate) struct MetricsPainter<T: 'static> {
+    pub(crate) painter: Box<dyn AnyPainter<T>>,
+    pub(crate) metrics: std::rc::Rc<std::collections::HashMap<String, f32>>,
+}
+
+impl<T: 'static> AnyPainter<T> for MetricsPainter<T> {
+    #[inline]
+    fn paint(
+        &mut self,
+        obj: &mut dyn std::any::Any,
+        aux: &mut ui::Aux<T>,
+    ) -> Vec<gfx::DisplayCommand> {
+        self.painter.paint(obj, aux)
+    }
+
+    #[inline]
+    fn size_hint(&mut self, obj: &mut dyn std::any::Any) -> gfx::Size {
+        self.painter.size_hint(obj)
+    }
+
+    fn metrics(&self, obj: &dyn std::any::Any, metric: &'static str) -> Option<f32> {
+        match self.metrics.get(metric) {
+            Some(value) => Some(*value),
+            None => self.painter.metrics(obj, metric),
+        }
+    }
+
+    #[inline]
+    fn hit_test(&self, obj: &dyn std::any::Any, point: gfx::Point) -> Option<usize> {
+        self.painter.hit_test(obj, point)
+    }
+
+    #[inline]
+    fn caret_rect(&self, obj: &dyn std::any::Any, index: usize) -> Option<gfx::Rect> {
+        self.painter.caret_rect(obj, index)
+    }
+
+    #[inline]
+    fn shared(&self) -> bool {
+        self.painter.shared()
+    }
+}
+
 #[cfg(feature = "kit")]
 pub struct Standards {
     pub label_size: f32,
     pub button_text_alignment: ui::layout::Alignment,
 }
 
+/// Unknown painters and colors should resolve to a fallback rather than panic, since widgets outside `kit` may request their own
+/// (see [`FallbackPainter`](FallbackPainter)).
 pub trait Theme<T: 'static> {
     fn painter(&self, p: &'static str) -> Box<dyn AnyPainter<T>>;
     fn color(&self, c: &'static str) -> gfx::Color;
 
+    /// Returns the glyph path of the icon `name` (see [`icons`](icons)) filling `rect`, which painters then stroke.
+    ///
+    /// By default this is the [standard glyph](icons::standard), or an empty path for unknown icons.
+    fn icon(&self, name: &'static str, rect: gfx::Rect) -> gfx::VectorPath {
+        icons::standard(name, rect).unwrap_or_else(|| gfx::VectorPathBuilder::new().build())
+    }
+
+    /// Returns the fill for the color `c` over `bounds` (in absolute coordinates), which may be a gradient rather than a flat color.
+    ///
+    /// By default this is the flat [`color`](Theme::color). Painters query this through [`Aux::style_color`](ui::Aux::style_color)
+    /// for the fills of controls (e.g. buttons), where a gradient makes sense.
+    fn style_color(&self, c: &'static str, _bounds: gfx::Rect) -> gfx::StyleColor {
+        gfx::StyleColor::Color(self.color(c))
+    }
+
+    /// Returns the color `c` for widgets with a [style class](ui::Common::add_class), or `None` if the class doesn't change it.
+    ///
+    /// This is resolved by [`Aux::color`](ui::Aux::color).
+    fn class_color(&self, _class: &str, _c: &'static str) -> Option<gfx::Color> {
+        None
+    }
+
+    /// Adjusts (e.g. by wrapping to override metrics) or replaces the painter `p` for widgets with a [style class](ui::Common::add_class).
+    fn class_painter(
+        &self,
+        _class: &str,
+        _p: &'static str,
+        painter: Box<dyn AnyPainter<T>>,
+    ) -> Box<dyn AnyPainter<T>> {
+        painter
+    }
+
+    /// Loads a font from its file data and registers it under `name`, so that it can be referred to in [fallback chains](Theme::set_font_fallback).
+    ///
+    /// By default this fails with [`FontsUnsupported`](ThemeError::FontsUnsupported).
+    fn register_font(
+        &self,
+        _display: &mut dyn gfx::GraphicsDisplay,
+        _name: &str,
+        _data: Vec<u8>,
+    ) -> Result<(), ThemeError> {
+        Err(ThemeError::FontsUnsupported)
+    }
+
+    /// Sets the fonts, in order of preference, for drawing text of `script`, each either [registered](Theme::register_font) or a system font.
+    /// The first available font of the chain is used; an empty chain removes the fallback.
+    ///
+    /// By default this fails with [`FontsUnsupported`](ThemeError::FontsUnsupported).
+    fn set_font_fallback(
+        &self,
+        _display: &mut dyn gfx::GraphicsDisplay,
+        _script: Script,
+        _fonts: &[&str],
+    ) -> Result<(), ThemeError> {
+        Err(ThemeError::FontsUnsupported)
+    }
+
+    /// Pushes the drop shadow cast by `rect` (with corner `radii`) raised to `elevation` (see [`elevations`](elevations)),
+    /// which painters then draw the element over the top of.
+    ///
+    /// By default this is an [`elevation_shadow`](draw::elevation_shadow) of the [`SHADOW`](colors::SHADOW) color.
+    fn shadow(
+        &self,
+        out: &mut gfx::DisplayListBuilder,
+        rect: gfx::Rect,
+        radii: [f32; 4],
+        elevation: f32,
+    ) {
+        draw::elevation_shadow(out, rect, radii, self.color(colors::SHADOW), elevation);
+    }
+
+    /// Pushes the highlight of a selected region (e.g. selected text, or the highlighted item of a list), which painters then draw the content over the top of.
+    ///
+    /// Text boxes, lists and tables should all highlight through this, so that selections look alike throughout.
+    /// By default this is a [`selection`](draw::selection) of the [`SELECTION`](colors::SELECTION) color.
+    fn selection(
+        &self,
+        out: &mut gfx::DisplayListBuilder,
+        rect: gfx::Rect,
+        radii: [f32; 4],
+        focused: bool,
+    ) {
+        draw::selection(out, rect, radii, self.color(colors::SELECTION), focused);
+    }
+
+    /// Pushes a text caret standing at `top` and spanning `height` (i.e. the line height), for text widgets to draw during the visible phase of their [blink](Blink).
+    ///
+    /// By default this is a [`caret`](draw::caret) of the [`FOREGROUND`](colors::FOREGROUND) color.
+    fn caret(&self, out: &mut gfx::DisplayListBuilder, top: gfx::Point, height: f32) {
+        draw::caret(out, top, height, self.color(colors::FOREGROUND));
+    }
+
     #[cfg(feature = "kit")]
     fn standards(&self) -> Standards;
 }
 
-pub fn get_painter<E: ui::Element + 'static>(
-    theme: &dyn Theme<E::Aux>,
-    p: &'static str,
-) -> Painter<E> {
-    Painter(Some(theme.painter(p)), Default::default())
+/// Reference-counted theme, so that it can be shared between [`Aux`](ui::Aux) and [theme overrides](ui::Common::set_theme).
+pub struct SharedTheme<T: 'static>(pub std::rc::Rc<dyn Theme<T>>);
+
+impl<T: 'static> Theme<T> for SharedTheme<T> {
+    #[inline]
+    fn painter(&self, p: &'static str) -> Box<dyn AnyPainter<T>> {
+        self.0.painter(p)
+    }
+
+    #[inline]
+    fn color(&self, c: &'static str) -> gfx::Color {
+        self.0.color(c)
+    }
+
+    #[inline]
+    fn icon(&self, name: &'static str, rect: gfx::Rect) -> gfx::VectorPath {
+        self.0.icon(name, rect)
+    }
+
+    #[inline]
+    fn style_color(&self, c: &'static str, bounds: gfx::Rect) -> gfx::StyleColor {
+        self.0.style_color(c, bounds)
+    }
+
+    #[inline]
+    fn class_color(&self, class: &str, c: &'static str) -> Option<gfx::Color> {
+        self.0.class_color(class, c)
+    }
+
+    #[inline]
+    fn class_painter(
+        &self,
+        class: &str,
+        p: &'static str,
+        painter: Box<dyn AnyPainter<T>>,
+    ) -> Box<dyn AnyPainter<T>> {
+        self.0.class_painter(class, p, painter)
+    }
+
+    #[inline]
+    fn register_font(
+        &self,
+        display: &mut dyn gfx::GraphicsDisplay,
+        name: &str,
+        data: Vec<u8>,
+    ) -> Result<(), ThemeError> {
+        self.0.register_font(display, name, data)
+    }
+
+    #[inline]
+    fn set_font_fallback(
+        &self,
+        display: &mut dyn gfx::GraphicsDisplay,
+        script: Script,
+        fonts: &[&str],
+    ) -> Result<(), ThemeError> {
+        self.0.set_font_fallback(display, script, fonts)
+    }
+
+    #[inline]
+    fn shadow(
+        &self,
+        out: &mut gfx::DisplayListBuilder,
+        rect: gfx::Rect,
+        radii: [f32; 4],
+        elevation: f32,
+    ) {
+        self.0.shadow(out, rect, radii, elevation)
+    }
+
+    #[inline]
+    fn selection(
+        &self,
+        out: &mut gfx::DisplayListBuilder,
+        rect: gfx::Rect,
+        radii: [f32; 4],
+        focused: bool,
+    ) {
+        self.0.selection(out, rect, radii, focused)
+    }
+
+    #[inline]
+    fn caret(&self, out: &mut gfx::DisplayListBuilder, top: gfx::Point, height: f32) {
+        self.0.caret(out, top, height)
+    }
+
+    #[cfg(feature = "kit")]
+    #[inline]
+    fn standards(&self) -> Standards {
+        self.0.standards()
+    }
+}
+
+/// Retrieves a painter for a widget from the theme in `aux`, which is shared with other widgets if possible (see [`PainterCache`](PainterCache)).
+pub fn get_painter<E: ui::Element + 'static>(aux: &ui::Aux<E::Aux>, p: &'static str) -> Painter<E> {
+    Painter(
+        Some(aux.painters.get(aux.theme.as_ref(), p, &[])),
+        p,
+        Vec::new(),
+        Default::default(),
+    )
+}
+
+/// Retrieves a new painter for a widget from the current theme and repaints the widget.
+///
+/// This is the first step of handling [`ThemeChangedEvent`](ui::ThemeChangedEvent); widgets which size themselves by their painter
+/// should then re-apply the size hint.
+pub fn refresh_painter<E: ui::Element + 'static>(
+    obj: &mut E,
+    p: impl Fn(&mut E) -> &mut Painter<E>,
+    aux: &ui::Aux<E::Aux>,
+) {
+    p(obj).refresh(aux);
+    obj.common().with(|x| x.repaint());
 }
 
 pub fn paint<E: ui::Element + 'static>(
@@ -92,9 +581,25 @@ pub fn paint<E: ui::Element + 'static>(
     p: impl Fn(&mut E) -> &mut Painter<E>,
     aux: &mut ui::Aux<E::Aux>,
 ) -> Vec<gfx::DisplayCommand> {
-    let mut painter = p(obj).0.take().unwrap();
-    let out = AnyPainter::paint(&mut *painter, obj, aux);
-    p(obj).0 = Some(painter);
+    use as_any::Downcast;
+
+    // classes may have changed since the painter was retrieved.
+    if p(obj).2 != aux.classes {
+        let painter = p(obj);
+        painter.2 = aux.classes.clone();
+        painter.refresh(aux);
+    }
+
+    let shared = p(obj).0.take().unwrap();
+    let out = {
+        let mut painter = shared.borrow_mut();
+        match painter.downcast_ref::<FallbackPainter>() {
+            // the fallback painter doesn't know the type of the object, so it's given the bounds here.
+            Some(fallback) => fallback.placeholder(obj.bounds(), aux),
+            None => AnyPainter::paint(&mut **painter, obj, aux),
+        }
+    };
+    p(obj).0 = Some(shared);
     out
 }
 
@@ -102,19 +607,55 @@ pub fn size_hint<E: ui::Element + 'static>(
     obj: &mut E,
     p: impl Fn(&mut E) -> &mut Painter<E>,
 ) -> gfx::Size {
-    let mut painter = p(obj).0.take().unwrap();
-    let out = AnyPainter::size_hint(&mut *painter, obj);
+    let painter = p(obj).0.take().unwrap();
+    let out = AnyPainter::size_hint(&mut **painter.borrow_mut(), obj);
     p(obj).0 = Some(painter);
     out
 }
 
+/// Sizes a widget to its painter's size hint, and records the hint as the widget's
+/// [preferred size](ui::Common::set_preferred_size) so that layouts can use it.
+pub fn apply_size_hint<E: ui::Element + 'static>(
+    obj: &mut E,
+    p: impl Fn(&mut E) -> &mut Painter<E>,
+) -> gfx::Size {
+    let size = size_hint(obj, p);
+    obj.common().with(|x| {
+        x.set_preferred_size(size);
+        x.set_size(size);
+    });
+    size
+}
+
 pub fn metrics<E: ui::Element + 'static>(
     obj: &mut E,
     metric: &'static str,
     p: impl Fn(&mut E) -> &mut Painter<E>,
 ) -> Option<f32> {
     let painter = p(obj).0.take().unwrap();
-    let out = AnyPainter::metrics(&*painter, obj, metric);
+    let out = AnyPainter::metrics(&**painter.borrow(), obj, metric);
+    p(obj).0 = Some(painter);
+    out
+}
+
+pub fn hit_test<E: ui::Element + 'static>(
+    obj: &mut E,
+    point: gfx::Point,
+    p: impl Fn(&mut E) -> &mut Painter<E>,
+) -> Option<usize> {
+    let painter = p(obj).0.take().unwrap();
+    let out = AnyPainter::hit_test(&**painter.borrow(), obj, point);
+    p(obj).0 = Some(painter);
+    out
+}
+
+pub fn caret_rect<E: ui::Element + 'static>(
+    obj: &mut E,
+    index: usize,
+    p: impl Fn(&mut E) -> &mut Painter<E>,
+) -> Option<gfx::Rect> {
+    let painter = p(obj).0.take().unwrap();
+    let out = AnyPainter::caret_rect(&**painter.borrow(), obj, index);
     p(obj).0 = Some(painter);
     out
 }
@@ -127,12 +668,69 @@ pub fn multi_metrics<E: ui::Element + 'static>(
     let painter = p(obj).0.take().unwrap();
     let mut out = Vec::new();
     for m in metric {
-        out.push(AnyPainter::metrics(&*painter, obj, m));
+        out.push(AnyPainter::metrics(&**painter.borrow(), obj, m));
     }
     p(obj).0 = Some(painter);
     out
 }
 
+/// Typed accessors for the standard [`metrics`](metrics), implemented for every [`Painter`](Painter).
+///
+/// Missing metrics resolve to zero rather than `None`, so that widgets don't have to unwrap each one.
+pub trait PainterMetrics<E: ui::Element + 'static> {
+    /// Returns a metric by name or [token](MetricToken).
+    fn metric(&self, obj: &E, metric: impl Into<MetricToken>) -> Option<f32>;
+
+    /// Returns the inner padding, split evenly between opposing sides.
+    fn padding(&self, obj: &E) -> ui::layout::SideMargins {
+        let x = self.metric(obj, MetricToken::PADDING_X).unwrap_or_default() / 2.;
+        let y = self.metric(obj, MetricToken::PADDING_Y).unwrap_or_default() / 2.;
+        ui::layout::SideMargins::new(y, x, y, x)
+    }
+
+    /// Returns the spacing between sub-elements (e.g. a check mark and its label).
+    fn spacing(&self, obj: &E) -> f32 {
+        self.metric(obj, MetricToken::SPACING).unwrap_or_default()
+    }
+
+    /// Returns the size of icons.
+    fn icon_size(&self, obj: &E) -> f32 {
+        self.metric(obj, MetricToken::ICON_SIZE).unwrap_or_default()
+    }
+
+    /// Returns the spacing between an icon and adjacent content.
+    fn icon_spacing(&self, obj: &E) -> f32 {
+        self.metric(obj, MetricToken::ICON_SPACING)
+            .unwrap_or_default()
+    }
+
+    /// Returns the distance from the top of the text to its baseline, if the object has text.
+    fn baseline(&self, obj: &E) -> Option<f32> {
+        self.metric(obj, MetricToken::BASELINE)
+    }
+
+    /// Returns the offset of the content from where it's laid out.
+    fn content_offset(&self, obj: &E) -> gfx::Vector {
+        gfx::Vector::new(
+            self.metric(obj, MetricToken::CONTENT_OFFSET_X)
+                .unwrap_or_default(),
+            self.metric(obj, MetricToken::CONTENT_OFFSET_Y)
+                .unwrap_or_default(),
+        )
+    }
+}
+
+impl<E: ui::Element + 'static> PainterMetrics<E> for Painter<E> {
+    #[inline]
+    fn metric(&self, obj: &E, metric: impl Into<MetricToken>) -> Option<f32> {
+        AnyPainter::metrics(
+            &**self.0.as_ref().unwrap().borrow(),
+            obj,
+            metric.into().name(),
+        )
+    }
+}
+
 pub mod painters {
     //! Standard painter definitions used by `kit`.
     //! For a theme to support `kit`, it must implement all of these.
@@ -140,10 +738,21 @@ pub mod painters {
     pub const BUTTON: &str = "button";
     pub const LABEL: &str = "label";
     pub const TEXT_BOX: &str = "text_box";
+    pub const TEXT_INPUT: &str = "text_input";
     pub const CHECK_MARK_BOX: &str = "check_mark_box";
     pub const COMBO_BOX: &str = "combo_box";
     pub const COMBO_LIST: &str = "combo_list";
     pub const COMBO_LIST_ITEM: &str = "combo_list_item";
+    pub const FILE_BROWSER: &str = "file_browser";
+    pub const FILE_BROWSER_ITEM: &str = "file_browser_item";
+    pub const GAUGE: &str = "gauge";
+    pub const CALENDAR: &str = "calendar";
+    pub const CALENDAR_DAY: &str = "calendar_day";
+    pub const TOOLTIP: &str = "tooltip";
+    pub const STATUS_INDICATOR: &str = "status_indicator";
+    pub const RANGE_SLIDER: &str = "range_slider";
+    /// Track and thumb of a scroll bar, in either orientation.
+    pub const SCROLL_BAR: &str = "scroll_bar";
 }
 
 pub mod metrics {
@@ -152,7 +761,72 @@ pub mod metrics {
 
     pub const PADDING_X: &str = "padding_x";
     pub const PADDING_Y: &str = "padding_y";
-    pub const CHECK_MARK_SPACING: &str = "spacing";
+    pub const SPACING: &str = "spacing";
+    pub const CHECK_MARK_SPACING: &str = SPACING;
+    pub const ICON_SIZE: &str = "icon_size";
+    pub const ICON_SPACING: &str = "icon_spacing";
+    pub const THUMB_SIZE: &str = "thumb_size";
+    /// Distance from the top of text to its baseline.
+    pub const BASELINE: &str = "baseline";
+    /// Horizontal offset of the content (e.g. the label of a button) from where it's laid out, such as to depress it while pressed.
+    pub const CONTENT_OFFSET_X: &str = "content_offset_x";
+    /// Vertical offset of the content from where it's laid out (see [`CONTENT_OFFSET_X`](CONTENT_OFFSET_X)).
+    pub const CONTENT_OFFSET_Y: &str = "content_offset_y";
+}
+
+pub mod elevations {
+    //! Standard elevations of raised elements, for [shadows](crate::theme::Theme::shadow).
+    //! Painters should pick from these, so that elements at the same level cast the same shadows.
+
+    /// Not raised at all; casts no shadow.
+    pub const FLAT: f32 = 0.;
+    /// Panels resting slightly above the background (e.g. cards).
+    pub const CARD: f32 = 2.;
+    /// Small transient hints (e.g. tooltips and status indicators).
+    pub const TOOLTIP: f32 = 3.;
+    /// Floating lists and menus (e.g. the list of a combo box).
+    pub const POPUP: f32 = 6.;
+    /// Dialogs over the rest of the application.
+    pub const DIALOG: f32 = 12.;
+}
+
+pub mod icons {
+    //! Standard icon definitions used by `kit`.
+    //! Themes needn't implement these, as there are [standard glyphs](standard) for each.
+
+    use {crate::theme::draw, reclutch::display as gfx};
+
+    pub const CHECK_MARK: &str = "check_mark";
+    pub const CHEVRON_UP: &str = "chevron_up";
+    pub const CHEVRON_DOWN: &str = "chevron_down";
+    pub const CHEVRON_LEFT: &str = "chevron_left";
+    pub const CHEVRON_RIGHT: &str = "chevron_right";
+    /// Up and down chevrons stacked together, as seen on combo boxes.
+    pub const UP_DOWN_ARROWS: &str = "up_down_arrows";
+    pub const FOLDER: &str = "folder";
+    pub const FILE: &str = "file";
+    /// Glyphs of the buttons of a custom window title bar.
+    pub const CLOSE: &str = "close";
+    pub const MINIMIZE: &str = "minimize";
+    pub const MAXIMIZE: &str = "maximize";
+
+    /// Returns the glyph (from [`draw`](draw)) of a standard icon filling `rect`, or `None` if `name` isn't a standard icon.
+    pub fn standard(name: &str, rect: gfx::Rect) -> Option<gfx::VectorPath> {
+        Some(match name {
+            CHECK_MARK => draw::check_mark(rect),
+            CHEVRON_UP => draw::chevron(rect, draw::Direction::Up),
+            CHEVRON_DOWN => draw::chevron(rect, draw::Direction::Down),
+            CHEVRON_LEFT => draw::chevron(rect, draw::Direction::Left),
+            CHEVRON_RIGHT => draw::chevron(rect, draw::Direction::Right),
+            UP_DOWN_ARROWS => draw::up_down_arrows(rect),
+            FOLDER => draw::folder(rect),
+            FILE => draw::file(rect),
+            CLOSE => draw::cross(rect),
+            MINIMIZE => draw::minimize(rect),
+            MAXIMIZE => draw::maximize(rect),
+            _ => return None,
+        })
+    }
 }
 
 pub mod colors {
@@ -171,4 +845,14 @@ pub mod colors {
     pub const TEXT_CONTROL: &str = "text_control";
     /// An element that is "activated".
     pub const ACTIVE: &str = "active";
+    /// Used to indicate errors, such as invalid input.
+    pub const ERROR: &str = "error";
+    /// Color used by text and other foreground elements of disabled controls.
+    pub const DISABLED_FOREGROUND: &str = "disabled_foreground";
+    /// Color used to fill disabled controls, in place of [`STRONG_BACKGROUND`](STRONG_BACKGROUND) or [`ACTIVE`](ACTIVE).
+    pub const DISABLED_BACKGROUND: &str = "disabled_background";
+    /// Color of the [shadows](crate::theme::Theme::shadow) cast by raised elements.
+    pub const SHADOW: &str = "shadow";
+    /// Color of the highlight behind selected text and items (see [`Theme::selection`](crate::theme::Theme::selection)).
+    pub const SELECTION: &str = "selection";
 }
@@ -0,0 +1,185 @@
+//! Partial overrides of the painters, colors and metrics of an existing theme.
+
+use {
+    crate::theme::*,
+    reclutch::display as gfx,
+    std::{collections::HashMap, rc::Rc},
+};
+
+/// Theme which delegates to a base theme, except for the painters, colors and metrics replaced by the user.
+///
+/// This allows small adjustments without implementing [`Theme`](Theme) from scratch;
+/// ```ignore
+/// let theme = theme::Override::new(FlatTheme::new(display, None, None)?)
+///     .with_color(colors::ACTIVE, gfx::Color::new(0.9, 0.35, 0.15, 1.0))
+///     .with_metric(painters::BUTTON, metrics::PADDING_X, 24.)
+///     .with_painter(painters::CHECK_MARK_BOX, |_| Box::new(MyCheckMarkBoxPainter));
+/// ```
+pub struct Override<B: Theme<T>, T: 'static> {
+    base: B,
+    painters: HashMap<&'static str, Box<dyn Fn(&B) -> Box<dyn AnyPainter<T>>>>,
+    colors: HashMap<&'static str, Box<dyn Fn(&B) -> gfx::Color>>,
+    metrics: HashMap<&'static str, Rc<HashMap<String, f32>>>,
+}
+
+impl<B: Theme<T>, T: 'static> Override<B, T> {
+    pub fn new(base: B) -> Self {
+        Override {
+            base,
+            painters: HashMap::new(),
+            colors: HashMap::new(),
+            metrics: HashMap::new(),
+        }
+    }
+
+    /// Replaces the painter `p` with those created by `painter`, which is given the base theme.
+    pub fn with_painter(
+        mut self,
+        p: &'static str,
+        painter: impl Fn(&B) -> Box<dyn AnyPainter<T>> + 'static,
+    ) -> Self {
+        self.painters.insert(p, Box::new(painter));
+        self
+    }
+
+    /// Replaces the color `c`.
+    pub fn with_color(self, c: &'static str, color: gfx::Color) -> Self {
+        self.with_color_fn(c, move |_| color)
+    }
+
+    /// Replaces the color `c` with that returned by `color`, which is given the base theme (e.g. to derive it from another color).
+    pub fn with_color_fn(
+        mut self,
+        c: &'static str,
+        color: impl Fn(&B) -> gfx::Color + 'static,
+    ) -> Self {
+        self.colors.insert(c, Box::new(color));
+        self
+    }
+
+    /// Replaces the metric `metric` of the painter `p` (whether it's that of the base theme or a replacement).
+    pub fn with_metric(mut self, p: &'static str, metric: &'static str, value: f32) -> Self {
+        let metrics = self.metrics.entry(p).or_default();
+        Rc::make_mut(metrics).insert(metric.to_string(), value);
+        self
+    }
+
+    #[inline]
+    pub fn base(&self) -> &B {
+        &self.base
+    }
+}
+
+impl<B: Theme<T>, T: 'static> Theme<T> for Override<B, T> {
+    fn painter(&self, p: &'static str) -> Box<dyn AnyPainter<T>> {
+        let painter = match self.painters.get(p) {
+            Some(painter) => painter(&self.base),
+            None => self.base.painter(p),
+        };
+
+        match self.metrics.get(p) {
+            Some(metrics) => Box::new(MetricsPainter {
+                painter,
+                metrics: Rc::clone(metrics),
+            }),
+            None => painter,
+        }
+    }
+
+    fn color(&self, c: &'static str) -> gfx::Color {
+        match self.colors.get(c) {
+            Some(color) => color(&self.base),
+            None => self.base.color(c),
+        }
+    }
+
+    #[inline]
+    fn icon(&self, name: &'static str, rect: gfx::Rect) -> gfx::VectorPath {
+        self.base.icon(name, rect)
+    }
+
+    /// Replaced colors are always flat, even if the base theme fills them with a gradient.
+    fn style_color(&self, c: &'static str, bounds: gfx::Rect) -> gfx::StyleColor {
+        match self.colors.get(c) {
+            Some(color) => gfx::StyleColor::Color(color(&self.base)),
+            None => self.base.style_color(c, bounds),
+        }
+    }
+
+    #[inline]
+    fn class_color(&self, class: &str, c: &'static str) -> Option<gfx::Color> {
+        self.base.class_color(class, c)
+    }
+
+    #[inline]
+    fn class_painter(
+        &self,
+        class: &str,
+        p: &'static str,
+        painter: Box<dyn AnyPainter<T>>,
+    ) -> Box<dyn AnyPainter<T>> {
+        self.base.class_painter(class, p, painter)
+    }
+
+    #[inline]
+    fn register_font(
+        &self,
+        display: &mut dyn gfx::GraphicsDisplay,
+        name: &str,
+        data: Vec<u8>,
+    ) -> Result<(), ThemeError> {
+        self.base.register_font(display, name, data)
+    }
+
+    #[inline]
+    fn set_font_fallback(
+        &self,
+        display: &mut dyn gfx::GraphicsDisplay,
+        script: Script,
+        fonts: &[&str],
+    ) -> Result<(), ThemeError> {
+        self.base.set_font_fallback(display, script, fonts)
+    }
+
+    /// Shadows are drawn by the base theme, unless the [`SHADOW`](colors::SHADOW) color is replaced.
+    fn shadow(
+        &self,
+        out: &mut gfx::DisplayListBuilder,
+        rect: gfx::Rect,
+        radii: [f32; 4],
+        elevation: f32,
+    ) {
+        match self.colors.get(colors::SHADOW) {
+            Some(color) => draw::elevation_shadow(out, rect, radii, color(&self.base), elevation),
+            None => self.base.shadow(out, rect, radii, elevation),
+        }
+    }
+
+    /// Selections are drawn by the base theme, unless the [`SELECTION`](colors::SELECTION) color is replaced.
+    fn selection(
+        &self,
+        out: &mut gfx::DisplayListBuilder,
+        rect: gfx::Rect,
+        radii: [f32; 4],
+        focused: bool,
+    ) {
+        match self.colors.get(colors::SELECTION) {
+            Some(color) => draw::selection(out, rect, radii, color(&self.base), focused),
+            None => self.base.selection(out, rect, radii, focused),
+        }
+    }
+
+    /// Carets are drawn by the base theme, unless the [`FOREGROUND`](colors::FOREGROUND) color is replaced.
+    fn caret(&self, out: &mut gfx::DisplayListBuilder, top: gfx::Point, height: f32) {
+        match self.colors.get(colors::FOREGROUND) {
+            Some(color) => draw::caret(out, top, height, color(&self.base)),
+            None => self.base.caret(out, top, height),
+        }
+    }
+
+    #[cfg(feature = "kit")]
+    #[inline]
+    fn standards(&self) -> Standards {
+        self.base.standards()
+    }
+}
@@ -0,0 +1,592 @@
+use {
+    crate::{
+        kit,
+        prelude::*,
+        theme::{
+            flat::{self, fade, interaction_fill, rgba, Brightness, FlatTheme},
+            *,
+        },
+        ui,
+    },
+    reclutch::display as gfx,
+    std::time::Duration,
+};
+
+const CORNER_RADIUS: f32 = 8.;
+const CORNER_RADII: [f32; 4] = [CORNER_RADIUS, CORNER_RADIUS, CORNER_RADIUS, CORNER_RADIUS];
+
+const TRANSITION_DURATION: Duration = Duration::from_millis(150);
+
+/// Transitions a metric (such as an elevation) towards `target`, returning the value to paint with.
+///
+/// The animator is created upon first use so that the initial value doesn't animate.
+fn transition<T: 'static>(
+    animator: &mut Option<Animator<f32>>,
+    target: f32,
+    common: &ui::CommonRef,
+    aux: &ui::Aux<T>,
+) -> f32 {
+    animator
+        .get_or_insert_with(|| Animator::new(target, TRANSITION_DURATION))
+        .animate(target, common, aux)
+}
+
+/// Resting elevation of a control, raised while hovered and lowered while pressed.
+fn control_elevation(interaction: kit::Interaction) -> f32 {
+    if interaction.pressed {
+        0.5
+    } else if interaction.hovered {
+        elevations::CARD + 1.
+    } else {
+        1.
+    }
+}
+
+/// Theme of pill-shaped, raised controls which lift on hover, over a muted palette.
+///
+/// Painters without a distinct look here (e.g. labels and text boxes) are those of the [flat theme](flat::FlatTheme),
+/// drawn with this theme's colors and shadows.
+pub struct SoftTheme {
+    flat: FlatTheme,
+}
+
+impl SoftTheme {
+    /// Creates the theme with its dark palette.
+    #[inline]
+    pub fn new(
+        display: &mut dyn gfx::GraphicsDisplay,
+        fonts: Option<flat::Fonts>,
+        font_sizes: Option<flat::FontSizes>,
+    ) -> Result<Self, ThemeError> {
+        SoftTheme::with_brightness(display, fonts, font_sizes, Brightness::Dark)
+    }
+
+    /// Creates the theme with its light palette.
+    #[inline]
+    pub fn new_light(
+        display: &mut dyn gfx::GraphicsDisplay,
+        fonts: Option<flat::Fonts>,
+        font_sizes: Option<flat::FontSizes>,
+    ) -> Result<Self, ThemeError> {
+        SoftTheme::with_brightness(display, fonts, font_sizes, Brightness::Light)
+    }
+
+    pub fn with_brightness(
+        display: &mut dyn gfx::GraphicsDisplay,
+        fonts: Option<flat::Fonts>,
+        font_sizes: Option<flat::FontSizes>,
+        brightness: Brightness,
+    ) -> Result<Self, ThemeError> {
+        Ok(SoftTheme {
+            flat: FlatTheme::with_brightness(display, fonts, font_sizes, brightness)?,
+        })
+    }
+
+    #[inline]
+    pub fn brightness(&self) -> Brightness {
+        self.flat.brightness()
+    }
+
+    /// Returns the underlying flat theme, which holds the fonts (see [`FlatTheme::register_font`](flat::FlatTheme::register_font)).
+    #[inline]
+    pub fn flat(&self) -> &FlatTheme {
+        &self.flat
+    }
+}
+
+impl<T: 'static> Theme<T> for SoftTheme {
+    fn painter(&self, p: &'static str) -> Box<dyn AnyPainter<T>> {
+        match p {
+            painters::BUTTON => Box::new(ButtonPainter {
+                fill: None,
+                elevation: None,
+            }),
+            painters::CHECK_MARK_BOX => Box::new(CheckMarkBoxPainter {
+                fill: None,
+                check: None,
+            }),
+            painters::TEXT_INPUT => Box::new(TextInputPainter {
+                base: Theme::<T>::painter(&self.flat, p),
+                underline: None,
+            }),
+            painters::COMBO_LIST => Box::new(ComboListPainter),
+            painters::RANGE_SLIDER => Box::new(RangeSliderPainter { elevation: None }),
+            _ => Theme::<T>::painter(&self.flat, p),
+        }
+    }
+
+    fn color(&self, c: &'static str) -> gfx::Color {
+        match self.brightness() {
+            Brightness::Dark => match c {
+                colors::FOREGROUND => rgba(226, 224, 232, 1.0),
+                colors::BACKGROUND => rgba(30, 29, 36, 1.0),
+                colors::WEAK_FOREGROUND => rgba(140, 137, 152, 1.0),
+                colors::STRONG_BACKGROUND => rgba(52, 50, 62, 1.0),
+                colors::TEXT_CONTROL => rgba(42, 40, 50, 1.0),
+                colors::ACTIVE => rgba(128, 110, 220, 1.0),
+                colors::ERROR => rgba(232, 104, 112, 1.0),
+                colors::DISABLED_FOREGROUND => rgba(92, 90, 102, 1.0),
+                colors::DISABLED_BACKGROUND => rgba(40, 39, 48, 1.0),
+                colors::SHADOW => rgba(0, 0, 0, 0.6),
+                colors::SELECTION => rgba(128, 110, 220, 0.45),
+                // unknown colors (e.g. of third-party widgets) are treated as foreground.
+                _ => rgba(226, 224, 232, 1.0),
+            },
+            Brightness::Light => match c {
+                colors::FOREGROUND => rgba(40, 38, 48, 1.0),
+                colors::BACKGROUND => rgba(246, 244, 250, 1.0),
+                colors::WEAK_FOREGROUND => rgba(128, 124, 140, 1.0),
+                colors::STRONG_BACKGROUND => rgba(255, 255, 255, 1.0),
+                colors::TEXT_CONTROL => rgba(236, 233, 242, 1.0),
+                colors::ACTIVE => rgba(103, 80, 200, 1.0),
+                colors::ERROR => rgba(200, 60, 72, 1.0),
+                colors::DISABLED_FOREGROUND => rgba(176, 172, 186, 1.0),
+                colors::DISABLED_BACKGROUND => rgba(232, 229, 238, 1.0),
+                colors::SHADOW => rgba(40, 30, 80, 0.3),
+                colors::SELECTION => rgba(103, 80, 200, 0.3),
+                _ => rgba(40, 38, 48, 1.0),
+            },
+        }
+    }
+
+    /// Shadows are softer and spread further than those of the flat theme.
+    fn shadow(
+        &self,
+        out: &mut gfx::DisplayListBuilder,
+        rect: gfx::Rect,
+        radii: [f32; 4],
+        elevation: f32,
+    ) {
+        draw::soft_shadow(
+            out,
+            rect,
+            radii,
+            Theme::<T>::color(self, colors::SHADOW),
+            elevation * 1.5,
+        );
+    }
+
+    #[inline]
+    fn register_font(
+        &self,
+        display: &mut dyn gfx::GraphicsDisplay,
+        name: &str,
+        data: Vec<u8>,
+    ) -> Result<(), ThemeError> {
+        Theme::<T>::register_font(&self.flat, display, name, data)
+    }
+
+    #[inline]
+    fn set_font_fallback(
+        &self,
+        display: &mut dyn gfx::GraphicsDisplay,
+        script: Script,
+        fonts: &[&str],
+    ) -> Result<(), ThemeError> {
+        self.flat.set_fallback(display, script, fonts)
+    }
+
+    fn standards(&self) -> Standards {
+        Theme::<T>::standards(&self.flat)
+    }
+}
+
+struct ButtonPainter {
+    fill: Option<Animator<gfx::Color>>,
+    elevation: Option<Animator<f32>>,
+}
+
+impl<T: 'static> TypedPainter<T> for ButtonPainter {
+    type Object = kit::Button<T>;
+
+    fn paint(
+        &mut self,
+        obj: &mut kit::Button<T>,
+        aux: &mut ui::Aux<T>,
+    ) -> Vec<gfx::DisplayCommand> {
+        let mut out = gfx::DisplayListBuilder::new();
+
+        let bounds = obj.bounds();
+        let radii = draw::radii(bounds.size.height / 2.);
+        let interaction = obj.interaction();
+
+        let elevation = transition(
+            &mut self.elevation,
+            control_elevation(interaction),
+            obj.common(),
+            aux,
+        );
+        aux.theme.shadow(&mut out, bounds, radii, elevation);
+
+        let fill = aux.color(if obj.toggled() {
+            colors::ACTIVE
+        } else {
+            colors::STRONG_BACKGROUND
+        });
+        let fill = fade(
+            &mut self.fill,
+            interaction_fill(fill, interaction, aux),
+            obj.common(),
+            aux,
+        );
+        out.push_round_rectangle(bounds, radii, draw::fill(fill), None);
+
+        if aux.has_focus(obj.common()) {
+            draw::focus_ring(
+                &mut out,
+                bounds,
+                radii,
+                draw::with_alpha(aux.color(colors::ACTIVE), 0.4),
+                3.,
+                2.,
+            );
+        }
+
+        if let (Some(icon), Some(rect)) = (obj.icon(), obj.icon_bounds()) {
+            out.push_path(
+                icon.path(rect),
+                false,
+                draw::stroke(aux.color(colors::FOREGROUND), 1.5),
+                None,
+            );
+        }
+
+        out.build()
+    }
+
+    #[inline]
+    fn size_hint(&mut self, _obj: &mut kit::Button<T>) -> gfx::Size {
+        Default::default()
+    }
+
+    fn metrics(&self, obj: &kit::Button<T>, metric: &'static str) -> Option<f32> {
+        match metric {
+            metrics::PADDING_X => Some(36.),
+            metrics::PADDING_Y => Some(10.),
+            metrics::ICON_SIZE => Some(14.),
+            metrics::ICON_SPACING => Some(8.),
+            // the content sinks slightly as the button is pressed down.
+            metrics::CONTENT_OFFSET_Y if obj.interaction().pressed => Some(1.),
+            _ => None,
+        }
+    }
+}
+
+struct CheckMarkBoxPainter {
+    fill: Option<Animator<gfx::Color>>,
+    check: Option<Animator<f32>>,
+}
+
+impl<T: 'static> TypedPainter<T> for CheckMarkBoxPainter {
+    type Object = kit::CheckMarkBox<T>;
+
+    fn paint(
+        &mut self,
+        obj: &mut kit::CheckMarkBox<T>,
+        aux: &mut ui::Aux<T>,
+    ) -> Vec<gfx::DisplayCommand> {
+        let mut out = gfx::DisplayListBuilder::new();
+
+        let bounds = obj.bounds();
+        let radii = draw::radii(bounds.size.height / 3.);
+
+        let fill = aux.color(if obj.checked() {
+            colors::ACTIVE
+        } else {
+            colors::TEXT_CONTROL
+        });
+        let fill = fade(
+            &mut self.fill,
+            interaction_fill(fill, obj.interaction(), aux),
+            obj.common(),
+            aux,
+        );
+
+        // unchecked boxes are outlined, whereas checked boxes are filled in.
+        let check = transition(
+            &mut self.check,
+            if obj.checked() { 1. } else { 0. },
+            obj.common(),
+            aux,
+        );
+        draw::bordered_rect(
+            &mut out,
+            bounds,
+            radii,
+            fill,
+            if check < 1. {
+                Some((
+                    draw::with_alpha(aux.color(colors::WEAK_FOREGROUND), 1. - check),
+                    2.,
+                ))
+            } else {
+                None
+            },
+        );
+
+        if aux.has_focus(obj.common()) {
+            draw::focus_ring(
+                &mut out,
+                bounds,
+                radii,
+                draw::with_alpha(aux.color(colors::ACTIVE), 0.4),
+                3.,
+                2.,
+            );
+        }
+
+        // the check mark grows in from the center as the box is checked.
+        if check > 0. {
+            let inset = 4. + (1. - check) * bounds.size.width / 4.;
+            out.push_path(
+                aux.theme
+                    .icon(icons::CHECK_MARK, bounds.inflate(-inset, -inset)),
+                false,
+                gfx::GraphicsDisplayPaint::Stroke(gfx::GraphicsDisplayStroke {
+                    thickness: 2.,
+                    color: draw::with_alpha(aux.color(colors::BACKGROUND), check).into(),
+                    ..Default::default()
+                }),
+                None,
+            )
+        }
+
+        out.build()
+    }
+
+    #[inline]
+    fn size_hint(&mut self, _obj: &mut kit::CheckMarkBox<T>) -> gfx::Size {
+        gfx::Size::new(20., 20.)
+    }
+
+    fn metrics(&self, _obj: &kit::CheckMarkBox<T>, metric: &'static str) -> Option<f32> {
+        match metric {
+            metrics::CHECK_MARK_SPACING => Some(8.0),
+            _ => None,
+        }
+    }
+}
+
+/// Filled field with an underline which widens when focused, as opposed to the bordered box of the flat theme.
+struct TextInputPainter<T: 'static> {
+    base: Box<dyn AnyPainter<T>>,
+    underline: Option<Animator<f32>>,
+}
+
+impl<T: 'static> TypedPainter<T> for TextInputPainter<T> {
+    type Object = kit::TextInput<T>;
+
+    fn paint(&mut self, obj: &mut Self::Object, aux: &mut ui::Aux<T>) -> Vec<gfx::DisplayCommand> {
+        let mut out = gfx::DisplayListBuilder::new();
+
+        let bounds = obj.bounds();
+        let focused = obj.focused(aux);
+
+        out.push_round_rectangle(
+            bounds,
+            [CORNER_RADIUS, CORNER_RADIUS, 0., 0.],
+            draw::fill(aux.color(colors::TEXT_CONTROL)),
+            None,
+        );
+
+        out.push_rectangle(
+            gfx::Rect::new(
+                gfx::Point::new(bounds.min_x(), bounds.max_y() - 1.),
+                gfx::Size::new(bounds.size.width, 1.),
+            ),
+            draw::fill(aux.color(colors::WEAK_FOREGROUND)),
+            None,
+        );
+
+        let underline = transition(
+            &mut self.underline,
+            if focused { 1. } else { 0. },
+            obj.common(),
+            aux,
+        );
+        if underline > 0. {
+            let width = bounds.size.width * underline;
+            out.push_rectangle(
+                gfx::Rect::new(
+                    gfx::Point::new(bounds.center().x - width / 2., bounds.max_y() - 2.),
+                    gfx::Size::new(width, 2.),
+                ),
+                draw::fill(aux.color(colors::ACTIVE)),
+                None,
+            );
+        }
+
+        let icon_size = self.base.metrics(obj, metrics::ICON_SIZE).unwrap_or(0.);
+        let padding_x = self.base.metrics(obj, metrics::PADDING_X).unwrap_or(0.);
+        let icon_rect = |x: f32| {
+            gfx::Rect::new(
+                gfx::Point::new(x, bounds.center().y - icon_size / 2.),
+                gfx::Size::new(icon_size, icon_size),
+            )
+        };
+        let icons = [
+            (obj.leading_icon(), bounds.min_x() + padding_x / 2.),
+            (
+                obj.trailing_icon(),
+                bounds.max_x() - padding_x / 2. - icon_size,
+            ),
+        ];
+        for (icon, x) in icons.iter() {
+            if let Some(icon) = icon {
+                out.push_path(
+                    icon.path(icon_rect(*x)),
+                    false,
+                    draw::stroke(
+                        aux.color(if focused {
+                            colors::ACTIVE
+                        } else {
+                            colors::WEAK_FOREGROUND
+                        }),
+                        1.5,
+                    ),
+                    None,
+                );
+            }
+        }
+
+        out.build()
+    }
+
+    /// The field is a little taller than that of the flat theme, leaving room for the underline.
+    fn size_hint(&mut self, obj: &mut Self::Object) -> gfx::Size {
+        self.base.size_hint(obj) + gfx::Size::new(0., 6.)
+    }
+
+    fn metrics(&self, obj: &Self::Object, metric: &'static str) -> Option<f32> {
+        match metric {
+            metrics::PADDING_Y => Some(14.),
+            _ => self.base.metrics(obj, metric),
+        }
+    }
+}
+
+/// Opaque card floating above the combo box, rather than the translucent, blurred list of the flat theme.
+struct ComboListPainter;
+
+impl<T: 'static> TypedPainter<T> for ComboListPainter {
+    type Object = kit::ComboList<T>;
+
+    fn paint(&mut self, obj: &mut Self::Object, aux: &mut ui::Aux<T>) -> Vec<gfx::DisplayCommand> {
+        let mut out = gfx::DisplayListBuilder::new();
+
+        let bounds = obj.bounds();
+
+        aux.theme
+            .shadow(&mut out, bounds, CORNER_RADII, elevations::POPUP);
+        out.push_round_rectangle(
+            bounds,
+            CORNER_RADII,
+            draw::fill(aux.color(colors::STRONG_BACKGROUND)),
+            None,
+        );
+
+        out.build()
+    }
+
+    #[inline]
+    fn size_hint(&mut self, _obj: &mut Self::Object) -> gfx::Size {
+        Default::default()
+    }
+
+    #[inline]
+    fn shared(&self) -> bool {
+        true
+    }
+}
+
+const SLIDER_THUMB_SIZE: f32 = 18.;
+const SLIDER_TRACK_THICKNESS: f32 = 6.;
+
+struct RangeSliderPainter {
+    elevation: Option<Animator<f32>>,
+}
+
+impl<T: 'static> TypedPainter<T> for RangeSliderPainter {
+    type Object = kit::RangeSlider<T>;
+
+    fn paint(&mut self, obj: &mut Self::Object, aux: &mut ui::Aux<T>) -> Vec<gfx::DisplayCommand> {
+        let mut out = gfx::DisplayListBuilder::new();
+
+        let bounds = obj.bounds();
+        let center_y = bounds.center().y;
+        let (left, right) = obj.track();
+        let x = |value: f32| left + obj.fraction(value) * (right - left);
+        let (low, high) = obj.values();
+
+        let track = |from: f32, to: f32| {
+            gfx::Rect::new(
+                gfx::Point::new(from, center_y - SLIDER_TRACK_THICKNESS / 2.),
+                gfx::Size::new(to - from, SLIDER_TRACK_THICKNESS),
+            )
+        };
+
+        out.push_round_rectangle(
+            track(left, right),
+            draw::radii(SLIDER_TRACK_THICKNESS / 2.),
+            draw::fill(draw::with_alpha(aux.color(colors::ACTIVE), 0.25)),
+            None,
+        );
+
+        out.push_round_rectangle(
+            track(x(low), x(high)),
+            draw::radii(SLIDER_TRACK_THICKNESS / 2.),
+            draw::fill(aux.color(colors::ACTIVE)),
+            None,
+        );
+
+        // the dragged thumb is lifted above the other.
+        let lifted = transition(
+            &mut self.elevation,
+            if obj.dragging().is_some() {
+                elevations::CARD + 2.
+            } else {
+                1.
+            },
+            obj.common(),
+            aux,
+        );
+
+        let radii = draw::radii(SLIDER_THUMB_SIZE / 2.);
+        for (thumb, value) in [(kit::Thumb::Low, low), (kit::Thumb::High, high)].iter() {
+            let rect = gfx::Rect::new(
+                gfx::Point::new(
+                    x(*value) - SLIDER_THUMB_SIZE / 2.,
+                    center_y - SLIDER_THUMB_SIZE / 2.,
+                ),
+                gfx::Size::new(SLIDER_THUMB_SIZE, SLIDER_THUMB_SIZE),
+            );
+            let elevation = if obj.dragging() == Some(*thumb) {
+                lifted
+            } else {
+                1.
+            };
+            aux.theme.shadow(&mut out, rect, radii, elevation);
+            draw::bordered_rect(
+                &mut out,
+                rect,
+                radii,
+                aux.color(colors::STRONG_BACKGROUND),
+                Some((aux.color(colors::ACTIVE), 2.)),
+            );
+        }
+
+        out.build()
+    }
+
+    #[inline]
+    fn size_hint(&mut self, _obj: &mut Self::Object) -> gfx::Size {
+        gfx::Size::new(200., SLIDER_THUMB_SIZE)
+    }
+
+    fn metrics(&self, _obj: &Self::Object, metric: &'static str) -> Option<f32> {
+        match metric {
+            metrics::THUMB_SIZE => Some(SLIDER_THUMB_SIZE),
+            _ => None,
+        }
+    }
+}
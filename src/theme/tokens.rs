@@ -0,0 +1,92 @@
+//! Typed names of theme colors and metrics, as an alternative to the string constants of [`colors`](super::colors) and [`metrics`](super::metrics).
+//!
+//! The standard tokens are associated constants, so a misspelt name fails to compile rather than resolving to a theme's fallback at runtime.
+//! Names outside the standard set (e.g. those of third-party widgets) are given through [`ColorToken::custom`](ColorToken::custom) and
+//! [`MetricToken::custom`](MetricToken::custom).
+
+use crate::theme::{colors, metrics};
+
+/// Name of a color within a theme (see [`Aux::color`](crate::ui::Aux::color)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ColorToken(&'static str);
+
+impl ColorToken {
+    pub const FOREGROUND: ColorToken = ColorToken(colors::FOREGROUND);
+    pub const BACKGROUND: ColorToken = ColorToken(colors::BACKGROUND);
+    pub const WEAK_FOREGROUND: ColorToken = ColorToken(colors::WEAK_FOREGROUND);
+    pub const STRONG_BACKGROUND: ColorToken = ColorToken(colors::STRONG_BACKGROUND);
+    pub const TEXT_CONTROL: ColorToken = ColorToken(colors::TEXT_CONTROL);
+    pub const ACTIVE: ColorToken = ColorToken(colors::ACTIVE);
+    pub const ERROR: ColorToken = ColorToken(colors::ERROR);
+    pub const DISABLED_FOREGROUND: ColorToken = ColorToken(colors::DISABLED_FOREGROUND);
+    pub const DISABLED_BACKGROUND: ColorToken = ColorToken(colors::DISABLED_BACKGROUND);
+    pub const SHADOW: ColorToken = ColorToken(colors::SHADOW);
+    pub const SELECTION: ColorToken = ColorToken(colors::SELECTION);
+
+    /// Creates a token for a color outside the standard set, which themes may not know of.
+    #[inline]
+    pub const fn custom(name: &'static str) -> Self {
+        ColorToken(name)
+    }
+
+    #[inline]
+    pub const fn name(self) -> &'static str {
+        self.0
+    }
+}
+
+impl From<&'static str> for ColorToken {
+    #[inline]
+    fn from(name: &'static str) -> Self {
+        ColorToken(name)
+    }
+}
+
+impl From<ColorToken> for &'static str {
+    #[inline]
+    fn from(token: ColorToken) -> Self {
+        token.0
+    }
+}
+
+/// Name of a painter metric (see [`PainterMetrics::metric`](crate::theme::PainterMetrics::metric)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MetricToken(&'static str);
+
+impl MetricToken {
+    pub const PADDING_X: MetricToken = MetricToken(metrics::PADDING_X);
+    pub const PADDING_Y: MetricToken = MetricToken(metrics::PADDING_Y);
+    pub const SPACING: MetricToken = MetricToken(metrics::SPACING);
+    pub const CHECK_MARK_SPACING: MetricToken = MetricToken(metrics::CHECK_MARK_SPACING);
+    pub const ICON_SIZE: MetricToken = MetricToken(metrics::ICON_SIZE);
+    pub const ICON_SPACING: MetricToken = MetricToken(metrics::ICON_SPACING);
+    pub const THUMB_SIZE: MetricToken = MetricToken(metrics::THUMB_SIZE);
+    pub const BASELINE: MetricToken = MetricToken(metrics::BASELINE);
+    pub const CONTENT_OFFSET_X: MetricToken = MetricToken(metrics::CONTENT_OFFSET_X);
+    pub const CONTENT_OFFSET_Y: MetricToken = MetricToken(metrics::CONTENT_OFFSET_Y);
+
+    /// Creates a token for a metric outside the standard set, which painters may not know of.
+    #[inline]
+    pub const fn custom(name: &'static str) -> Self {
+        MetricToken(name)
+    }
+
+    #[inline]
+    pub const fn name(self) -> &'static str {
+        self.0
+    }
+}
+
+impl From<&'static str> for MetricToken {
+    #[inline]
+    fn from(name: &'static str) -> Self {
+        MetricToken(name)
+    }
+}
+
+impl From<MetricToken> for &'static str {
+    #[inline]
+    fn from(token: MetricToken) -> Self {
+        token.0
+    }
+}
@@ -1,10 +1,16 @@
 use {crate::ui::layout, reclutch::display as gfx, std::collections::BTreeMap};
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "spec", derive(serde::Deserialize), serde(default))]
 pub struct HStackConfig {
     pub left_margin: f32,
     pub right_margin: f32,
     pub alignment: layout::Alignment,
+    pub fill_h: Option<f32>,
+    /// Weight by which the item is widened to share the width left over by all the items.
+    ///
+    /// Items with a weight of `0.0` (the default) keep their own width.
+    pub flex: f32,
 }
 
 impl From<(f32, f32)> for HStackConfig {
@@ -20,20 +26,142 @@ impl From<(f32, f32)> for HStackConfig {
 struct Item {
     config: HStackConfig,
     item: layout::Item,
+    /// The size last given to the item and its natural size at the time, if the two differed.
+    stretched: Option<(gfx::Size, gfx::Size)>,
+}
+
+impl Item {
+    /// Returns the size of the item without the extra space it was given by flexing or filling.
+    ///
+    /// Items without a preferred size report their current size, which includes any extra space they were given;
+    /// as long as the item still has the size it was given, the size from before is used instead.
+    fn natural_size(&self) -> gfx::Size {
+        let size = self.item.preferred_size();
+        match self.stretched {
+            Some((given, natural)) if given == size => natural,
+            _ => size,
+        }
+    }
 }
 
 pub struct HStack {
     entries: BTreeMap<u64, Item>,
+    order: Vec<u64>,
     next_id: u64,
+    spacing: f32,
 }
 
 impl HStack {
     pub fn new() -> Self {
+        HStack::with_spacing(0.0)
+    }
+
+    /// Creates a `HStack` which leaves `spacing` between adjacent items, on top of their margins.
+    pub fn with_spacing(spacing: f32) -> Self {
         HStack {
             entries: Default::default(),
+            order: Vec::new(),
             next_id: 0,
+            spacing,
         }
     }
+
+    #[inline]
+    pub fn set_spacing(&mut self, spacing: f32) {
+        self.spacing = spacing;
+    }
+
+    #[inline]
+    pub fn spacing(&self) -> f32 {
+        self.spacing
+    }
+
+    /// Adds an item at `index` in the order of the items, shifting the items after it.
+    ///
+    /// `index` is clamped to the number of items, so that an out of range index is the same as [`push`](layout::Layout::push).
+    pub fn insert_at(
+        &mut self,
+        index: usize,
+        item: impl Into<layout::Item>,
+        config: Option<HStackConfig>,
+    ) -> u64 {
+        let id = layout::Layout::push(self, item, config);
+        self.reorder(&id, index);
+        id
+    }
+
+    /// Moves the item of `id` to `index` in the order of the items (clamped to the last position).
+    ///
+    /// Returns `false` if there is no such item.
+    pub fn reorder(&mut self, id: &u64, index: usize) -> bool {
+        match self.order.iter().position(|x| x == id) {
+            Some(old) => {
+                self.order.remove(old);
+                let index = index.min(self.order.len());
+                self.order.insert(index, *id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the position of the item of `id` in the order of the items.
+    #[inline]
+    pub fn index_of(&self, id: &u64) -> Option<usize> {
+        self.order.iter().position(|x| x == id)
+    }
+
+    /// Returns the entries in the order they're laid out.
+    fn ordered(&self) -> impl Iterator<Item = &Item> {
+        self.order.iter().map(move |id| &self.entries[id])
+    }
+}
+
+impl layout::Node<HStack> {
+    /// Adds an item at `index` in the order of the items; see [`HStack::insert_at`](HStack::insert_at).
+    pub fn insert_at(
+        &mut self,
+        index: usize,
+        item: impl Into<layout::Item>,
+        config: Option<HStackConfig>,
+    ) -> u64 {
+        let id = self.push(item, config);
+        self.reorder(&id, index);
+        id
+    }
+}
+
+/// Returns the distance from the top of an item to its baseline; the bottom edge if it has no text.
+fn baseline(entry: &Item) -> f32 {
+    entry
+        .item
+        .baseline()
+        .unwrap_or_else(|| entry.natural_size().height)
+}
+
+/// Returns the height above and below the shared baseline of the baseline-aligned items, as `(ascent, descent)`.
+fn baseline_extents<'a>(entries: impl Iterator<Item = &'a Item>) -> (f32, f32) {
+    entries
+        .filter(|x| {
+            x.config.alignment == layout::Alignment::Baseline
+                && layout::should_layout(&x.item)
+                && layout::as_spacer(&x.item).is_none()
+        })
+        .fold((0.0, 0.0), |(ascent, descent), x| {
+            let baseline = baseline(x);
+            let height = x.natural_size().height;
+            (
+                f32::max(ascent, baseline),
+                f32::max(descent, height - baseline),
+            )
+        })
+}
+
+/// Returns the flex weight of an entry, which for spacers is that of the spacer.
+fn flex(entry: &Item) -> f32 {
+    layout::as_spacer(&entry.item)
+        .map_or(entry.config.flex, |x| x.flex())
+        .max(0.0)
 }
 
 impl layout::Layout for HStack {
@@ -48,13 +176,16 @@ impl layout::Layout for HStack {
             Item {
                 config: config.unwrap_or_default(),
                 item: item.into(),
+                stretched: None,
             },
         );
+        self.order.push(id);
         id
     }
 
     #[inline]
     fn remove(&mut self, id: &u64) -> Option<layout::Item> {
+        self.order.retain(|x| x != id);
         self.entries.remove(id).map(|x| x.item)
     }
 
@@ -74,47 +205,98 @@ impl layout::Layout for HStack {
     }
 
     fn items(&self) -> Vec<(&layout::Item, &u64)> {
-        self.entries
-            .values()
-            .map(|x| &x.item)
-            .zip(self.entries.keys())
+        self.order
+            .iter()
+            .map(|id| (&self.entries[id].item, id))
             .collect()
     }
 
     fn min_size(&self) -> gfx::Size {
         let mut width = 0.0;
         let mut height = 0.0;
-        for entry in self.entries.values() {
+        let mut count = 0;
+        for entry in self.ordered() {
             if !layout::should_layout(&entry.item) {
                 continue;
             }
 
-            let rect = entry.item.rect();
-            if rect.size.height > height {
-                height = rect.size.height;
+            count += 1;
+            width += entry.config.left_margin + entry.config.right_margin;
+            if let Some(spacer) = layout::as_spacer(&entry.item) {
+                width += spacer.size();
+                continue;
+            }
+
+            let size = entry.natural_size();
+            if size.height > height {
+                height = size.height;
             }
-            width += rect.size.width + entry.config.left_margin + entry.config.left_margin;
+            width += size.width;
         }
-        gfx::Size::new(width, height)
+        width += self.spacing * (count.max(1) - 1) as f32;
+
+        let (ascent, descent) = baseline_extents(self.entries.values());
+        gfx::Size::new(width, f32::max(height, ascent + descent))
     }
 
     fn update(&mut self, bounds: gfx::Rect) {
+        let total_flex: f32 = self
+            .entries
+            .values()
+            .filter(|x| layout::should_layout(&x.item))
+            .map(flex)
+            .sum();
+        let extra = if total_flex > 0.0 {
+            (bounds.size.width - layout::Layout::min_size(self).width).max(0.0)
+        } else {
+            0.0
+        };
+
+        let (ascent, _) = baseline_extents(self.entries.values());
+
         let mut x = bounds.origin.x;
-        for entry in self.entries.values_mut() {
+        let mut first = true;
+        for id in &self.order {
+            let entry = self.entries.get_mut(id).unwrap();
             if !layout::should_layout(&entry.item) {
                 continue;
             }
 
+            if !first {
+                x += self.spacing;
+            }
+            first = false;
+
             x += entry.config.left_margin;
-            let rect = entry.item.rect();
-            entry.item.set_rect(gfx::Rect::new(
-                gfx::Point::new(
-                    x,
-                    layout::align_y(rect, bounds, entry.config.alignment, 0.0),
-                ),
-                rect.size,
-            ));
-            x += rect.size.width + entry.config.right_margin;
+            let size = match layout::as_spacer(&entry.item) {
+                Some(spacer) => gfx::Size::new(spacer.size(), 0.0),
+                None => entry.natural_size(),
+            };
+            let rect = gfx::Rect::new(Default::default(), size);
+            let mut w = rect.size.width;
+            if total_flex > 0.0 {
+                w += extra * flex(entry) / total_flex;
+            }
+            let h = if let Some(f) = entry.config.fill_h {
+                bounds.size.height * f
+            } else {
+                rect.size.height
+            };
+            let y = if entry.config.alignment == layout::Alignment::Baseline {
+                bounds.origin.y + ascent - baseline(entry)
+            } else {
+                layout::align_y(rect, bounds, entry.config.alignment, 0.0)
+            };
+            let given = gfx::Size::new(w, h);
+            entry.stretched = if given != size {
+                Some((given, size))
+            } else {
+                None
+            };
+            entry
+                .item
+                .set_rect(gfx::Rect::new(gfx::Point::new(x, y), given));
+            x += w + entry.config.right_margin;
         }
     }
 }
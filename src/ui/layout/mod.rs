@@ -1,9 +1,22 @@
 pub mod hstack;
+pub mod padded;
 pub mod relative_box;
+pub mod spacer;
+#[cfg(feature = "spec")]
+pub mod spec;
+pub mod table;
 pub mod vfill;
+pub mod virtualized;
 pub mod vstack;
+pub mod zstack;
 
-pub use {hstack::*, relative_box::*, vfill::*, vstack::*};
+pub use {
+    hstack::*, padded::*, relative_box::*, spacer::*, table::*, vfill::*, virtualized::*,
+    vstack::*, zstack::*,
+};
+
+#[cfg(feature = "spec")]
+pub use spec::{from_spec, LayoutSpec, SpecError};
 
 use {
     crate::{prelude::*, ui},
@@ -37,6 +50,24 @@ impl Item {
             Item::Layout(l) => l.0.rect(),
         }
     }
+
+    /// Returns the size this item should be laid out with.
+    ///
+    /// This is the widget's [preferred size](ui::Common::set_preferred_size) if it has one, otherwise the current size.
+    pub fn preferred_size(&self) -> gfx::Size {
+        match self {
+            Item::Widget(w) => w.with(|x| x.preferred_size().unwrap_or_else(|| x.size())),
+            Item::Layout(l) => l.0.rect().size,
+        }
+    }
+
+    /// Returns the distance from the top of the item to the baseline of its text, if it is a widget with text.
+    pub fn baseline(&self) -> Option<f32> {
+        match self {
+            Item::Widget(w) => w.with(|x| x.baseline()),
+            Item::Layout(_) => None,
+        }
+    }
 }
 
 impl<E: Element> From<&E> for Item {
@@ -227,6 +258,10 @@ impl Downcast for dyn DynNode {}
 pub struct DynamicNode(pub(crate) Box<dyn DynNode>);
 
 impl DynamicNode {
+    pub fn cast<L: Layout>(&self) -> Option<&Node<L>> {
+        self.0.as_ref().downcast_ref::<Node<L>>()
+    }
+
     pub fn cast_mut<L: Layout>(&mut self) -> Option<&mut Node<L>> {
         self.0.as_mut().downcast_mut::<Node<L>>()
     }
@@ -259,6 +294,50 @@ fn update_layout_impl<T: 'static>(widget: &dyn WidgetChildren<T>) {
     }
 }
 
+/// Updates the layouts affected by [invalidated](ui::Common::invalidate_layout) widgets within `widget`.
+///
+/// Only the smallest subtrees which contain the changes are updated; a change propagates up to the nearest ancestor
+/// which has a layout and whose size doesn't depend on its content (i.e. [`LayoutMode::Fill`](ui::LayoutMode::Fill)),
+/// or otherwise up to `widget`.
+pub fn update_dirty_layouts<T: 'static>(widget: &dyn WidgetChildren<T>) {
+    if update_dirty_layouts_impl(widget) {
+        update_layout(widget);
+        clear_dirty(widget);
+    }
+}
+
+/// Returns `true` if the changes within `widget` have to be handled further up.
+fn update_dirty_layouts_impl<T: 'static>(widget: &dyn WidgetChildren<T>) -> bool {
+    let mut dirty = widget
+        .common()
+        .with(|x| std::mem::replace(&mut x.layout_dirty, false));
+    for child in widget.children() {
+        dirty |= update_dirty_layouts_impl(child);
+    }
+
+    if !dirty {
+        return false;
+    }
+
+    let contained = widget
+        .common()
+        .with(|x| x.layout.is_some() && x.layout_mode() == ui::LayoutMode::Fill);
+    if contained {
+        update_layout(widget);
+        clear_dirty(widget);
+    }
+
+    !contained
+}
+
+/// Clears the changes made by updating the layouts of `widget`, so that they aren't mistaken for new changes.
+fn clear_dirty<T: 'static>(widget: &dyn WidgetChildren<T>) {
+    widget.common().with(|x| x.layout_dirty = false);
+    for child in widget.children() {
+        clear_dirty(child);
+    }
+}
+
 fn resize_layout<T: 'static>(widget: &dyn WidgetChildren<T>) {
     for child in widget.children() {
         resize_layout(child);
@@ -274,11 +353,129 @@ fn resize_layout<T: 'static>(widget: &dyn WidgetChildren<T>) {
 
 pub type SideMargins = reclutch::euclid::SideOffsets2D<f32, reclutch::euclid::UnknownUnit>;
 
+/// A margin which is either a fixed length or relative to the size of the parent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spec", derive(serde::Deserialize))]
+pub enum Margin {
+    Fixed(f32),
+    /// Fraction of the parent's width (for left and right margins) or height (for top and bottom margins), e.g. `0.1` for 10%.
+    Percent(f32),
+}
+
+impl Margin {
+    /// Returns the length of the margin within a parent dimension of `parent`.
+    pub fn resolve(self, parent: f32) -> f32 {
+        match self {
+            Margin::Fixed(x) => x,
+            Margin::Percent(x) => parent * x,
+        }
+    }
+
+    fn fixed(self) -> f32 {
+        match self {
+            Margin::Fixed(x) => x,
+            Margin::Percent(_) => 0.0,
+        }
+    }
+
+    fn percent(self) -> f32 {
+        match self {
+            Margin::Fixed(_) => 0.0,
+            Margin::Percent(x) => x,
+        }
+    }
+}
+
+impl Default for Margin {
+    #[inline]
+    fn default() -> Self {
+        Margin::Fixed(0.0)
+    }
+}
+
+impl From<f32> for Margin {
+    #[inline]
+    fn from(x: f32) -> Self {
+        Margin::Fixed(x)
+    }
+}
+
+/// Margins on each side, which are [resolved](Margins::resolve) into [`SideMargins`](SideMargins) once the size of the parent is known.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spec", derive(serde::Deserialize), serde(default))]
+pub struct Margins {
+    pub top: Margin,
+    pub right: Margin,
+    pub bottom: Margin,
+    pub left: Margin,
+}
+
+impl Margins {
+    pub fn new(
+        top: impl Into<Margin>,
+        right: impl Into<Margin>,
+        bottom: impl Into<Margin>,
+        left: impl Into<Margin>,
+    ) -> Self {
+        Margins {
+            top: top.into(),
+            right: right.into(),
+            bottom: bottom.into(),
+            left: left.into(),
+        }
+    }
+
+    pub fn new_all_same(margin: impl Into<Margin>) -> Self {
+        let margin = margin.into();
+        Margins::new(margin, margin, margin, margin)
+    }
+
+    /// Returns the lengths of the margins within a parent of size `parent`.
+    pub fn resolve(&self, parent: gfx::Size) -> SideMargins {
+        SideMargins::new(
+            self.top.resolve(parent.height),
+            self.right.resolve(parent.width),
+            self.bottom.resolve(parent.height),
+            self.left.resolve(parent.width),
+        )
+    }
+
+    /// Returns the smallest parent size which leaves `size` within the margins.
+    pub fn expand(&self, size: gfx::Size) -> gfx::Size {
+        gfx::Size::new(
+            expand(size.width, self.left, self.right),
+            expand(size.height, self.top, self.bottom),
+        )
+    }
+}
+
+impl From<SideMargins> for Margins {
+    fn from(margins: SideMargins) -> Self {
+        Margins::new(margins.top, margins.right, margins.bottom, margins.left)
+    }
+}
+
+/// Returns the smallest length which leaves `length` between the margins `a` and `b`.
+fn expand(length: f32, a: Margin, b: Margin) -> f32 {
+    let fixed = length + a.fixed() + b.fixed();
+    let percent = a.percent() + b.percent();
+    if percent < 1.0 {
+        fixed / (1.0 - percent)
+    } else {
+        fixed
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "spec", derive(serde::Deserialize))]
 pub enum Alignment {
     Begin,
     Middle,
     End,
+    /// Aligns the [baselines](ui::Common::baseline) of text across items (widgets without text are aligned by their bottom edge).
+    ///
+    /// This is supported vertically by [`HStack`](HStack); elsewhere it is the same as `Begin`.
+    Baseline,
 }
 
 impl Default for Alignment {
@@ -290,7 +487,7 @@ impl Default for Alignment {
 
 pub fn align_x(inner: gfx::Rect, outer: gfx::Rect, align: Alignment, padding: f32) -> f32 {
     match align {
-        Alignment::Begin => outer.origin.x + padding,
+        Alignment::Begin | Alignment::Baseline => outer.origin.x + padding,
         Alignment::Middle => gfx::center_horizontally(inner, outer).x,
         Alignment::End => outer.max_x() - inner.size.width - padding,
     }
@@ -298,7 +495,7 @@ pub fn align_x(inner: gfx::Rect, outer: gfx::Rect, align: Alignment, padding: f3
 
 pub fn align_y(inner: gfx::Rect, outer: gfx::Rect, align: Alignment, padding: f32) -> f32 {
     match align {
-        Alignment::Begin => outer.origin.y + padding,
+        Alignment::Begin | Alignment::Baseline => outer.origin.y + padding,
         Alignment::Middle => gfx::center_vertically(inner, outer).y,
         Alignment::End => outer.max_y() - inner.size.height - padding,
     }
@@ -0,0 +1,93 @@
+use {crate::ui::layout, reclutch::display as gfx};
+
+/// Insets a single child by margins on each side, filling the rest of the space.
+///
+/// Being a layout node itself, this can be nested in any other layout so that padding doesn't have to be part of that layout's configuration.
+pub struct Padded {
+    item: Option<layout::Item>,
+    margins: layout::Margins,
+}
+
+impl Padded {
+    pub fn new(margins: impl Into<layout::Margins>) -> Self {
+        Padded {
+            item: None,
+            margins: margins.into(),
+        }
+    }
+
+    /// Creates a `Padded` with the same margin on all sides.
+    #[inline]
+    pub fn uniform(margin: impl Into<layout::Margin>) -> Self {
+        Padded::new(layout::Margins::new_all_same(margin))
+    }
+
+    #[inline]
+    pub fn set_margins(&mut self, margins: impl Into<layout::Margins>) {
+        self.margins = margins.into();
+    }
+
+    #[inline]
+    pub fn margins(&self) -> layout::Margins {
+        self.margins
+    }
+}
+
+impl layout::Layout for Padded {
+    type Config = ();
+    type Id = ();
+
+    #[inline]
+    fn push(&mut self, item: impl Into<layout::Item>, _: ()) {
+        self.item = Some(item.into());
+    }
+
+    #[inline]
+    fn remove(&mut self, _: &()) -> Option<layout::Item> {
+        self.item.take()
+    }
+
+    #[inline]
+    fn get(&self, _: &()) -> Option<&layout::Item> {
+        self.item.as_ref()
+    }
+
+    #[inline]
+    fn get_mut(&mut self, _: &()) -> Option<&mut layout::Item> {
+        self.item.as_mut()
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.item.is_some() as _
+    }
+
+    fn items(&self) -> Vec<(&layout::Item, &Self::Id)> {
+        if let Some(item) = &self.item {
+            vec![(item, &())]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn min_size(&self) -> gfx::Size {
+        let size = match &self.item {
+            Some(item) if layout::should_layout(item) => item.preferred_size(),
+            _ => Default::default(),
+        };
+        self.margins.expand(size)
+    }
+
+    fn update(&mut self, bounds: gfx::Rect) {
+        if let Some(item) = &mut self.item {
+            if !layout::should_layout(item) {
+                return;
+            }
+
+            let mut rect = bounds.inner_rect(self.margins.resolve(bounds.size));
+            rect.size.width = rect.size.width.max(0.);
+            rect.size.height = rect.size.height.max(0.);
+            item.set_rect(rect);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use {crate::ui::layout, reclutch::display as gfx};
+use {crate::ui::layout, reclutch::display as gfx, std::collections::BTreeMap};
 
 /// A 2D position based on a relative offset and an absolute offset.
 /// The `relative` offset is expressed as a fraction of the corresponding parent dimension for each component in `(x, y)`.
@@ -9,9 +9,11 @@ use {crate::ui::layout, reclutch::display as gfx};
 /// placed within a parent of size `100 x 100` positioned at `(50, 50)` will result in an absolute position of `(60, 90)` (or relatively; `(10, 40)`).
 /// Following the calculation; `100 * 0.3 = 30, + 50 = 80, + 5 = 85, - 0.5 * 50 = 60` and `100 * 0.1 = 10, + 50 = 60, + 30 = 90`.
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spec", derive(serde::Deserialize), serde(default))]
 pub struct FractionalPosition {
     pub relative: (f32, f32),
     pub post_relative: (f32, f32),
+    #[cfg_attr(feature = "spec", serde(deserialize_with = "layout::spec::vector"))]
     pub real: gfx::Vector,
 }
 
@@ -23,9 +25,11 @@ pub struct FractionalPosition {
 /// For example, `FractionalSize { relative: (0.5, 0.75), post_relative: (0.6, -0.2), real: Size::new(15, 10) }` placed within a parent
 /// of size `100 x 100` will result in a size of `120 x 68`, because `100 * 0.5 = 50, + 15 = 75, + 0.6 * 75 = 120` and `100 * 0.75 = 75, + 10 = 85, - 0.2 * 85 = 68`.
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spec", derive(serde::Deserialize), serde(default))]
 pub struct FractionalSize {
     pub relative: (f32, f32),
     pub post_relative: (f32, f32),
+    #[cfg_attr(feature = "spec", serde(deserialize_with = "layout::spec::size"))]
     pub real: gfx::Size,
 }
 
@@ -33,6 +37,7 @@ pub struct FractionalSize {
 ///
 /// If `size` is left as `None`, then the size of the child will be used.
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spec", derive(serde::Deserialize), serde(default))]
 pub struct RelativeBoxConfig {
     pub position: FractionalPosition,
     pub size: Option<FractionalSize>,
@@ -53,7 +58,7 @@ impl RelativeBoxConfig {
 }
 
 /// Powerful layout based on quantities relative to the parent.
-/// This only supports a single child.
+/// Each child is placed according to its own `RelativeBoxConfig`.
 ///
 /// This layout is very useful for any of the following scenarios;
 /// - Centering an item.
@@ -62,74 +67,98 @@ impl RelativeBoxConfig {
 ///
 /// These are all scenarios which can be expressed as screen-space fractions.
 pub struct RelativeBox {
-    item: Option<layout::Item>,
+    entries: BTreeMap<u64, Item>,
+    next_id: u64,
+}
+
+struct Item {
     config: RelativeBoxConfig,
+    item: layout::Item,
 }
 
 impl RelativeBox {
-    pub fn new(config: RelativeBoxConfig) -> Self {
-        RelativeBox { item: None, config }
+    pub fn new() -> Self {
+        RelativeBox {
+            entries: Default::default(),
+            next_id: 0,
+        }
     }
 }
 
 impl layout::Layout for RelativeBox {
-    type Config = ();
-    type Id = ();
-
-    #[inline]
-    fn push(&mut self, item: impl Into<layout::Item>, _: ()) {
-        self.item = Some(item.into());
+    type Config = RelativeBoxConfig;
+    type Id = u64;
+
+    fn push(&mut self, item: impl Into<layout::Item>, config: RelativeBoxConfig) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.entries.insert(
+            id,
+            Item {
+                config,
+                item: item.into(),
+            },
+        );
+        id
     }
 
     #[inline]
-    fn remove(&mut self, _: &()) -> Option<layout::Item> {
-        self.item.take()
+    fn remove(&mut self, id: &u64) -> Option<layout::Item> {
+        self.entries.remove(id).map(|x| x.item)
     }
 
     #[inline]
-    fn get(&self, _: &()) -> Option<&layout::Item> {
-        self.item.as_ref()
+    fn get(&self, id: &u64) -> Option<&layout::Item> {
+        Some(&self.entries.get(id)?.item)
     }
 
     #[inline]
-    fn get_mut(&mut self, _: &()) -> Option<&mut layout::Item> {
-        self.item.as_mut()
+    fn get_mut(&mut self, id: &u64) -> Option<&mut layout::Item> {
+        Some(&mut self.entries.get_mut(id)?.item)
     }
 
     #[inline]
     fn len(&self) -> usize {
-        self.item.is_some() as _
+        self.entries.len()
     }
 
-    fn items(&self) -> Vec<(&layout::Item, &Self::Id)> {
-        if let Some(item) = &self.item {
-            vec![(item, &())]
-        } else {
-            Vec::new()
-        }
+    fn items(&self) -> Vec<(&layout::Item, &u64)> {
+        self.entries
+            .values()
+            .map(|x| &x.item)
+            .zip(self.entries.keys())
+            .collect()
     }
 
     fn min_size(&self) -> gfx::Size {
-        if let Some(item) = &self.item {
-            if !layout::should_layout(item) {
-                Default::default()
-            } else {
-                item.rect().size
+        let mut width = 0.0;
+        let mut height = 0.0;
+        for entry in self.entries.values() {
+            if !layout::should_layout(&entry.item) {
+                continue;
+            }
+
+            let size = entry.item.preferred_size();
+            if size.width > width {
+                width = size.width;
+            }
+            if size.height > height {
+                height = size.height;
             }
-        } else {
-            Default::default()
         }
+        gfx::Size::new(width, height)
     }
 
     fn update(&mut self, bounds: gfx::Rect) {
-        if let Some(item) = &mut self.item {
+        for entry in self.entries.values_mut() {
+            let item = &mut entry.item;
             if !layout::should_layout(item) {
-                return;
+                continue;
             }
 
             let mut rect = item.rect();
-            let position = self.config.position;
-            rect.size = if let Some(size) = self.config.size {
+            let position = entry.config.position;
+            rect.size = if let Some(size) = entry.config.size {
                 let mut new_size = gfx::Size::new(
                     bounds.size.width * size.relative.0,
                     bounds.size.height * size.relative.1,
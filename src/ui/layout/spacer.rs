@@ -0,0 +1,85 @@
+use {crate::ui::layout, reclutch::display as gfx};
+
+/// Empty space between the items of a stack ([`HStack`](layout::HStack) or [`VStack`](layout::VStack)).
+///
+/// A spacer takes up `size` along the direction of the stack, plus a share of the leftover space according to its `flex` weight,
+/// in the same way as an item with that [`flex`](layout::HStackConfig::flex) configured.
+///
+/// Spacers have no items of their own; anything pushed to one is discarded.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Spacer {
+    size: f32,
+    flex: f32,
+}
+
+impl Spacer {
+    /// Creates a spacer which is always `size` long.
+    pub fn fixed(size: f32) -> Self {
+        Spacer { size, flex: 0.0 }
+    }
+
+    /// Creates a spacer which takes up a share of the leftover space by the weight of `flex`.
+    pub fn flexible(flex: f32) -> Self {
+        Spacer { size: 0.0, flex }
+    }
+
+    #[inline]
+    pub fn size(&self) -> f32 {
+        self.size
+    }
+
+    #[inline]
+    pub fn flex(&self) -> f32 {
+        self.flex
+    }
+}
+
+impl layout::Layout for Spacer {
+    type Config = ();
+    type Id = ();
+
+    #[inline]
+    fn push(&mut self, _item: impl Into<layout::Item>, _: ()) {}
+
+    #[inline]
+    fn remove(&mut self, _: &()) -> Option<layout::Item> {
+        None
+    }
+
+    #[inline]
+    fn get(&self, _: &()) -> Option<&layout::Item> {
+        None
+    }
+
+    #[inline]
+    fn get_mut(&mut self, _: &()) -> Option<&mut layout::Item> {
+        None
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        0
+    }
+
+    #[inline]
+    fn items(&self) -> Vec<(&layout::Item, &())> {
+        Vec::new()
+    }
+
+    /// Stacks measure spacers along their own direction, so spacers don't have a size otherwise.
+    #[inline]
+    fn min_size(&self) -> gfx::Size {
+        Default::default()
+    }
+
+    #[inline]
+    fn update(&mut self, _bounds: gfx::Rect) {}
+}
+
+/// Returns the spacer which `item` is, if it is one.
+pub(crate) fn as_spacer(item: &layout::Item) -> Option<Spacer> {
+    match item {
+        layout::Item::Layout(node) => node.cast::<Spacer>().map(|x| **x),
+        layout::Item::Widget(_) => None,
+    }
+}
@@ -0,0 +1,157 @@
+use {
+    crate::ui::layout::{self, Layout},
+    reclutch::display as gfx,
+    serde::Deserialize,
+    std::collections::HashMap,
+};
+
+/// Description of a layout tree, which can be deserialized (e.g. from RON or JSON) and built with [`build`](LayoutSpec::build).
+///
+/// For example, in RON;
+/// ```text
+/// VStack(spacing: 5.0, items: [
+///     (item: Slot("title")),
+///     (item: HStack(items: [
+///         (item: Slot("input"), config: (flex: 1.0)),
+///         (item: Slot("add"), config: (left_margin: 5.0)),
+///     ])),
+/// ])
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub enum LayoutSpec {
+    /// An item bound by name when the layout is built.
+    Slot(String),
+    HStack {
+        #[serde(default)]
+        spacing: f32,
+        #[serde(default)]
+        items: Vec<Entry<layout::HStackConfig>>,
+    },
+    VStack {
+        #[serde(default)]
+        spacing: f32,
+        #[serde(default)]
+        items: Vec<Entry<layout::VStackConfig>>,
+    },
+    Table {
+        #[serde(default)]
+        columns: Vec<layout::TableColumn>,
+        #[serde(default)]
+        column_spacing: f32,
+        #[serde(default)]
+        row_spacing: f32,
+        #[serde(default)]
+        items: Vec<Entry<layout::TableConfig>>,
+    },
+    RelativeBox {
+        #[serde(default)]
+        items: Vec<Entry<layout::RelativeBoxConfig>>,
+    },
+    Padded {
+        margins: layout::Margins,
+        item: Box<LayoutSpec>,
+    },
+    FixedSpacer(f32),
+    FlexibleSpacer(f32),
+}
+
+/// An item of a [`LayoutSpec`](LayoutSpec) along with its configuration within the parent layout.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(bound(deserialize = "C: Deserialize<'de> + Default"))]
+pub struct Entry<C> {
+    pub item: LayoutSpec,
+    #[serde(default)]
+    pub config: C,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SpecError {
+    #[error("Failed to parse layout description: {0}")]
+    Parse(#[from] ron::Error),
+    #[error("No item was given for the slot \"{0}\"")]
+    MissingSlot(String),
+    #[error("The root of a layout description must be a layout rather than a slot")]
+    RootSlot,
+}
+
+impl LayoutSpec {
+    /// Builds the layout tree, taking the item for each slot out of `slots`.
+    ///
+    /// Slots which aren't referred to are left in `slots`.
+    pub fn build(self, slots: &mut HashMap<&str, layout::Item>) -> Result<layout::Item, SpecError> {
+        Ok(match self {
+            LayoutSpec::Slot(name) => match slots.remove(name.as_str()) {
+                Some(item) => item,
+                None => return Err(SpecError::MissingSlot(name)),
+            },
+            LayoutSpec::HStack { spacing, items } => {
+                let mut node = layout::HStack::with_spacing(spacing).into_node(None);
+                for entry in items {
+                    node.push(entry.item.build(slots)?, Some(entry.config));
+                }
+                node.into()
+            }
+            LayoutSpec::VStack { spacing, items } => {
+                let mut node = layout::VStack::with_spacing(spacing).into_node(None);
+                for entry in items {
+                    node.push(entry.item.build(slots)?, Some(entry.config));
+                }
+                node.into()
+            }
+            LayoutSpec::Table {
+                columns,
+                column_spacing,
+                row_spacing,
+                items,
+            } => {
+                let mut table = layout::Table::new(columns);
+                table.set_spacing(column_spacing, row_spacing);
+                let mut node = table.into_node(None);
+                for entry in items {
+                    node.push(entry.item.build(slots)?, entry.config);
+                }
+                node.into()
+            }
+            LayoutSpec::RelativeBox { items } => {
+                let mut node = layout::RelativeBox::new().into_node(None);
+                for entry in items {
+                    node.push(entry.item.build(slots)?, entry.config);
+                }
+                node.into()
+            }
+            LayoutSpec::Padded { margins, item } => {
+                let mut node = layout::Padded::new(margins).into_node(None);
+                node.push(item.build(slots)?, ());
+                node.into()
+            }
+            LayoutSpec::FixedSpacer(size) => layout::Spacer::fixed(size).into_node(None).into(),
+            LayoutSpec::FlexibleSpacer(flex) => {
+                layout::Spacer::flexible(flex).into_node(None).into()
+            }
+        })
+    }
+}
+
+/// Parses a layout tree from a RON description of a [`LayoutSpec`](LayoutSpec) and builds it, binding the named slots to `slots`.
+///
+/// The result can be given to a widget through [`set_dynamic_layout`](crate::ui::Common::set_dynamic_layout).
+pub fn from_spec(
+    spec: &str,
+    mut slots: HashMap<&str, layout::Item>,
+) -> Result<layout::DynamicNode, SpecError> {
+    let spec: LayoutSpec = ron::de::from_str(spec)?;
+    match spec.build(&mut slots)? {
+        layout::Item::Layout(node) => Ok(node),
+        layout::Item::Widget(_) => Err(SpecError::RootSlot),
+    }
+}
+
+pub(crate) fn vector<'de, D: serde::Deserializer<'de>>(d: D) -> Result<gfx::Vector, D::Error> {
+    let (x, y) = <(f32, f32)>::deserialize(d)?;
+    Ok(gfx::Vector::new(x, y))
+}
+
+pub(crate) fn size<'de, D: serde::Deserializer<'de>>(d: D) -> Result<gfx::Size, D::Error> {
+    let (width, height) = <(f32, f32)>::deserialize(d)?;
+    Ok(gfx::Size::new(width, height))
+}
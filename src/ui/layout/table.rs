@@ -0,0 +1,423 @@
+use {crate::ui::layout, reclutch::display as gfx, std::collections::BTreeMap};
+
+/// How the width of a `Table` column is decided.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "spec", derive(serde::Deserialize))]
+pub enum TableColumn {
+    /// As wide as the widest cell in the column.
+    Auto,
+    /// A fixed width.
+    Fixed(f32),
+    /// A fraction of the width of the table (e.g. `0.25` for a quarter).
+    Fraction(f32),
+}
+
+impl Default for TableColumn {
+    #[inline]
+    fn default() -> Self {
+        TableColumn::Auto
+    }
+}
+
+/// Configuration of a cell in a `Table`.
+///
+/// The cell covers `row_span` rows and `column_span` columns (a span of 0 is treated as 1), starting at `row` and `column`.
+/// Unless `fill` is set, the item keeps its own size and is aligned within the cell.
+///
+/// If `auto` is set, `row` and `column` are ignored and the cell is instead placed in the next free cell when it's pushed (see [`Table`](Table)).
+#[derive(Default, Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "spec", derive(serde::Deserialize), serde(default))]
+pub struct TableConfig {
+    pub row: usize,
+    pub column: usize,
+    pub row_span: usize,
+    pub column_span: usize,
+    pub horizontal_alignment: layout::Alignment,
+    pub vertical_alignment: layout::Alignment,
+    pub fill: bool,
+    pub auto: bool,
+}
+
+impl TableConfig {
+    /// Returns the configuration of a single cell at `row` and `column`.
+    pub fn cell(row: usize, column: usize) -> Self {
+        TableConfig {
+            row,
+            column,
+            row_span: 1,
+            column_span: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the configuration of a single cell placed automatically in the next free cell.
+    pub fn auto() -> Self {
+        TableConfig {
+            auto: true,
+            ..TableConfig::cell(0, 0)
+        }
+    }
+
+    /// Makes the cell span `rows` rows and `columns` columns.
+    pub fn span(self, rows: usize, columns: usize) -> Self {
+        TableConfig {
+            row_span: rows,
+            column_span: columns,
+            ..self
+        }
+    }
+}
+
+impl From<(usize, usize)> for TableConfig {
+    #[inline]
+    fn from(cell: (usize, usize)) -> Self {
+        TableConfig::cell(cell.0, cell.1)
+    }
+}
+
+struct Item {
+    config: TableConfig,
+    item: layout::Item,
+}
+
+/// Grows `sizes` (of rows or columns) to fit `cells`, given as `(start, span, size)`.
+///
+/// Cells within a single row/column are fitted first, then any spanning cells which still don't fit share the difference
+/// between their `growable` rows/columns.
+fn fit(
+    sizes: &mut [f32],
+    cells: &[(usize, usize, f32)],
+    growable: impl Fn(usize) -> bool,
+    spacing: f32,
+) {
+    for &(start, span, size) in cells {
+        if span == 1 && growable(start) {
+            sizes[start] = sizes[start].max(size);
+        }
+    }
+
+    for &(start, span, size) in cells {
+        if span == 1 {
+            continue;
+        }
+
+        let range = start..start + span;
+        let current = sizes[range.clone()].iter().sum::<f32>() + spacing * (span - 1) as f32;
+        let targets: Vec<usize> = range.filter(|&x| growable(x)).collect();
+        if size > current && !targets.is_empty() {
+            let extra = (size - current) / targets.len() as f32;
+            for i in targets {
+                sizes[i] += extra;
+            }
+        }
+    }
+}
+
+/// Grid of rows and columns, where each row is as tall as its tallest cell and each column is sized according to its [`TableColumn`](TableColumn).
+///
+/// Cells may span multiple rows and columns, making this suitable for property grids and forms.
+///
+/// Cells can also be [placed automatically](TableConfig::auto), flowing left to right across the columns given to [`new`](Table::new)
+/// and then onto new rows as needed, which suits galleries and other uniform grids.
+/// Each automatically placed cell goes in the first free cell after the previous one.
+pub struct Table {
+    entries: BTreeMap<u64, Item>,
+    next_id: u64,
+    cursor: (usize, usize),
+    columns: Vec<TableColumn>,
+    column_spacing: f32,
+    row_spacing: f32,
+}
+
+impl Table {
+    /// Creates a table with the given column widths; columns beyond these are [`Auto`](TableColumn::Auto).
+    pub fn new(columns: Vec<TableColumn>) -> Self {
+        Table {
+            entries: Default::default(),
+            next_id: 0,
+            cursor: (0, 0),
+            columns,
+            column_spacing: 0.,
+            row_spacing: 0.,
+        }
+    }
+
+    /// Changes the space between adjacent columns and adjacent rows.
+    pub fn set_spacing(&mut self, column_spacing: f32, row_spacing: f32) {
+        self.column_spacing = column_spacing;
+        self.row_spacing = row_spacing;
+    }
+
+    #[inline]
+    pub fn spacing(&self) -> (f32, f32) {
+        (self.column_spacing, self.row_spacing)
+    }
+
+    #[inline]
+    pub fn column(&self, column: usize) -> TableColumn {
+        self.columns.get(column).copied().unwrap_or_default()
+    }
+
+    /// Returns `true` if any cell overlaps the `row_span` by `column_span` cells starting at `row` and `column`.
+    fn occupied(&self, row: usize, column: usize, row_span: usize, column_span: usize) -> bool {
+        self.entries.values().any(|x| {
+            let config = x.config;
+            config.row < row + row_span
+                && row < config.row + config.row_span.max(1)
+                && config.column < column + column_span
+                && column < config.column + config.column_span.max(1)
+        })
+    }
+
+    /// Places an automatic cell at the first free position after the previous one, as `(row, column)`.
+    fn flow(&mut self, row_span: usize, column_span: usize) -> (usize, usize) {
+        let columns = self.columns.len().max(1);
+        let column_span = column_span.min(columns);
+        let (mut row, mut column) = self.cursor;
+        loop {
+            if column + column_span > columns {
+                row += 1;
+                column = 0;
+            } else if self.occupied(row, column, row_span, column_span) {
+                column += 1;
+            } else {
+                self.cursor = (row, column + column_span);
+                return (row, column);
+            }
+        }
+    }
+
+    /// Returns the column widths and row heights, with fractional columns resolved against `width` if there is one.
+    fn measure(&self, width: Option<f32>) -> (Vec<f32>, Vec<f32>) {
+        let entries: Vec<(TableConfig, gfx::Size)> = self
+            .entries
+            .values()
+            .filter(|x| layout::should_layout(&x.item))
+            .map(|x| {
+                let mut config = x.config;
+                config.row_span = config.row_span.max(1);
+                config.column_span = config.column_span.max(1);
+                (config, x.item.preferred_size())
+            })
+            .collect();
+
+        let column_count = entries
+            .iter()
+            .map(|(x, _)| x.column + x.column_span)
+            .max()
+            .unwrap_or(0)
+            .max(self.columns.len());
+        let row_count = entries
+            .iter()
+            .map(|(x, _)| x.row + x.row_span)
+            .max()
+            .unwrap_or(0);
+
+        let mut widths: Vec<f32> = (0..column_count)
+            .map(|i| match (self.column(i), width) {
+                (TableColumn::Fixed(w), _) => w,
+                (TableColumn::Fraction(f), Some(width)) => width * f,
+                _ => 0.,
+            })
+            .collect();
+        let columns: Vec<(usize, usize, f32)> = entries
+            .iter()
+            .map(|(x, size)| (x.column, x.column_span, size.width))
+            .collect();
+        fit(
+            &mut widths,
+            &columns,
+            // without a width to take a fraction of, fractional columns are measured like automatic ones.
+            |i| match self.column(i) {
+                TableColumn::Auto => true,
+                TableColumn::Fraction(_) => width.is_none(),
+                TableColumn::Fixed(_) => false,
+            },
+            self.column_spacing,
+        );
+
+        let mut heights = vec![0.; row_count];
+        let rows: Vec<(usize, usize, f32)> = entries
+            .iter()
+            .map(|(x, size)| (x.row, x.row_span, size.height))
+            .collect();
+        fit(&mut heights, &rows, |_| true, self.row_spacing);
+
+        (widths, heights)
+    }
+}
+
+impl layout::Layout for Table {
+    type Config = TableConfig;
+    type Id = u64;
+
+    fn push(&mut self, item: impl Into<layout::Item>, mut config: TableConfig) -> u64 {
+        if config.auto {
+            // a cell can't flow across more columns than there are.
+            config.column_span = config.column_span.max(1).min(self.columns.len().max(1));
+            let position = self.flow(config.row_span.max(1), config.column_span);
+            config.row = position.0;
+            config.column = position.1;
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.entries.insert(
+            id,
+            Item {
+                config,
+                item: item.into(),
+            },
+        );
+        id
+    }
+
+    #[inline]
+    fn remove(&mut self, id: &u64) -> Option<layout::Item> {
+        self.entries.remove(id).map(|x| x.item)
+    }
+
+    #[inline]
+    fn get(&self, id: &u64) -> Option<&layout::Item> {
+        Some(&self.entries.get(id)?.item)
+    }
+
+    #[inline]
+    fn get_mut(&mut self, id: &u64) -> Option<&mut layout::Item> {
+        Some(&mut self.entries.get_mut(id)?.item)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn items(&self) -> Vec<(&layout::Item, &u64)> {
+        self.entries
+            .values()
+            .map(|x| &x.item)
+            .zip(self.entries.keys())
+            .collect()
+    }
+
+    fn min_size(&self) -> gfx::Size {
+        let (widths, heights) = self.measure(None);
+        let total = |sizes: &[f32], spacing: f32| {
+            sizes.iter().sum::<f32>() + spacing * sizes.len().saturating_sub(1) as f32
+        };
+        gfx::Size::new(
+            total(&widths, self.column_spacing),
+            total(&heights, self.row_spacing),
+        )
+    }
+
+    fn update(&mut self, bounds: gfx::Rect) {
+        let (widths, heights) = self.measure(Some(bounds.size.width));
+
+        // the start of each column and row, plus the end of the last.
+        let offsets = |sizes: &[f32], origin: f32, spacing: f32| {
+            let mut offsets = vec![origin];
+            for size in sizes {
+                offsets.push(offsets.last().unwrap() + size + spacing);
+            }
+            offsets
+        };
+        let xs = offsets(&widths, bounds.origin.x, self.column_spacing);
+        let ys = offsets(&heights, bounds.origin.y, self.row_spacing);
+
+        for entry in self.entries.values_mut() {
+            if !layout::should_layout(&entry.item) {
+                continue;
+            }
+
+            let config = entry.config;
+            let end_column = config.column + config.column_span.max(1);
+            let end_row = config.row + config.row_span.max(1);
+            let cell = gfx::Rect::new(
+                gfx::Point::new(xs[config.column], ys[config.row]),
+                gfx::Size::new(
+                    xs[end_column] - xs[config.column] - self.column_spacing,
+                    ys[end_row] - ys[config.row] - self.row_spacing,
+                ),
+            );
+
+            if config.fill {
+                entry.item.set_rect(cell);
+            } else {
+                let rect = gfx::Rect::new(Default::default(), entry.item.preferred_size());
+                entry.item.set_rect(gfx::Rect::new(
+                    gfx::Point::new(
+                        layout::align_x(rect, cell, config.horizontal_alignment, 0.0),
+                        layout::align_y(rect, cell, config.vertical_alignment, 0.0),
+                    ),
+                    rect.size,
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::ui, crate::ui::layout::Layout};
+
+    fn cell(width: f32, height: f32) -> ui::CommonRef {
+        let common = ui::CommonRef::new(None);
+        common.with(|x| x.set_preferred_size(gfx::Size::new(width, height)));
+        common
+    }
+
+    fn position(table: &Table, id: u64) -> (usize, usize) {
+        let config = table.entries[&id].config;
+        (config.row, config.column)
+    }
+
+    #[test]
+    fn flow_around_spanning_cells() {
+        let mut table = Table::new(vec![TableColumn::Auto; 3]);
+        table.push(cell(10., 10.), TableConfig::cell(0, 1).span(2, 1));
+
+        let ids: Vec<u64> = (0..5)
+            .map(|_| table.push(cell(10., 10.), TableConfig::auto()))
+            .collect();
+        let positions: Vec<_> = ids.iter().map(|&id| position(&table, id)).collect();
+        assert_eq!(positions, vec![(0, 0), (0, 2), (1, 0), (1, 2), (2, 0)]);
+    }
+
+    #[test]
+    fn flow_clamps_wide_spans() {
+        let mut table = Table::new(vec![TableColumn::Auto; 2]);
+        let first = table.push(cell(10., 10.), TableConfig::auto());
+        let wide = table.push(cell(10., 10.), TableConfig::auto().span(1, 5));
+        let last = table.push(cell(10., 10.), TableConfig::auto());
+
+        assert_eq!(position(&table, first), (0, 0));
+        assert_eq!(position(&table, wide), (1, 0));
+        assert_eq!(table.entries[&wide].config.column_span, 2);
+        assert_eq!(position(&table, last), (2, 0));
+        assert_eq!(table.measure(None).0.len(), 2);
+    }
+
+    #[test]
+    fn measure_fractional_columns() {
+        let mut table = Table::new(vec![TableColumn::Fraction(0.5), TableColumn::Auto]);
+        table.push(cell(40., 10.), TableConfig::cell(0, 0));
+        table.push(cell(30., 20.), TableConfig::cell(0, 1));
+
+        // without a width, fractional columns are as wide as their cells.
+        assert_eq!(table.measure(None), (vec![40., 30.], vec![20.]));
+        assert_eq!(table.measure(Some(200.)), (vec![100., 30.], vec![20.]));
+        assert_eq!(table.min_size(), gfx::Size::new(70., 20.));
+    }
+
+    #[test]
+    fn fit_spanning_cells() {
+        let mut sizes = vec![10., 10.];
+        fit(&mut sizes, &[(0, 2, 50.)], |_| true, 10.);
+        assert_eq!(sizes, vec![20., 20.]);
+
+        let mut sizes = vec![10., 10.];
+        fit(&mut sizes, &[(0, 1, 15.), (0, 2, 50.)], |i| i == 1, 10.);
+        assert_eq!(sizes, vec![10., 30.]);
+    }
+}
@@ -3,7 +3,7 @@ use {crate::ui::layout, reclutch::display as gfx, std::collections::BTreeMap};
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct VFillConfig {
     pub flex: f32,
-    pub margins: layout::SideMargins,
+    pub margins: layout::Margins,
     pub alignment: layout::Alignment,
 }
 
@@ -17,11 +17,11 @@ impl Default for VFillConfig {
     }
 }
 
-impl From<(f32, layout::SideMargins)> for VFillConfig {
-    fn from(config: (f32, layout::SideMargins)) -> Self {
+impl<M: Into<layout::Margins>> From<(f32, M)> for VFillConfig {
+    fn from(config: (f32, M)) -> Self {
         VFillConfig {
             flex: config.0,
-            margins: config.1,
+            margins: config.1.into(),
             ..Default::default()
         }
     }
@@ -105,16 +105,24 @@ impl layout::Layout for VFill {
     fn min_size(&self) -> gfx::Size {
         let mut width = 0.0;
         let mut height = 0.0;
+        // the share of the height taken up by percentage margins.
+        let mut percent = 0.0;
         for entry in self.entries.values() {
             if !layout::should_layout(&entry.item) {
                 continue;
             }
 
-            let rect = entry.item.rect();
-            if rect.size.width > width {
-                width = rect.size.width;
+            let size = entry.item.preferred_size();
+            let margins = entry.config.margins;
+            let outer = margins.expand(size).width;
+            if outer > width {
+                width = outer;
             }
-            height += rect.size.height + entry.config.margins.top + entry.config.margins.bottom;
+            height += size.height + margins.top.fixed() + margins.bottom.fixed();
+            percent += margins.top.percent() + margins.bottom.percent();
+        }
+        if percent < 1.0 {
+            height /= 1.0 - percent;
         }
         gfx::Size::new(width, height)
     }
@@ -127,22 +135,18 @@ impl layout::Layout for VFill {
                 continue;
             }
 
-            y += entry.config.margins.top;
+            let margins = entry.config.margins.resolve(bounds.size);
+            y += margins.top;
             let mut rect = entry.item.rect();
             rect.size.height = width_portion * entry.config.flex;
             entry.item.set_rect(gfx::Rect::new(
                 gfx::Point::new(
-                    layout::align_x(
-                        rect,
-                        bounds,
-                        entry.config.alignment,
-                        entry.config.margins.horizontal(),
-                    ),
+                    layout::align_x(rect, bounds, entry.config.alignment, margins.horizontal()),
                     y,
                 ),
                 rect.size,
             ));
-            y += rect.size.height + entry.config.margins.bottom;
+            y += rect.size.height + margins.bottom;
         }
     }
 }
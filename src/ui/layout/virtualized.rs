@@ -0,0 +1,199 @@
+use {crate::ui::layout, reclutch::display as gfx, std::collections::BTreeMap, std::ops::Range};
+
+/// Change in the items of a [`Virtual`](Virtual) layout which intersect its viewport.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VirtualEvent {
+    /// The item at this index has scrolled into the viewport and should be created and pushed.
+    Enter(usize),
+    /// The item at this index has scrolled out of the viewport and can be removed.
+    Leave(usize),
+}
+
+/// Vertical list of a (potentially very large) number of items, of which only those intersecting the viewport are laid out.
+///
+/// The height of each item is given up-front by an extent function, so the items themselves only need to exist while they're visible.
+/// The layout reports which items have entered or left the viewport to the [callback](Virtual::on_change),
+/// in response to which items should be pushed (with their index as the configuration) or removed.
+///
+/// The viewport is the visible region of the whole list, so its origin is the scroll offset.
+/// Items are positioned shifted by this offset, filling the width of the layout.
+pub struct Virtual {
+    entries: BTreeMap<usize, layout::Item>,
+    extent: Box<dyn Fn(usize) -> f32>,
+    offsets: Vec<f32>,
+    viewport: gfx::Rect,
+    visible: Range<usize>,
+    callback: Option<Box<dyn FnMut(VirtualEvent)>>,
+}
+
+impl Virtual {
+    /// Creates a `Virtual` of `count` items, where `extent` returns the height of the item at a given index.
+    pub fn new(count: usize, extent: impl Fn(usize) -> f32 + 'static) -> Self {
+        let mut layout = Virtual {
+            entries: Default::default(),
+            extent: Box::new(extent),
+            offsets: Vec::new(),
+            viewport: Default::default(),
+            visible: 0..0,
+            callback: None,
+        };
+        layout.set_count(count);
+        layout
+    }
+
+    /// Sets the callback which is notified when items enter or leave the viewport.
+    ///
+    /// This is invoked from within layout updates, so typically it should only queue the changes to be handled afterwards.
+    pub fn on_change(&mut self, callback: impl FnMut(VirtualEvent) + 'static) {
+        self.callback = Some(Box::new(callback));
+    }
+
+    /// Changes the number of items in the list.
+    pub fn set_count(&mut self, count: usize) {
+        self.offsets.clear();
+        self.offsets.reserve(count + 1);
+        let mut y = 0.0;
+        self.offsets.push(y);
+        for i in 0..count {
+            y += (self.extent)(i).max(0.0);
+            self.offsets.push(y);
+        }
+        self.refresh();
+    }
+
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Re-evaluates the extent of every item, for when the heights given by the extent function have changed.
+    #[inline]
+    pub fn invalidate_extents(&mut self) {
+        self.set_count(self.count());
+    }
+
+    /// Changes the visible region of the list.
+    pub fn set_viewport(&mut self, viewport: gfx::Rect) {
+        self.viewport = viewport;
+        self.refresh();
+    }
+
+    #[inline]
+    pub fn viewport(&self) -> gfx::Rect {
+        self.viewport
+    }
+
+    /// Returns the indices of the items intersecting the viewport.
+    #[inline]
+    pub fn visible(&self) -> Range<usize> {
+        self.visible.clone()
+    }
+
+    /// Returns the height of the entire list.
+    #[inline]
+    pub fn content_height(&self) -> f32 {
+        self.offsets[self.count()]
+    }
+
+    /// Returns the offset of the top of the item at `index` from the top of the list.
+    #[inline]
+    pub fn offset(&self, index: usize) -> f32 {
+        self.offsets[index.min(self.count())]
+    }
+
+    fn refresh(&mut self) {
+        let count = self.count();
+        let top = self.viewport.min_y();
+        let bottom = self.viewport.max_y();
+        let start = self.offsets[1..].partition_point(|&end| end <= top);
+        let end = self.offsets[..count]
+            .partition_point(|&begin| begin < bottom)
+            .max(start);
+        let visible = start..end;
+
+        if let Some(callback) = &mut self.callback {
+            for i in self.visible.clone() {
+                if !visible.contains(&i) {
+                    callback(VirtualEvent::Leave(i));
+                }
+            }
+            for i in visible.clone() {
+                if !self.visible.contains(&i) {
+                    callback(VirtualEvent::Enter(i));
+                }
+            }
+        }
+
+        self.visible = visible;
+    }
+}
+
+impl layout::Layout for Virtual {
+    /// The index of the item within the list.
+    type Config = usize;
+    type Id = usize;
+
+    /// Pushes the item at `index`, replacing any item already pushed at that index.
+    fn push(&mut self, item: impl Into<layout::Item>, index: usize) -> usize {
+        self.entries.insert(index, item.into());
+        index
+    }
+
+    #[inline]
+    fn remove(&mut self, id: &usize) -> Option<layout::Item> {
+        self.entries.remove(id)
+    }
+
+    #[inline]
+    fn get(&self, id: &usize) -> Option<&layout::Item> {
+        self.entries.get(id)
+    }
+
+    #[inline]
+    fn get_mut(&mut self, id: &usize) -> Option<&mut layout::Item> {
+        self.entries.get_mut(id)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn items(&self) -> Vec<(&layout::Item, &usize)> {
+        self.entries.values().zip(self.entries.keys()).collect()
+    }
+
+    /// The layout takes up the height of the viewport, rather than that of the entire list.
+    fn min_size(&self) -> gfx::Size {
+        let mut width = 0.0;
+        for item in self.entries.range(self.visible.clone()).map(|x| x.1) {
+            if layout::should_layout(item) {
+                let size = item.preferred_size();
+                if size.width > width {
+                    width = size.width;
+                }
+            }
+        }
+        gfx::Size::new(width, self.viewport.size.height)
+    }
+
+    fn update(&mut self, bounds: gfx::Rect) {
+        let scroll = self.viewport.origin.y;
+        for (&index, item) in self.entries.range_mut(self.visible.clone()) {
+            if !layout::should_layout(item) {
+                continue;
+            }
+
+            item.set_rect(gfx::Rect::new(
+                gfx::Point::new(
+                    bounds.origin.x,
+                    bounds.origin.y + self.offsets[index] - scroll,
+                ),
+                gfx::Size::new(
+                    bounds.size.width,
+                    self.offsets[index + 1] - self.offsets[index],
+                ),
+            ));
+        }
+    }
+}
@@ -1,11 +1,16 @@
 use {crate::ui::layout, reclutch::display as gfx, std::collections::BTreeMap};
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "spec", derive(serde::Deserialize), serde(default))]
 pub struct VStackConfig {
     pub top_margin: f32,
     pub bottom_margin: f32,
     pub alignment: layout::Alignment,
     pub fill_w: Option<f32>,
+    /// Weight by which the item is heightened to share the height left over by all the items.
+    ///
+    /// Items with a weight of `0.0` (the default) keep their own height.
+    pub flex: f32,
 }
 
 impl From<(f32, f32)> for VStackConfig {
@@ -21,20 +26,116 @@ impl From<(f32, f32)> for VStackConfig {
 struct Item {
     config: VStackConfig,
     item: layout::Item,
+    /// The size last given to the item and its natural size at the time, if the two differed.
+    stretched: Option<(gfx::Size, gfx::Size)>,
+}
+
+impl Item {
+    /// Returns the size of the item without the extra space it was given by flexing or filling.
+    ///
+    /// Items without a preferred size report their current size, which includes any extra space they were given;
+    /// as long as the item still has the size it was given, the size from before is used instead.
+    fn natural_size(&self) -> gfx::Size {
+        let size = self.item.preferred_size();
+        match self.stretched {
+            Some((given, natural)) if given == size => natural,
+            _ => size,
+        }
+    }
 }
 
 pub struct VStack {
     entries: BTreeMap<u64, Item>,
+    order: Vec<u64>,
     next_id: u64,
+    spacing: f32,
 }
 
 impl VStack {
     pub fn new() -> Self {
+        VStack::with_spacing(0.0)
+    }
+
+    /// Creates a `VStack` which leaves `spacing` between adjacent items, on top of their margins.
+    pub fn with_spacing(spacing: f32) -> Self {
         VStack {
             entries: Default::default(),
+            order: Vec::new(),
             next_id: 0,
+            spacing,
         }
     }
+
+    #[inline]
+    pub fn set_spacing(&mut self, spacing: f32) {
+        self.spacing = spacing;
+    }
+
+    #[inline]
+    pub fn spacing(&self) -> f32 {
+        self.spacing
+    }
+
+    /// Adds an item at `index` in the order of the items, shifting the items after it.
+    ///
+    /// `index` is clamped to the number of items, so that an out of range index is the same as [`push`](layout::Layout::push).
+    pub fn insert_at(
+        &mut self,
+        index: usize,
+        item: impl Into<layout::Item>,
+        config: Option<VStackConfig>,
+    ) -> u64 {
+        let id = layout::Layout::push(self, item, config);
+        self.reorder(&id, index);
+        id
+    }
+
+    /// Moves the item of `id` to `index` in the order of the items (clamped to the last position).
+    ///
+    /// Returns `false` if there is no such item.
+    pub fn reorder(&mut self, id: &u64, index: usize) -> bool {
+        match self.order.iter().position(|x| x == id) {
+            Some(old) => {
+                self.order.remove(old);
+                let index = index.min(self.order.len());
+                self.order.insert(index, *id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the position of the item of `id` in the order of the items.
+    #[inline]
+    pub fn index_of(&self, id: &u64) -> Option<usize> {
+        self.order.iter().position(|x| x == id)
+    }
+
+    /// Returns the entries in the order they're laid out.
+    fn ordered(&self) -> impl Iterator<Item = &Item> {
+        self.order.iter().map(move |id| &self.entries[id])
+    }
+}
+
+impl layout::Node<VStack> {
+    /// Adds an item at `index` in the order of the items; see [`VStack::insert_at`](VStack::insert_at).
+    pub fn insert_at(
+        &mut self,
+        index: usize,
+        item: impl Into<layout::Item>,
+        config: Option<VStackConfig>,
+    ) -> u64 {
+        let id = self.push(item, config);
+        self.reorder(&id, index);
+        id
+    }
+}
+
+/// Returns the flex weight of an entry, which for spacers is that of the spacer.
+fn flex(entry: &Item) -> f32 {
+    layout::as_spacer(&entry.item)
+        .map_or(entry.config.flex, |x| x.flex())
+        .max(0.0)
 }
 
 impl layout::Layout for VStack {
@@ -49,13 +150,16 @@ impl layout::Layout for VStack {
             Item {
                 config: config.unwrap_or_default(),
                 item: item.into(),
+                stretched: None,
             },
         );
+        self.order.push(id);
         id
     }
 
     #[inline]
     fn remove(&mut self, id: &u64) -> Option<layout::Item> {
+        self.order.retain(|x| x != id);
         self.entries.remove(id).map(|x| x.item)
     }
 
@@ -75,52 +179,93 @@ impl layout::Layout for VStack {
     }
 
     fn items(&self) -> Vec<(&layout::Item, &u64)> {
-        self.entries
-            .values()
-            .map(|x| &x.item)
-            .zip(self.entries.keys())
+        self.order
+            .iter()
+            .map(|id| (&self.entries[id].item, id))
             .collect()
     }
 
     fn min_size(&self) -> gfx::Size {
         let mut width = 0.0;
         let mut height = 0.0;
-        for entry in self.entries.values() {
+        let mut count = 0;
+        for entry in self.ordered() {
             if !layout::should_layout(&entry.item) {
                 continue;
             }
 
-            let rect = entry.item.rect();
-            if rect.size.width > width {
-                width = rect.size.width;
+            count += 1;
+            height += entry.config.top_margin + entry.config.bottom_margin;
+            if let Some(spacer) = layout::as_spacer(&entry.item) {
+                height += spacer.size();
+                continue;
             }
-            height += rect.size.height + entry.config.top_margin + entry.config.bottom_margin;
+
+            let size = entry.natural_size();
+            if size.width > width {
+                width = size.width;
+            }
+            height += size.height;
         }
+        height += self.spacing * (count.max(1) - 1) as f32;
         gfx::Size::new(width, height)
     }
 
     fn update(&mut self, bounds: gfx::Rect) {
+        let total_flex: f32 = self
+            .entries
+            .values()
+            .filter(|x| layout::should_layout(&x.item))
+            .map(flex)
+            .sum();
+        let extra = if total_flex > 0.0 {
+            (bounds.size.height - layout::Layout::min_size(self).height).max(0.0)
+        } else {
+            0.0
+        };
+
         let mut y = bounds.origin.y;
-        for entry in self.entries.values_mut() {
+        let mut first = true;
+        for id in &self.order {
+            let entry = self.entries.get_mut(id).unwrap();
             if !layout::should_layout(&entry.item) {
                 continue;
             }
 
+            if !first {
+                y += self.spacing;
+            }
+            first = false;
+
             y += entry.config.top_margin;
-            let rect = entry.item.rect();
+            let size = match layout::as_spacer(&entry.item) {
+                Some(spacer) => gfx::Size::new(0.0, spacer.size()),
+                None => entry.natural_size(),
+            };
+            let rect = gfx::Rect::new(Default::default(), size);
             let w = if let Some(f) = entry.config.fill_w {
                 bounds.size.width * f
             } else {
                 rect.size.width
             };
+            let mut h = rect.size.height;
+            if total_flex > 0.0 {
+                h += extra * flex(entry) / total_flex;
+            }
+            let given = gfx::Size::new(w, h);
+            entry.stretched = if given != size {
+                Some((given, size))
+            } else {
+                None
+            };
             entry.item.set_rect(gfx::Rect::new(
                 gfx::Point::new(
                     layout::align_x(rect, bounds, entry.config.alignment, 0.0),
                     y,
                 ),
-                gfx::Size::new(w, rect.size.height),
+                given,
             ));
-            y += rect.size.height + entry.config.bottom_margin;
+            y += h + entry.config.bottom_margin;
         }
     }
 }
@@ -0,0 +1,124 @@
+use {crate::ui::layout, reclutch::display as gfx, std::collections::BTreeMap};
+
+/// Configuration of an item in a `ZStack`.
+///
+/// The item is aligned within the stack, then moved by `offset`.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct ZStackConfig {
+    pub horizontal_alignment: layout::Alignment,
+    pub vertical_alignment: layout::Alignment,
+    pub offset: gfx::Vector,
+}
+
+impl From<(layout::Alignment, layout::Alignment)> for ZStackConfig {
+    fn from(alignment: (layout::Alignment, layout::Alignment)) -> Self {
+        ZStackConfig {
+            horizontal_alignment: alignment.0,
+            vertical_alignment: alignment.1,
+            ..Default::default()
+        }
+    }
+}
+
+struct Item {
+    config: ZStackConfig,
+    item: layout::Item,
+}
+
+/// Places all of its items over the same rectangle, each individually aligned.
+///
+/// This is suitable for badges, watermarks and other overlays.
+/// Note that layering follows the widget tree rather than the layout, so overlays should come after what they cover in the children.
+pub struct ZStack {
+    entries: BTreeMap<u64, Item>,
+    next_id: u64,
+}
+
+impl ZStack {
+    pub fn new() -> Self {
+        ZStack {
+            entries: Default::default(),
+            next_id: 0,
+        }
+    }
+}
+
+impl layout::Layout for ZStack {
+    type Config = Option<ZStackConfig>;
+    type Id = u64;
+
+    fn push(&mut self, item: impl Into<layout::Item>, config: Option<ZStackConfig>) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.entries.insert(
+            id,
+            Item {
+                config: config.unwrap_or_default(),
+                item: item.into(),
+            },
+        );
+        id
+    }
+
+    #[inline]
+    fn remove(&mut self, id: &u64) -> Option<layout::Item> {
+        self.entries.remove(id).map(|x| x.item)
+    }
+
+    #[inline]
+    fn get(&self, id: &u64) -> Option<&layout::Item> {
+        Some(&self.entries.get(id)?.item)
+    }
+
+    #[inline]
+    fn get_mut(&mut self, id: &u64) -> Option<&mut layout::Item> {
+        Some(&mut self.entries.get_mut(id)?.item)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn items(&self) -> Vec<(&layout::Item, &u64)> {
+        self.entries
+            .values()
+            .map(|x| &x.item)
+            .zip(self.entries.keys())
+            .collect()
+    }
+
+    fn min_size(&self) -> gfx::Size {
+        let mut width = 0.0;
+        let mut height = 0.0;
+        for entry in self.entries.values() {
+            if !layout::should_layout(&entry.item) {
+                continue;
+            }
+
+            let size = entry.item.preferred_size();
+            if size.width > width {
+                width = size.width;
+            }
+            if size.height > height {
+                height = size.height;
+            }
+        }
+        gfx::Size::new(width, height)
+    }
+
+    fn update(&mut self, bounds: gfx::Rect) {
+        for entry in self.entries.values_mut() {
+            if !layout::should_layout(&entry.item) {
+                continue;
+            }
+
+            let rect = gfx::Rect::new(Default::default(), entry.item.preferred_size());
+            let position = gfx::Point::new(
+                layout::align_x(rect, bounds, entry.config.horizontal_alignment, 0.0),
+                layout::align_y(rect, bounds, entry.config.vertical_alignment, 0.0),
+            ) + entry.config.offset;
+            entry.item.set_rect(gfx::Rect::new(position, rect.size));
+        }
+    }
+}
@@ -26,11 +26,74 @@ pub struct Aux<T: 'static> {
     pub queue: uniq::rc::Queue,
     /// Top-level (or near top-level) widget which fills the entire window.
     pub central_widget: CommonRef,
+    /// Whether the layout of the central widget needs to be updated; see [`invalidate_root_layout`](Aux::invalidate_root_layout).
+    root_layout_invalid: bool,
     /// Current widget that has focus.
     pub focus_widget: Option<CommonRef>,
+    /// Clipboard used for cut, copy and paste; see [`clipboard`](Aux::clipboard).
+    pub clipboard: Box<dyn Clipboard>,
+    /// Global interaction timings and thresholds.
+    pub interaction: InteractionConfig,
+    /// Timing of the current frame, advanced by [`tick`](Aux::tick).
+    clock: FrameClock,
+    /// Number of physical pixels per logical unit of the window; see [`snap`](Aux::snap).
+    scale_factor: f32,
+    /// Shape of the mouse cursor, applied by the window after each update.
+    ///
+    /// `app` sets this to the [cursor](Common::set_cursor) of the top-most widget under the mouse (see [`cursor_at`](cursor_at)) each update.
+    pub cursor_icon: CursorIcon,
+    /// Keyboard modifiers currently held.
+    key_modifiers: KeyModifiers,
+    /// Number of presses in quick succession of the mouse button last pressed.
+    click_count: u32,
+    /// Whether the application should exit once the current update is finished; see [`request_exit`](Aux::request_exit).
+    exit_requested: bool,
+    /// Timers started by [`set_timeout`](Aux::set_timeout) and [`set_interval`](Aux::set_interval).
+    timers: Timers,
+    /// Handle for emitting events from other threads; see [`proxy`](Aux::proxy).
+    proxy: Proxy,
+    /// Area (in absolute coordinates) of the caret of the focused text widget, which input methods show their candidate window next to.
+    ///
+    /// Text widgets set this as their caret moves while focused; `app` then positions the candidate window below it.
+    /// This is the only part of input method support that `app` provides so far; composition isn't reported (see [`ImeCompositionEvent`](ImeCompositionEvent)).
+    pub ime_area: Option<gfx::Rect>,
+    /// [Style classes](Common::add_class) of the widget currently being updated or drawn.
+    pub(crate) classes: Vec<String>,
+    /// Painters shared between widgets, which are retrieved from the theme through [`get_painter`](crate::theme::get_painter).
+    ///
+    /// These are cleared when the theme is replaced through [`set_theme`](Aux::set_theme).
+    pub(crate) painters: crate::theme::PainterCache<T>,
 }
 
 impl<T: 'static> Aux<T> {
+    /// Creates the auxiliary state of a UI showing `theme`, with a [local clipboard](LocalClipboard) and default [interaction settings](InteractionConfig).
+    ///
+    /// Hosts other than `app` create this before the widgets, then drive it through [`tick`](Aux::tick) and the `propagate_*` functions.
+    pub fn new(data: T, theme: Box<dyn Theme<T>>) -> Self {
+        Aux {
+            data,
+            theme,
+            id: uniq::id::next(),
+            queue: Default::default(),
+            central_widget: CommonRef::new(None),
+            root_layout_invalid: false,
+            focus_widget: None,
+            clipboard: Box::new(LocalClipboard::default()),
+            interaction: Default::default(),
+            clock: Default::default(),
+            scale_factor: 1.,
+            cursor_icon: Default::default(),
+            key_modifiers: Default::default(),
+            click_count: 0,
+            exit_requested: false,
+            timers: Default::default(),
+            proxy: Default::default(),
+            ime_area: None,
+            classes: Vec::new(),
+            painters: Default::default(),
+        }
+    }
+
     /// Creates a new [`Listener`](Listener).
     #[inline]
     pub fn listen<U: uniq::Packable>(&self) -> Listener<U> {
@@ -60,6 +123,454 @@ impl<T: 'static> Aux<T> {
     pub fn has_focus(&self, common: &CommonRef) -> bool {
         self.focus_widget.as_ref() == Some(common)
     }
+
+    /// Returns a color from the theme, as changed by the [current style classes](Aux::classes).
+    ///
+    /// Painters should prefer this over [`Theme::color`](Theme::color) so that classes apply.
+    pub fn color(&self, c: impl Into<crate::theme::ColorToken>) -> gfx::Color {
+        let c = c.into().name();
+        self.classes
+            .iter()
+            .rev()
+            .find_map(|class| self.theme.class_color(class, c))
+            .unwrap_or_else(|| self.theme.color(c))
+    }
+
+    /// Returns a fill from the theme over `bounds` (see [`Theme::style_color`](Theme::style_color)),
+    /// unless the [current style classes](Aux::classes) change the color.
+    pub fn style_color(
+        &self,
+        c: impl Into<crate::theme::ColorToken>,
+        bounds: gfx::Rect,
+    ) -> gfx::StyleColor {
+        let c = c.into().name();
+        match self
+            .classes
+            .iter()
+            .rev()
+            .find_map(|class| self.theme.class_color(class, c))
+        {
+            Some(color) => gfx::StyleColor::Color(color),
+            None => self.theme.style_color(c, bounds),
+        }
+    }
+
+    /// Replaces the application theme at runtime, emitting [`ThemeChangedEvent`](ThemeChangedEvent) so that every widget can refresh itself.
+    ///
+    /// Widgets under a [theme override](Common::set_theme) keep using the override.
+    pub fn set_theme(&mut self, theme: Box<dyn Theme<T>>) {
+        self.theme = theme;
+        self.painters.clear();
+        self.emit(&self.id, ThemeChangedEvent);
+        self.invalidate_root_layout();
+    }
+
+    /// Changes the [scale factor](Aux::scale_factor), emitting [`ThemeChangedEvent`](ThemeChangedEvent) so that every widget repaints at the new scale.
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        if self.scale_factor != scale_factor {
+            self.scale_factor = scale_factor;
+            self.emit(&self.id, ThemeChangedEvent);
+            self.invalidate_root_layout();
+        }
+    }
+
+    #[inline]
+    pub fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    /// Rounds a logical coordinate to the nearest physical pixel, so that strokes and edges stay crisp at fractional scale factors.
+    #[inline]
+    pub fn snap(&self, x: f32) -> f32 {
+        (x * self.scale_factor).round() / self.scale_factor
+    }
+
+    /// Changes the layout of the [central widget](Aux::central_widget), scheduling it to be updated.
+    pub fn set_root_layout<L: layout::Layout>(
+        &mut self,
+        layout: impl Into<Option<layout::Node<L>>>,
+    ) {
+        self.central_widget.with(|x| x.set_layout(layout));
+        self.invalidate_root_layout();
+    }
+
+    /// Mutably accesses the layout of the [central widget](Aux::central_widget) through a closure, scheduling it to be updated.
+    ///
+    /// Returns `None` if the central widget has no layout, or if it isn't of type `L`.
+    pub fn root_layout_mut<L: layout::Layout, R>(
+        &mut self,
+        f: impl FnOnce(&mut layout::Node<L>) -> R,
+    ) -> Option<R> {
+        let out = self
+            .central_widget
+            .with(|x| x.layout_mut()?.cast_mut::<L>().map(f));
+        if out.is_some() {
+            self.invalidate_root_layout();
+        }
+        out
+    }
+
+    /// Schedules the layout of the [central widget](Aux::central_widget) to be updated once the current update is finished.
+    ///
+    /// This should be invoked after changing something which the root layout depends on (e.g. the size of a widget within it).
+    #[inline]
+    pub fn invalidate_root_layout(&mut self) {
+        self.root_layout_invalid = true;
+    }
+
+    /// Updates the layout of the [central widget](Aux::central_widget) if it has been [invalidated](Aux::invalidate_root_layout).
+    ///
+    /// `app` invokes this after every update.
+    pub fn update_root_layout(&mut self) {
+        if std::mem::replace(&mut self.root_layout_invalid, false) {
+            layout::update_direct_layout(&self.central_widget);
+        }
+    }
+
+    /// Returns the clipboard, for widgets to implement cut, copy and paste.
+    ///
+    /// Under `app` this is the OS clipboard (if available), otherwise it's whichever clipboard the `Aux` was created with,
+    /// typically a [`LocalClipboard`](LocalClipboard) which only exchanges text within the application.
+    #[inline]
+    pub fn clipboard(&mut self) -> &mut dyn Clipboard {
+        &mut *self.clipboard
+    }
+
+    /// Returns the keyboard modifiers currently held, for modifier-dependent handling of keyboard and mouse events (e.g. Ctrl+click).
+    ///
+    /// `app` keeps this up to date as the modifiers change, before emitting the events which follow.
+    #[inline]
+    pub fn modifiers(&self) -> KeyModifiers {
+        self.key_modifiers
+    }
+
+    /// Changes the keyboard modifiers currently held, as reported by the window.
+    #[inline]
+    pub fn set_modifiers(&mut self, modifiers: KeyModifiers) {
+        self.key_modifiers = modifiers;
+    }
+
+    /// Returns how many times the mouse button last pressed was pressed in quick succession (e.g. `2` for a double-click, `3` for a triple-click).
+    ///
+    /// `app` counts presses within the [double-click interval](InteractionConfig::double_click_interval) of each other,
+    /// without the cursor moving further than the [drag threshold](InteractionConfig::drag_threshold).
+    #[inline]
+    pub fn click_count(&self) -> u32 {
+        self.click_count
+    }
+
+    /// Changes how many times the mouse button last pressed was pressed in quick succession, as counted by the host.
+    #[inline]
+    pub fn set_click_count(&mut self, click_count: u32) {
+        self.click_count = click_count;
+    }
+
+    /// Asks the application to exit once the current update is finished; `app` then closes the window and ends the event loop.
+    ///
+    /// Unlike the user closing the window, this doesn't emit [`CloseRequestedEvent`](CloseRequestedEvent), so it can be used to close after confirming it (e.g. through an "unsaved changes" dialog).
+    #[inline]
+    pub fn request_exit(&mut self) {
+        self.exit_requested = true;
+    }
+
+    /// Returns `true` if [`request_exit`](Aux::request_exit) has been called.
+    #[inline]
+    pub fn exit_requested(&self) -> bool {
+        self.exit_requested
+    }
+
+    /// Advances the frame clock and emits [`FrameTickEvent`](FrameTickEvent).
+    ///
+    /// Events sent through the [proxy](Aux::proxy) since the last frame, and those of [timers](Aux::set_timeout) which are due, are emitted here too.
+    ///
+    /// This should be invoked once per frame, prior to updating; `app` does so.
+    pub fn tick(&mut self) {
+        let dt = self.clock.tick();
+        self.emit(&self.id, FrameTickEvent(dt));
+        self.proxy.receive(&self.queue);
+        self.timers.fire(&self.queue, std::time::Instant::now());
+    }
+
+    /// Emits `event` from `id` once `duration` has passed.
+    pub fn set_timeout<E: 'static>(
+        &mut self,
+        duration: std::time::Duration,
+        id: &impl Id,
+        event: E,
+    ) -> TimerId {
+        let id = id.id();
+        let mut event = Some(event);
+        self.timers.start(duration, None, move |queue| {
+            if let Some(event) = event.take() {
+                queue.emit(id, event);
+            }
+        })
+    }
+
+    /// Emits a clone of `event` from `id` every `interval`, until [cancelled](Aux::cancel_timer).
+    pub fn set_interval<E: Clone + 'static>(
+        &mut self,
+        interval: std::time::Duration,
+        id: &impl Id,
+        event: E,
+    ) -> TimerId {
+        let id = id.id();
+        self.timers.start(interval, Some(interval), move |queue| {
+            queue.emit(id, event.clone())
+        })
+    }
+
+    /// Stops a timer before it fires (again), returning `false` if it had already finished or been cancelled.
+    pub fn cancel_timer(&mut self, timer: TimerId) -> bool {
+        self.timers.cancel(timer)
+    }
+
+    /// Returns when the next timer is due, for the event loop to wake up then.
+    #[inline]
+    pub fn next_timer(&self) -> Option<std::time::Instant> {
+        self.timers.next()
+    }
+
+    /// Returns a handle for emitting events into the queue from other threads (e.g. as downloads progress).
+    #[inline]
+    pub fn proxy(&self) -> Proxy {
+        self.proxy.clone()
+    }
+
+    /// Returns the time elapsed between the previous frame and the current one.
+    #[inline]
+    pub fn frame_dt(&self) -> std::time::Duration {
+        self.clock.dt()
+    }
+}
+
+/// Handle to a timer started through [`Aux`](Aux), for [cancelling](Aux::cancel_timer) it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimerId(u64);
+
+struct Timer {
+    id: TimerId,
+    due: std::time::Instant,
+    interval: Option<std::time::Duration>,
+    fire: Box<dyn FnMut(&uniq::rc::Queue)>,
+}
+
+/// Pending timers of an [`Aux`](Aux), which fire as they become due upon each [`tick`](Aux::tick).
+///
+/// Timers therefore fire at the granularity of frames, at the start of the first frame after they're due.
+#[derive(Default)]
+pub struct Timers {
+    next_id: u64,
+    timers: Vec<Timer>,
+}
+
+impl Timers {
+    fn start(
+        &mut self,
+        delay: std::time::Duration,
+        interval: Option<std::time::Duration>,
+        fire: impl FnMut(&uniq::rc::Queue) + 'static,
+    ) -> TimerId {
+        let id = TimerId(self.next_id);
+        self.next_id += 1;
+        self.timers.push(Timer {
+            id,
+            due: std::time::Instant::now() + delay,
+            interval,
+            fire: Box::new(fire),
+        });
+        id
+    }
+
+    fn cancel(&mut self, id: TimerId) -> bool {
+        let len = self.timers.len();
+        self.timers.retain(|timer| timer.id != id);
+        self.timers.len() != len
+    }
+
+    fn next(&self) -> Option<std::time::Instant> {
+        self.timers.iter().map(|timer| timer.due).min()
+    }
+
+    fn fire(&mut self, queue: &uniq::rc::Queue, now: std::time::Instant) {
+        for timer in &mut self.timers {
+            if timer.due > now {
+                continue;
+            }
+            (timer.fire)(queue);
+            if let Some(interval) = timer.interval {
+                // intervals missed while the loop was busy are skipped rather than fired in a burst.
+                timer.due += interval;
+                if timer.due <= now {
+                    timer.due = now + interval;
+                }
+            }
+        }
+        // only finished timeouts are left due.
+        self.timers.retain(|timer| timer.due > now);
+    }
+}
+
+impl std::fmt::Debug for Timers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Timers")
+            .field("pending", &self.timers.len())
+            .finish()
+    }
+}
+
+/// Measures the time between frames, so that widgets can animate independently of the frame rate.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FrameClock {
+    last: Option<std::time::Instant>,
+    dt: std::time::Duration,
+}
+
+impl FrameClock {
+    /// Starts a new frame, returning the time elapsed since the last one (zero for the first frame).
+    pub fn tick(&mut self) -> std::time::Duration {
+        let now = std::time::Instant::now();
+        self.dt = self.last.map(|last| now - last).unwrap_or_default();
+        self.last = Some(now);
+        self.dt
+    }
+
+    #[inline]
+    pub fn dt(&self) -> std::time::Duration {
+        self.dt
+    }
+}
+
+/// The user asked to close the window (e.g. through its close button).
+///
+/// The window closes once the event has been handled, unless a listener [cancels](CloseRequestedEvent::cancel) it,
+/// for instance to ask about unsaved changes first and then [exit](Aux::request_exit) if confirmed.
+#[derive(Debug, Clone, Default)]
+pub struct CloseRequestedEvent(Rc<Cell<bool>>);
+
+impl CloseRequestedEvent {
+    /// Keeps the window open.
+    #[inline]
+    pub fn cancel(&self) {
+        self.0.set(true);
+    }
+
+    #[inline]
+    pub fn cancelled(&self) -> bool {
+        self.0.get()
+    }
+}
+
+/// A new frame has started. Contains the time elapsed since the previous frame.
+pub struct FrameTickEvent(pub std::time::Duration);
+
+/// Global tuning for user interaction, shared by all widgets through [`Aux`](Aux).
+///
+/// `app` reads these from the OS settings on Windows, and uses the [defaults](InteractionConfig::default) elsewhere.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InteractionConfig {
+    /// Maximum time between two presses for them to count as a double-click.
+    pub double_click_interval: std::time::Duration,
+    /// Distance the cursor has to move while pressed before it counts as a drag.
+    pub drag_threshold: f32,
+    /// How long the cursor has to rest over a widget before its tooltip is shown.
+    pub tooltip_delay: std::time::Duration,
+    /// How long a key has to be held before it starts repeating.
+    pub key_repeat_delay: std::time::Duration,
+    /// Time between repeats of a held key.
+    pub key_repeat_interval: std::time::Duration,
+    /// How long after the last typed character a type-ahead search (e.g. in a combo list) is cleared.
+    pub type_ahead_timeout: std::time::Duration,
+    /// Distance scrolled by one line (i.e. one notch of a mouse wheel); see [`ScrollDelta::pixels`](ScrollDelta::pixels).
+    pub scroll_line: f32,
+}
+
+impl Default for InteractionConfig {
+    /// Returns the common defaults of desktop platforms.
+    fn default() -> Self {
+        InteractionConfig {
+            double_click_interval: std::time::Duration::from_millis(500),
+            drag_threshold: 4.,
+            tooltip_delay: std::time::Duration::from_millis(500),
+            key_repeat_delay: std::time::Duration::from_millis(500),
+            key_repeat_interval: std::time::Duration::from_millis(33),
+            type_ahead_timeout: std::time::Duration::from_secs(1),
+            scroll_line: 40.,
+        }
+    }
+}
+
+type ProxiedEvent = Box<dyn FnOnce(&uniq::rc::Queue) + Send>;
+
+#[derive(Default)]
+struct ProxyShared {
+    events: Vec<ProxiedEvent>,
+    waker: Option<Box<dyn Fn() + Send>>,
+}
+
+/// Cloneable handle for emitting events from other threads, obtained through [`Aux::proxy`](Aux::proxy).
+///
+/// Events are held until the start of the next frame, when they're emitted into the queue by [`Aux::tick`](Aux::tick).
+/// `app` wakes the event loop as events are sent, so that they're handled even while the window is idle.
+#[derive(Clone, Default)]
+pub struct Proxy(std::sync::Arc<std::sync::Mutex<ProxyShared>>);
+
+impl Proxy {
+    /// Emits `event` from `id` at the start of the next frame.
+    pub fn emit<E: Send + 'static>(&self, id: &impl Id, event: E) {
+        let id = id.id();
+        let mut shared = self.0.lock().unwrap();
+        shared.events.push(Box::new(move |queue: &uniq::rc::Queue| {
+            queue.emit(id, event)
+        }));
+        if let Some(waker) = &shared.waker {
+            waker();
+        }
+    }
+
+    /// Changes the function invoked whenever an event is sent, which should wake the event loop (e.g. through an `EventLoopProxy`).
+    pub fn set_waker(&self, waker: impl Fn() + Send + 'static) {
+        self.0.lock().unwrap().waker = Some(Box::new(waker));
+    }
+
+    fn receive(&self, queue: &uniq::rc::Queue) {
+        let events = std::mem::take(&mut self.0.lock().unwrap().events);
+        for event in events {
+            event(queue);
+        }
+    }
+}
+
+impl std::fmt::Debug for Proxy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Proxy").finish()
+    }
+}
+
+/// Text clipboard interface.
+///
+/// `app` provides an implementation backed by the OS clipboard, otherwise [`LocalClipboard`](LocalClipboard) can be used.
+pub trait Clipboard {
+    /// Returns the clipboard text, if there is any.
+    fn get_text(&mut self) -> Option<String>;
+    /// Replaces the clipboard text.
+    fn set_text(&mut self, text: String);
+}
+
+/// Clipboard which only exchanges text within the application.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LocalClipboard(Option<String>);
+
+impl Clipboard for LocalClipboard {
+    #[inline]
+    fn get_text(&mut self) -> Option<String> {
+        self.0.clone()
+    }
+
+    #[inline]
+    fn set_text(&mut self, text: String) {
+        self.0 = Some(text);
+    }
 }
 
 pub type Read<T> = uniq::Read<T>;
@@ -278,16 +789,84 @@ impl<T> Clone for ConsumableEvent<T> {
 
 /// A mouse button was pressed down.
 pub struct MousePressEvent(pub ConsumableEvent<(MouseButton, gfx::Point)>);
+/// A mouse button was pressed twice in quick succession, at about the same point. Follows the `MousePressEvent` of the second press.
+///
+/// The presses are counted by [`Aux::click_count`](Aux::click_count), for detecting triple-clicks and beyond.
+pub struct MouseDoubleClickEvent(pub ConsumableEvent<(MouseButton, gfx::Point)>);
 /// A mouse button was releasd. Always paired with a prior `MousePressEvent`.
 pub struct MouseReleaseEvent(pub ConsumableEvent<(MouseButton, gfx::Point)>);
 /// The mouse/cursor was moved.
 pub struct MouseMoveEvent(pub ConsumableEvent<gfx::Point>);
+/// The mouse wheel was turned or the trackpad was swiped, with the cursor at the given point.
+pub struct MouseScrollEvent(pub ConsumableEvent<(gfx::Point, ScrollDelta)>);
 /// A keyboard key was pressed down.
 pub struct KeyPressEvent(pub ConsumableEvent<KeyInput>);
 /// A keyboard key was released. Always paired with a prior `KeyPressEvent`.
 pub struct KeyReleaseEvent(pub ConsumableEvent<KeyInput>);
+/// A keyboard key was pressed down, identified by its platform-specific scancode.
+///
+/// This is emitted alongside `KeyPressEvent` for every key, including those which are [unknown](KeyInput::Unknown).
+pub struct RawKeyPressEvent(pub ConsumableEvent<u32>);
+/// A keyboard key was released, identified by its platform-specific scancode. Always paired with a prior `RawKeyPressEvent`.
+pub struct RawKeyReleaseEvent(pub ConsumableEvent<u32>);
 /// Printable character was typed. Related to string input.
 pub struct TextEvent(pub ConsumableEvent<char>);
+/// An input method changed the text being composed (e.g. CJK input prior to selecting a candidate).
+/// An empty string means the composition was cancelled.
+///
+/// `app` can't emit this yet, as its windowing backend doesn't report composition (see the roadmap); committed text arrives as `TextEvent`s instead.
+pub struct ImeCompositionEvent(pub ConsumableEvent<String>);
+/// An input method finished composing and committed text.
+///
+/// Like [`ImeCompositionEvent`](ImeCompositionEvent), this isn't emitted by `app` yet.
+pub struct ImeCommitEvent(pub ConsumableEvent<String>);
+
+/// Amount scrolled by a [`MouseScrollEvent`](MouseScrollEvent).
+///
+/// Positive values scroll towards the top-left of the content (i.e. turning the wheel away from the user, or swiping down or right).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScrollDelta {
+    /// Scrolled by lines, as reported by mouse wheels.
+    Lines(gfx::Vector),
+    /// Scrolled by a precise distance, as reported by trackpads.
+    Pixels(gfx::Vector),
+}
+
+impl ScrollDelta {
+    /// Returns the distance scrolled, with lines being `line` apart (typically [`InteractionConfig::scroll_line`](InteractionConfig::scroll_line)).
+    pub fn pixels(self, line: f32) -> gfx::Vector {
+        match self {
+            ScrollDelta::Lines(lines) => lines * line,
+            ScrollDelta::Pixels(pixels) => pixels,
+        }
+    }
+}
+
+/// Shape of the mouse cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CursorIcon {
+    /// The platform's default arrow.
+    Default,
+    /// A pointing hand, for links.
+    Pointer,
+    /// An I-beam, for selectable text.
+    Text,
+    /// Left and right arrows, for resizing horizontally (e.g. a splitter or a column edge).
+    ResizeHorizontal,
+    /// Up and down arrows, for resizing vertically.
+    ResizeVertical,
+    /// Arrows in all four directions, for moving.
+    Move,
+    /// The action isn't available (e.g. over a disabled widget).
+    NotAllowed,
+}
+
+impl Default for CursorIcon {
+    #[inline]
+    fn default() -> Self {
+        CursorIcon::Default
+    }
+}
 
 /// Clickable button on a mouse.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -307,7 +886,9 @@ macro_rules! keyboard_enum {
         #[repr(u32)]
         #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
         pub enum $name {
-            $($v),*
+            $($v,)*
+            #[doc = "Key which isn't otherwise enumerated, identified by its platform-specific scancode."]
+            Unknown(u32),
         }
 
         #[cfg(feature = "app")]
@@ -320,10 +901,14 @@ macro_rules! keyboard_enum {
         }
 
         #[cfg(feature = "app")]
-        impl Into<$other> for $name {
-            fn into(self) -> $other {
-                match self {
-                    $($name::$v => <$other>::$v),*
+        impl std::convert::TryFrom<$name> for $other {
+            #[doc = "The scancode of an unknown key."]
+            type Error = u32;
+
+            fn try_from(key: $name) -> Result<$other, u32> {
+                match key {
+                    $($name::$v => Ok(<$other>::$v),)*
+                    $name::Unknown(scancode) => Err(scancode),
                 }
             }
         }
@@ -601,6 +1186,15 @@ impl Interaction {
 
 pub struct TransformEvent;
 
+/// The rectangle of a widget was changed (e.g. moved or resized by a layout).
+///
+/// This is emitted on behalf of the widget when it is next updated, so several changes in between are reported as one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RectChangedEvent {
+    pub old: gfx::Rect,
+    pub new: gfx::Rect,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum LayoutMode {
     /// The size of the layout and the size of the widget are independent of each other.
@@ -623,6 +1217,11 @@ pub struct FocusChangedEvent {
     pub new_focus: Option<CommonRef>,
 }
 
+/// The [theme](Aux::theme) has been replaced through [`set_theme`](Aux::set_theme), or the [scale factor](Aux::scale_factor) has changed.
+///
+/// Widgets respond to this by retrieving new painters and colors from the theme, then resizing themselves accordingly.
+pub struct ThemeChangedEvent;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum FocusMode {
     /// The widget can only accept focus using keyboard input.
@@ -734,6 +1333,142 @@ impl<E: Element> ComponentList<E> {
     }
 }
 
+/// Common visual states of a widget, for use with [`StateMachine`](StateMachine).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum VisualState {
+    Idle,
+    Hover,
+    Pressed,
+    Disabled,
+    Focused,
+    /// A widget-specific state.
+    Custom(&'static str),
+}
+
+impl Default for VisualState {
+    #[inline]
+    fn default() -> Self {
+        VisualState::Idle
+    }
+}
+
+/// The state of a [`StateMachine`](StateMachine) changed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateChangedEvent<S> {
+    pub from: S,
+    pub to: S,
+}
+
+type StateGuard<W, S> = Box<dyn Fn(&W, &S, &S) -> bool>;
+type StateAction<W, T> = Box<dyn FnMut(&mut W, &mut Aux<T>)>;
+
+/// Component which tracks the visual state of a widget.
+///
+/// Transitions are requested through [`request`](StateMachine::request) and carried out when the component is next updated.
+/// Once a transition has been made, the exit actions of the old state and the entry actions of the new state are invoked,
+/// and a [`StateChangedEvent`](StateChangedEvent) is emitted from the widget.
+///
+/// If no transitions have been [allowed](StateMachine::allow), any transition is permitted.
+/// Otherwise, a transition is only made if an allowed transition matches and its guard passes.
+pub struct StateMachine<
+    T: 'static,
+    W: WidgetChildren<T>,
+    S: Clone + PartialEq + 'static = VisualState,
+> {
+    state: S,
+    pending: Vec<S>,
+    transitions: Vec<(Option<S>, Option<S>, StateGuard<W, S>)>,
+    entry: Vec<(S, StateAction<W, T>)>,
+    exit: Vec<(S, StateAction<W, T>)>,
+}
+
+impl<T: 'static, W: WidgetChildren<T>, S: Clone + PartialEq + 'static> StateMachine<T, W, S> {
+    pub fn new(initial: S) -> Self {
+        StateMachine {
+            state: initial,
+            pending: Vec::new(),
+            transitions: Vec::new(),
+            entry: Vec::new(),
+            exit: Vec::new(),
+        }
+    }
+
+    /// Returns the current state.
+    #[inline]
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// Queues a transition to `state`.
+    pub fn request(&mut self, state: S) {
+        self.pending.push(state);
+    }
+
+    /// Permits transitions from `from` to `to`, where `None` matches any state, as long as `guard` returns `true`.
+    ///
+    /// The guard is given the widget, the current state and the requested state.
+    pub fn allow(
+        &mut self,
+        from: impl Into<Option<S>>,
+        to: impl Into<Option<S>>,
+        guard: impl Fn(&W, &S, &S) -> bool + 'static,
+    ) {
+        self.transitions
+            .push((from.into(), to.into(), Box::new(guard)));
+    }
+
+    /// Adds an action to invoke whenever `state` is entered.
+    pub fn on_enter(&mut self, state: S, action: impl FnMut(&mut W, &mut Aux<T>) + 'static) {
+        self.entry.push((state, Box::new(action)));
+    }
+
+    /// Adds an action to invoke whenever `state` is exited.
+    pub fn on_exit(&mut self, state: S, action: impl FnMut(&mut W, &mut Aux<T>) + 'static) {
+        self.exit.push((state, Box::new(action)));
+    }
+
+    fn permits(&self, obj: &W, to: &S) -> bool {
+        self.transitions.is_empty()
+            || self.transitions.iter().any(|(from, target, guard)| {
+                from.as_ref().map(|x| *x == self.state).unwrap_or(true)
+                    && target.as_ref().map(|x| x == to).unwrap_or(true)
+                    && guard(obj, &self.state, to)
+            })
+    }
+}
+
+impl<T: 'static, W: WidgetChildren<T>, S: Clone + PartialEq + 'static> Component
+    for StateMachine<T, W, S>
+{
+    type Type = T;
+    type Object = W;
+
+    fn update(&mut self, obj: &mut W, aux: &mut Aux<T>) {
+        for to in std::mem::take(&mut self.pending) {
+            if to == self.state || !self.permits(obj, &to) {
+                continue;
+            }
+
+            for (state, action) in &mut self.exit {
+                if *state == self.state {
+                    action(obj, aux);
+                }
+            }
+
+            let from = std::mem::replace(&mut self.state, to.clone());
+
+            for (state, action) in &mut self.entry {
+                if *state == to {
+                    action(obj, aux);
+                }
+            }
+
+            obj.repaint();
+            obj.emit(aux, StateChangedEvent { from, to });
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Visibility {
     /// The widget and it's children are visible to rendering and layout.
@@ -768,14 +1503,23 @@ impl Default for Visibility {
 pub struct Common {
     pub(crate) layout: Option<layout::DynamicNode>,
     layout_mode: LayoutMode,
+    pub(crate) layout_dirty: bool,
     visible: Visibility,
     updates: bool,
     rect: gfx::Rect,
+    rect_change: Option<gfx::Rect>,
+    preferred_size: Option<gfx::Size>,
+    baseline: Option<f32>,
+    tooltip: Option<String>,
+    cursor: Option<CursorIcon>,
+    classes: Vec<String>,
+    theme: Option<Box<dyn std::any::Any>>,
     parent: Option<Weak<Cell<Option<Common>>>>,
     cmds: CommandGroup,
     id: u64,
     info: Option<Box<dyn std::any::Any>>,
     should_detach: bool,
+    redraw: bool,
 }
 
 impl Common {
@@ -796,20 +1540,36 @@ impl Common {
         Common {
             layout: None,
             layout_mode: Default::default(),
+            // a new widget has been inserted, which the layouts around it have to account for.
+            layout_dirty: true,
             visible: Default::default(),
             updates: true,
             rect: Default::default(),
+            rect_change: None,
+            preferred_size: None,
+            baseline: None,
+            tooltip: None,
+            cursor: None,
+            classes: Vec::new(),
+            theme: None,
             parent: parent.into().map(|x| Rc::downgrade(x.get_rc())),
             cmds: Default::default(),
             id: uniq::id::next(),
             info: info.into(),
             should_detach: false,
+            redraw: false,
         }
     }
 
     /// Changes the widget rectangle.
+    ///
+    /// A [`RectChangedEvent`](RectChangedEvent) will be emitted when the widget is next updated.
     #[inline(always)]
     pub fn set_rect(&mut self, rect: gfx::Rect) {
+        self.record_rect_change();
+        if rect.size != self.rect.size {
+            self.invalidate_layout();
+        }
         self.rect = rect;
         self.repaint();
         self.update_layout_size();
@@ -822,8 +1582,18 @@ impl Common {
     }
 
     /// Changes the widget rectangle size.
+    ///
+    /// If the widget has a [preferred size](Common::set_preferred_size), it becomes `size` as well, so that layouts keep the widget at the size given here.
+    /// (Layouts themselves place widgets through [`set_rect`](Common::set_rect), which leaves the preferred size alone.)
     #[inline]
     pub fn set_size(&mut self, size: gfx::Size) {
+        self.record_rect_change();
+        if size != self.rect.size {
+            self.invalidate_layout();
+        }
+        if self.preferred_size.is_some() {
+            self.preferred_size = Some(size);
+        }
         self.rect.size = size;
         self.repaint();
         self.update_layout_size();
@@ -835,9 +1605,126 @@ impl Common {
         self.rect.size
     }
 
+    /// Changes the size the widget would like to be given by layouts, independent of its current size.
+    ///
+    /// If `None`, layouts will use the current widget size instead.
+    #[inline]
+    pub fn set_preferred_size(&mut self, size: impl Into<Option<gfx::Size>>) {
+        let size = size.into();
+        if size != self.preferred_size {
+            self.invalidate_layout();
+        }
+        self.preferred_size = size;
+    }
+
+    /// Returns the preferred size, if one has been set.
+    #[inline]
+    pub fn preferred_size(&self) -> Option<gfx::Size> {
+        self.preferred_size
+    }
+
+    /// Changes the distance from the top of the widget to the baseline of its text, used by layouts to
+    /// [align text](layout::Alignment::Baseline) across widgets.
+    ///
+    /// `None` (the default) means the widget has no text.
+    #[inline]
+    pub fn set_baseline(&mut self, baseline: impl Into<Option<f32>>) {
+        let baseline = baseline.into();
+        if baseline != self.baseline {
+            self.invalidate_layout();
+        }
+        self.baseline = baseline;
+    }
+
+    /// Returns the distance from the top of the widget to the baseline of its text, if it has any.
+    #[inline]
+    pub fn baseline(&self) -> Option<f32> {
+        self.baseline
+    }
+
+    /// Changes the text shown in a tooltip when the cursor rests over the widget.
+    ///
+    /// If `None`, no tooltip is shown.
+    #[inline]
+    pub fn set_tooltip(&mut self, tooltip: impl Into<Option<String>>) {
+        self.tooltip = tooltip.into();
+    }
+
+    /// Returns the tooltip text, if there is any.
+    #[inline]
+    pub fn tooltip(&self) -> Option<&str> {
+        self.tooltip.as_deref()
+    }
+
+    /// Changes the shape of the mouse cursor while it's over the widget (and not over a child with a cursor of its own).
+    ///
+    /// If `None`, the cursor of the parent is used.
+    #[inline]
+    pub fn set_cursor(&mut self, cursor: impl Into<Option<CursorIcon>>) {
+        self.cursor = cursor.into();
+    }
+
+    #[inline]
+    pub fn cursor(&self) -> Option<CursorIcon> {
+        self.cursor
+    }
+
+    /// Attaches a style class (e.g. `"danger"`) to the widget, which the theme can use to resolve its colors and painter
+    /// (see [`Theme::class_color`](Theme::class_color) and [`Theme::class_painter`](Theme::class_painter)).
+    ///
+    /// Classes added later take precedence over earlier ones. Unlike theme overrides, classes don't apply to children.
+    pub fn add_class(&mut self, class: impl Into<String>) {
+        let class = class.into();
+        if !self.has_class(&class) {
+            self.classes.push(class);
+            self.repaint();
+        }
+    }
+
+    /// Detaches a style class, returning `false` if the widget didn't have it.
+    pub fn remove_class(&mut self, class: &str) -> bool {
+        let len = self.classes.len();
+        self.classes.retain(|x| x != class);
+        if self.classes.len() != len {
+            self.repaint();
+            true
+        } else {
+            false
+        }
+    }
+
+    #[inline]
+    pub fn has_class(&self, class: &str) -> bool {
+        self.classes.iter().any(|x| x == class)
+    }
+
+    /// Returns the style classes, in the order they were added.
+    #[inline]
+    pub fn classes(&self) -> &[String] {
+        &self.classes
+    }
+
+    /// Overrides the theme used by this widget and its children while they update and draw.
+    ///
+    /// Painters are retrieved when widgets are created, so the subtree should also be created
+    /// under the same theme (see [`with_theme`](with_theme)).
+    pub fn set_theme<T: 'static>(&mut self, theme: Option<Rc<dyn Theme<T>>>) {
+        self.theme = theme.map(|x| Box::new(x) as Box<dyn std::any::Any>);
+        self.repaint();
+    }
+
+    /// Returns the theme override, if there is one.
+    pub fn theme<T: 'static>(&self) -> Option<Rc<dyn Theme<T>>> {
+        self.theme
+            .as_ref()
+            .and_then(|x| x.downcast_ref::<Rc<dyn Theme<T>>>())
+            .cloned()
+    }
+
     /// Changes the widget rectangle position.
     #[inline]
     pub fn set_position(&mut self, position: gfx::Point) {
+        self.record_rect_change();
         self.rect.origin = position;
         self.repaint();
     }
@@ -877,6 +1764,9 @@ impl Common {
     /// If `false`, this widget will be excluded from rendering.
     #[inline]
     pub fn set_visible(&mut self, visible: Visibility) {
+        if visible != self.visible {
+            self.invalidate_layout();
+        }
         self.visible = visible;
     }
 
@@ -923,6 +1813,14 @@ impl Common {
         self.command_group().repaint();
     }
 
+    /// Flags a repaint once the widget has finished drawing, so that it's drawn again next frame.
+    ///
+    /// Unlike [`repaint`](Common::repaint), this can be invoked while drawing (e.g. by a painter which is animating).
+    #[inline]
+    pub fn request_redraw(&mut self) {
+        self.redraw = true;
+    }
+
     /// Emits an event to the global queue on the behalf of [`id`](Common::id).
     #[inline]
     pub fn emit<T: 'static, E: 'static>(&self, aux: &mut Aux<T>, event: E) {
@@ -977,14 +1875,40 @@ impl Common {
     #[inline]
     pub fn set_layout<L: layout::Layout>(&mut self, layout: impl Into<Option<layout::Node<L>>>) {
         self.layout = layout.into().map(|x| layout::DynamicNode(Box::new(x)));
+        self.invalidate_layout();
+    }
+
+    /// Changes the widget's layout to one whose type isn't known statically (e.g. one [loaded from a description](layout::from_spec)).
+    #[inline]
+    pub fn set_dynamic_layout(&mut self, layout: impl Into<Option<layout::DynamicNode>>) {
+        self.layout = layout.into();
+        self.invalidate_layout();
     }
 
     /// Returns the widget's layout, if any.
+    ///
+    /// Since the layout may be changed through this (e.g. by pushing items), it will be [invalidated](Common::invalidate_layout).
     #[inline]
     pub fn layout_mut(&mut self) -> Option<&mut layout::DynamicNode> {
+        self.invalidate_layout();
         self.layout.as_mut()
     }
 
+    /// Marks the layouts affected by this widget as needing to be updated, which is done by
+    /// [`update_dirty_layouts`](layout::update_dirty_layouts) (invoked by `app` after every update).
+    ///
+    /// This is already done when the size, preferred size, visibility or layout of the widget changes.
+    #[inline]
+    pub fn invalidate_layout(&mut self) {
+        self.layout_dirty = true;
+    }
+
+    /// Returns `true` if the widget has been [invalidated](Common::invalidate_layout) since its layouts were last updated.
+    #[inline]
+    pub fn layout_dirty(&self) -> bool {
+        self.layout_dirty
+    }
+
     pub fn set_layout_mode(&mut self, mode: LayoutMode) {
         self.layout_mode = mode;
         self.update_layout_size();
@@ -1005,6 +1929,20 @@ impl Common {
         self.should_detach
     }
 
+    /// Remembers the rectangle prior to the first change since the last [`RectChangedEvent`](RectChangedEvent).
+    #[inline]
+    fn record_rect_change(&mut self) {
+        self.rect_change.get_or_insert(self.rect);
+    }
+
+    /// Returns the rectangle change since this was last invoked, as `(old, new)`, if the rectangle is different.
+    pub fn take_rect_change(&mut self) -> Option<(gfx::Rect, gfx::Rect)> {
+        self.rect_change
+            .take()
+            .filter(|&old| old != self.rect)
+            .map(|old| (old, self.rect))
+    }
+
     fn update_layout_size(&mut self) {
         let size = self.size();
         let mut layout_size = None;
@@ -1016,6 +1954,7 @@ impl Common {
             }
         }
         if let Some(size) = layout_size {
+            self.record_rect_change();
             self.rect.size = size;
         }
     }
@@ -1032,11 +1971,55 @@ impl Id for Common {
 
 /// Recursively propagate the `update` method.
 pub fn propagate_update<T: 'static>(widget: &mut dyn WidgetChildren<T>, aux: &mut Aux<T>) {
+    if let Some(theme) = widget.common().with(|x| x.theme::<T>()) {
+        return with_theme(aux, theme, |aux| propagate_update_impl(widget, aux));
+    }
+
+    propagate_update_impl(widget, aux);
+}
+
+fn propagate_update_impl<T: 'static>(widget: &mut dyn WidgetChildren<T>, aux: &mut Aux<T>) {
+    widget.common().with(|x| {
+        if let Some((old, new)) = x.take_rect_change() {
+            x.emit(aux, RectChangedEvent { old, new });
+        }
+    });
+
     for child in widget.children_mut().into_iter().rev() {
         propagate_update(child, aux);
     }
 
-    widget.update(aux);
+    with_classes(widget.common(), aux, |aux| widget.update(aux));
+}
+
+/// Invokes `f` with `theme` temporarily replacing the theme in `aux`.
+///
+/// This is used for [theme overrides](Common::set_theme), and should also be used to create the widgets within them.
+pub fn with_theme<T: 'static, R>(
+    aux: &mut Aux<T>,
+    theme: Rc<dyn Theme<T>>,
+    f: impl FnOnce(&mut Aux<T>) -> R,
+) -> R {
+    let old = std::mem::replace(&mut aux.theme, Box::new(crate::theme::SharedTheme(theme)));
+    // painters of the outer theme mustn't be shared with widgets under the override.
+    let painters = std::mem::take(&mut aux.painters);
+    let out = f(aux);
+    aux.theme = old;
+    aux.painters = painters;
+    out
+}
+
+/// Invokes `f` with the [style classes](Common::add_class) of `common` as the current classes of `aux`.
+fn with_classes<T: 'static, R>(
+    common: &CommonRef,
+    aux: &mut Aux<T>,
+    f: impl FnOnce(&mut Aux<T>) -> R,
+) -> R {
+    let classes = common.with(|x| x.classes().to_vec());
+    let old = std::mem::replace(&mut aux.classes, classes);
+    let out = f(aux);
+    aux.classes = old;
+    out
 }
 
 /// Recursively propagate the `draw` method.
@@ -1044,11 +2027,23 @@ pub fn propagate_draw<T: 'static>(
     widget: &mut dyn WidgetChildren<T>,
     display: &mut dyn gfx::GraphicsDisplay,
     aux: &mut Aux<T>,
+) {
+    if let Some(theme) = widget.common().with(|x| x.theme::<T>()) {
+        return with_theme(aux, theme, |aux| propagate_draw_impl(widget, display, aux));
+    }
+
+    propagate_draw_impl(widget, display, aux);
+}
+
+fn propagate_draw_impl<T: 'static>(
+    widget: &mut dyn WidgetChildren<T>,
+    display: &mut dyn gfx::GraphicsDisplay,
+    aux: &mut Aux<T>,
 ) {
     let v = widget.visible();
 
     if v != Visibility::NoSelf && v != Visibility::Invisible && v != Visibility::None {
-        widget.draw(display, aux);
+        with_classes(widget.common(), aux, |aux| widget.draw(display, aux));
     }
 
     if v != Visibility::NoChildren && v != Visibility::Invisible && v != Visibility::None {
@@ -1058,6 +2053,61 @@ pub fn propagate_draw<T: 'static>(
     }
 }
 
+/// Returns the ID and tooltip of the top-most visible widget under `point` (in absolute coordinates) which has a tooltip.
+pub fn tooltip_at<T: 'static>(
+    widget: &dyn WidgetChildren<T>,
+    point: gfx::Point,
+) -> Option<(u64, String)> {
+    let v = widget.visible();
+    if v == Visibility::Invisible || v == Visibility::None {
+        return None;
+    }
+
+    if v != Visibility::NoChildren {
+        // children are drawn after (and thus above) their parent, and later siblings above earlier ones.
+        for child in widget.children().into_iter().rev() {
+            if let Some(tooltip) = tooltip_at(child, point) {
+                return Some(tooltip);
+            }
+        }
+    }
+
+    widget.common().with(|x| {
+        if v != Visibility::NoSelf && x.absolute_rect().contains(point) {
+            x.tooltip().map(|tooltip| (x.id(), tooltip.to_string()))
+        } else {
+            None
+        }
+    })
+}
+
+/// Returns the [cursor](Common::set_cursor) of the top-most visible widget under `point` (in absolute coordinates) which has one.
+pub fn cursor_at<T: 'static>(
+    widget: &dyn WidgetChildren<T>,
+    point: gfx::Point,
+) -> Option<CursorIcon> {
+    let v = widget.visible();
+    if v == Visibility::Invisible || v == Visibility::None {
+        return None;
+    }
+
+    if v != Visibility::NoChildren {
+        for child in widget.children().into_iter().rev() {
+            if let Some(cursor) = cursor_at(child, point) {
+                return Some(cursor);
+            }
+        }
+    }
+
+    widget.common().with(|x| {
+        if v != Visibility::NoSelf && x.absolute_rect().contains(point) {
+            x.cursor()
+        } else {
+            None
+        }
+    })
+}
+
 pub trait Id {
     fn id(&self) -> u64;
 }
@@ -1295,7 +2345,12 @@ pub fn draw<T: 'static, W: WidgetChildren<T>>(
         None,
     );
 
-    obj.common().with(|x| x.command_group().0 = Some(cmds));
+    obj.common().with(|x| {
+        x.command_group().0 = Some(cmds);
+        if std::mem::replace(&mut x.redraw, false) {
+            x.repaint();
+        }
+    });
 }
 
 /// Propagates the repaint flag to children of a widget if it is set.
@@ -1308,7 +2363,7 @@ pub fn propagate_repaint<T: 'static>(widget: &impl WidgetChildren<T>) {
 }
 
 /// Keyboard modifier keys state.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct KeyModifiers {
     pub shift: bool,
     pub ctrl: bool,
@@ -1348,6 +2403,61 @@ pub trait ElementMixin: Element {
         self.common().with(|x| x.size())
     }
 
+    #[inline]
+    fn set_preferred_size(&self, size: impl Into<Option<gfx::Size>>) {
+        let size = size.into();
+        self.common().with(|x| x.set_preferred_size(size));
+    }
+
+    #[inline]
+    fn preferred_size(&self) -> Option<gfx::Size> {
+        self.common().with(|x| x.preferred_size())
+    }
+
+    #[inline]
+    fn set_tooltip(&self, tooltip: impl Into<Option<String>>) {
+        let tooltip = tooltip.into();
+        self.common().with(|x| x.set_tooltip(tooltip));
+    }
+
+    #[inline]
+    fn tooltip(&self) -> Option<String> {
+        self.common().with(|x| x.tooltip().map(String::from))
+    }
+
+    /// Changes the [cursor](Common::set_cursor) of the widget.
+    #[inline]
+    fn set_cursor_icon(&self, cursor: impl Into<Option<CursorIcon>>) {
+        let cursor = cursor.into();
+        self.common().with(|x| x.set_cursor(cursor));
+    }
+
+    #[inline]
+    fn cursor_icon(&self) -> Option<CursorIcon> {
+        self.common().with(|x| x.cursor())
+    }
+
+    #[inline]
+    fn add_class(&self, class: impl Into<String>) {
+        let class = class.into();
+        self.common().with(|x| x.add_class(class));
+    }
+
+    #[inline]
+    fn remove_class(&self, class: &str) -> bool {
+        self.common().with(|x| x.remove_class(class))
+    }
+
+    #[inline]
+    fn has_class(&self, class: &str) -> bool {
+        self.common().with(|x| x.has_class(class))
+    }
+
+    #[inline]
+    fn set_theme(&self, theme: Option<Rc<dyn Theme<Self::Aux>>>) {
+        self.common().with(|x| x.set_theme(theme));
+    }
+
     #[inline]
     fn set_position(&self, position: gfx::Point) {
         self.common().with(|x| x.set_position(position));
@@ -1,4 +1,7 @@
-use {super::*, std::collections::BTreeMap};
+use {
+    super::*,
+    std::collections::{BTreeMap, HashMap},
+};
 
 /// Holds a strongly-typed ID of a child within a view.
 #[derive(Derivative)]
@@ -42,6 +45,7 @@ pub struct View<T: 'static, S: 'static> {
     state: S,
     next_child: u64,
     children: BTreeMap<u64, Box<AuxWidgetChildren<T>>>,
+    z_order: HashMap<u64, i32>,
     state_changed: Option<Vec<StateChangedCallback<Self>>>,
     common: CommonRef,
     listener: Listener<(Write<Self>, Write<Aux<T>>)>,
@@ -54,6 +58,7 @@ impl<T: 'static, S: 'static> View<T, S> {
             state,
             next_child: 0,
             children: BTreeMap::new(),
+            z_order: HashMap::new(),
             state_changed: Some(Vec::new()),
             common: CommonRef::new(parent),
             listener: aux.listen(),
@@ -105,6 +110,7 @@ impl<T: 'static, S: 'static> View<T, S> {
 
     /// Removes a child widget.
     pub fn remove<W: WidgetChildren<T> + 'static>(&mut self, child: ChildRef<W>) -> Option<W> {
+        self.z_order.remove(&child.0);
         self.children
             .remove(&child.0)
             .map(|x| *x.as_any_box().downcast::<W>().unwrap())
@@ -115,6 +121,46 @@ impl<T: 'static, S: 'static> View<T, S> {
         self.children.contains_key(&child.0)
     }
 
+    /// Changes the Z-order of a child widget.
+    ///
+    /// Children are drawn from lowest to highest Z (ties are broken by insertion order),
+    /// and receive input in the reverse order, so that a child drawn on top also gets first pick of events.
+    /// All children start at a Z of `0`.
+    pub fn set_z<W: WidgetChildren<T> + 'static>(&mut self, child: ChildRef<W>, z: i32) {
+        if self.children.contains_key(&child.0) {
+            self.z_order.insert(child.0, z);
+            self.repaint();
+        }
+    }
+
+    /// Returns the Z-order of a child widget.
+    pub fn z<W: WidgetChildren<T> + 'static>(&self, child: ChildRef<W>) -> Option<i32> {
+        if self.children.contains_key(&child.0) {
+            Some(self.z_order.get(&child.0).copied().unwrap_or(0))
+        } else {
+            None
+        }
+    }
+
+    /// Brings a child widget in front of all the other children.
+    pub fn raise<W: WidgetChildren<T> + 'static>(&mut self, child: ChildRef<W>) {
+        let top = self.z_order.values().copied().max().unwrap_or(0);
+        self.set_z(child, top + 1);
+    }
+
+    /// Sends a child widget behind all the other children.
+    pub fn lower<W: WidgetChildren<T> + 'static>(&mut self, child: ChildRef<W>) {
+        let bottom = self.z_order.values().copied().min().unwrap_or(0);
+        self.set_z(child, bottom - 1);
+    }
+
+    /// Returns the child keys, from back to front.
+    fn ordered_keys(&self) -> Vec<u64> {
+        let mut keys: Vec<u64> = self.children.keys().copied().collect();
+        keys.sort_by_key(|k| (self.z_order.get(k).copied().unwrap_or(0), *k));
+        keys
+    }
+
     /// Handles an event from a child node.
     pub fn handle<W: WidgetChildren<T> + 'static, Eo: 'static>(
         &mut self,
@@ -194,11 +240,30 @@ impl<T: 'static, S: 'static> View<T, S> {
 
 impl<T: 'static, S: 'static> WidgetChildren<T> for View<T, S> {
     fn children(&self) -> Vec<&dyn WidgetChildren<T>> {
-        self.children.values().map(|x| &**x).collect()
+        if self.z_order.is_empty() {
+            return self.children.values().map(|x| &**x).collect();
+        }
+
+        self.ordered_keys()
+            .into_iter()
+            .map(|k| &*self.children[&k])
+            .collect()
     }
 
     fn children_mut(&mut self) -> Vec<&mut dyn WidgetChildren<T>> {
-        self.children.values_mut().map(|x| &mut **x).collect()
+        if self.z_order.is_empty() {
+            return self.children.values_mut().map(|x| &mut **x).collect();
+        }
+
+        let keys = self.ordered_keys();
+        let mut children: HashMap<u64, &mut dyn WidgetChildren<T>> = self
+            .children
+            .iter_mut()
+            .map(|(k, x)| (*k, &mut **x as &mut dyn WidgetChildren<T>))
+            .collect();
+        keys.into_iter()
+            .filter_map(|k| children.remove(&k))
+            .collect()
     }
 }
 